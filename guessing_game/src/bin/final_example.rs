@@ -1,35 +1,62 @@
-use rand::Rng;
-use std::cmp::Ordering;
-use std::io;
+use guessing_game::cli::{self, Command, Options};
+use guessing_game::i18n;
+use std::process;
 
 fn main() {
-    println!("Guess the number!");
-
-    let secret_number = rand::thread_rng().gen_range(1, 101);
-
-    loop {
-        println!("Please input your guess.");
-
-        let mut guess = String::new();
+    let options = Options::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(cli::exit_code::USAGE);
+    });
+    if let Command::Scores { top } = options.command {
+        if let Err(err) = cli::print_high_scores(top) {
+            eprintln!("{}", i18n::fill("error.high-scores", &[("error", &err)]));
+        }
+        return;
+    }
+    if let Command::Stats = options.command {
+        if let Err(err) = cli::print_stats(options.name.as_deref()) {
+            eprintln!("{}", i18n::fill("error.stats", &[("error", &err)]));
+        }
+        return;
+    }
+    if let Command::Replay { file, speed, check } = &options.command {
+        process::exit(cli::play_replay(file, *speed, *check));
+    }
+    if let Command::Mastermind { rules, solver } = &options.command {
+        process::exit(cli::play_mastermind(&options, rules, *solver));
+    }
+    if let Command::Wordle { words, hard, daily } = &options.command {
+        process::exit(cli::play_wordle(&options, words, *hard, *daily));
+    }
 
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
+    let mut game = cli::start_game(&options).unwrap_or_else(|err| {
+        eprintln!("{}", i18n::fill("error.resume", &[("error", &err)]));
+        process::exit(cli::exit_code::USAGE);
+    });
 
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => continue,
-        };
+    let range = game.range();
+    println!("{}", i18n::fill("banner", &[("min", range.start()), ("max", range.end())]));
+    if let Some(attempts_left) = game.attempts_left() {
+        println!("{}", i18n::plural("attempts-left", u64::from(attempts_left), &[]));
+    }
+    if let Some(time_limit) = game.time_limit() {
+        println!("{}", i18n::plural("seconds-left", time_limit.as_secs(), &[]));
+    }
 
-        println!("You guessed: {}", guess);
+    if let Err(err) = cli::play(&options, &mut game) {
+        eprintln!("{}", err);
+        process::exit(cli::exit_code::NOT_FINISHED);
+    }
 
-        match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too big!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
-            }
-        }
+    if let Err(err) = cli::record_score(&options, &game) {
+        eprintln!("{}", i18n::fill("error.score", &[("error", &err)]));
+    }
+    if let Err(err) = cli::record_replay(&options, &game) {
+        eprintln!("{}", i18n::fill("error.replay", &[("error", &err)]));
     }
-}
\ No newline at end of file
+    if let Err(err) = cli::record_stats(&options, &game) {
+        eprintln!("{}", i18n::fill("error.record", &[("error", &err)]));
+    }
+
+    process::exit(cli::exit_code(game.status()));
+}
@@ -0,0 +1,88 @@
+use guessing_game::cli::{self, Options};
+use guessing_game::client::{self, Client};
+use guessing_game::i18n;
+use std::io;
+use std::process;
+
+fn main() {
+    let options = Options::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(cli::exit_code::USAGE);
+    });
+
+    let mut client = Client::connect(options.addr()).unwrap_or_else(|err| {
+        eprintln!(
+            "{}",
+            i18n::fill(
+                "client.connect-error",
+                &[("addr", &options.addr()), ("error", &err)]
+            )
+        );
+        process::exit(1);
+    });
+
+    // names are single words in the protocol
+    let name = options
+        .player_name()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_");
+    if let Some(room) = &options.room {
+        match client.join(room, &name) {
+            Ok(players) => println!(
+                "{}",
+                i18n::plural(
+                    "client.joined",
+                    players as u64,
+                    &[("name", &name), ("room", room)]
+                )
+            ),
+            Err(err) => {
+                eprintln!(
+                    "{}",
+                    i18n::fill("client.join-error", &[("room", room), ("error", &err)])
+                );
+                process::exit(1);
+            }
+        }
+    }
+
+    let range = client.range().clone();
+    println!(
+        "{}",
+        i18n::fill("banner", &[("min", range.start()), ("max", range.end())])
+    );
+    if let Some(max_attempts) = client.max_attempts() {
+        println!(
+            "{}",
+            i18n::plural("attempts-left", u64::from(max_attempts), &[])
+        );
+    }
+
+    if options.room.is_some() {
+        let outcome = options
+            .player(&range)
+            .and_then(|mut player| client::race(client, &name, &mut player, io::stdout()));
+        match outcome {
+            Ok(outcome) => process::exit(cli::race_exit_code(&outcome)),
+            Err(err) => {
+                eprintln!("{}", err);
+                process::exit(cli::exit_code::NOT_FINISHED);
+            }
+        }
+    }
+
+    let status = options
+        .player(&range)
+        .and_then(|mut player| client::play(&mut client, &mut player, &mut io::stdout()));
+    match status {
+        Ok(status) => {
+            let _ = client.quit();
+            process::exit(cli::exit_code(status));
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(cli::exit_code::NOT_FINISHED);
+        }
+    }
+}
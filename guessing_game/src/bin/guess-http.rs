@@ -0,0 +1,31 @@
+use guessing_game::cli::{self, Options};
+use guessing_game::http::{self, HttpServer};
+use guessing_game::i18n;
+use std::process;
+
+fn main() {
+    let options = Options::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(cli::exit_code::USAGE);
+    });
+
+    let addr = options.addr.as_deref().unwrap_or(http::DEFAULT_ADDR);
+    let server = HttpServer::bind(addr, options.rng()).unwrap_or_else(|err| {
+        eprintln!(
+            "{}",
+            i18n::fill("listen-error", &[("addr", &addr), ("error", &err)])
+        );
+        process::exit(1);
+    });
+    let server = server.with_game_ttl(options.idle_timeout.unwrap_or(http::DEFAULT_GAME_TTL));
+    let url = match server.local_addr() {
+        Ok(addr) => format!("http://{}", addr),
+        Err(_) => format!("http://{}", addr),
+    };
+    println!("{}", i18n::fill("listening", &[("addr", &url)]));
+
+    if let Err(err) = server.run() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
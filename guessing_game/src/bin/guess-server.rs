@@ -0,0 +1,31 @@
+use guessing_game::cli::{self, Options};
+use guessing_game::i18n;
+use guessing_game::server::Server;
+use std::process;
+
+fn main() {
+    let options = Options::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(cli::exit_code::USAGE);
+    });
+
+    let config = options.server_config();
+    let server = Server::bind(options.addr(), config, options.rng()).unwrap_or_else(|err| {
+        let addr = options.addr();
+        eprintln!(
+            "{}",
+            i18n::fill("listen-error", &[("addr", &addr), ("error", &err)])
+        );
+        process::exit(1);
+    });
+    let addr = match server.local_addr() {
+        Ok(addr) => addr.to_string(),
+        Err(_) => options.addr().to_string(),
+    };
+    println!("{}", i18n::fill("listening", &[("addr", &addr)]));
+
+    if let Err(err) = server.run() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
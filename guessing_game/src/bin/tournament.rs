@@ -0,0 +1,14 @@
+use guessing_game::cli::{self, Options};
+use std::process;
+
+fn main() {
+    let options = Options::from_env().unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(cli::exit_code::USAGE);
+    });
+
+    if let Err(err) = cli::run_tournament(&options) {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::liar::{self, Candidates};
+use crate::{Attempt, Feedback, Outcome, Player};
+use rand::rngs::StdRng;
+use rand::Rng;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// How a [`Bot`] picks its next guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strategy {
+    /// Always the middle of the numbers still possible: the optimal player.
+    BinarySearch,
+    /// Any number still possible, picked at random.
+    Random,
+    /// The smallest number still possible, one after the other.
+    Linear,
+    /// Roughly the middle, like a person who does not count too carefully.
+    HumanLike,
+}
+
+impl Strategy {
+    pub const ALL: [Strategy; 4] = [
+        Strategy::BinarySearch,
+        Strategy::Random,
+        Strategy::Linear,
+        Strategy::HumanLike,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Strategy::BinarySearch => "binary",
+            Strategy::Random => "random",
+            Strategy::Linear => "linear",
+            Strategy::HumanLike => "human",
+        }
+    }
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The name is not one of the bot [`Strategy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStrategyError(String);
+
+impl fmt::Display for ParseStrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown bot strategy '{}' (expected binary, random, linear or human)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseStrategyError {}
+
+impl FromStr for Strategy {
+    type Err = ParseStrategyError;
+
+    fn from_str(s: &str) -> Result<Strategy, ParseStrategyError> {
+        Strategy::ALL
+            .iter()
+            .copied()
+            .find(|strategy| strategy.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseStrategyError(s.to_string()))
+    }
+}
+
+/// A computer player: it keeps the numbers where the secret number can still
+/// be and guesses among them following its [`Strategy`]. The answers that
+/// hide the direction only narrow them to the distances of their
+/// [temperature](crate::Temperature).
+///
+/// Against a game that can lie, the bot works out the numbers still possible
+/// from the whole history instead, see [`Bot::with_lies`].
+#[derive(Debug, Clone)]
+pub struct Bot {
+    strategy: Strategy,
+    range: RangeInclusive<u32>,
+    max_lies: u32,
+    /// The numbers still possible, in order, without lies.
+    possible: Vec<Candidates>,
+    last_guess: Option<u32>,
+    rng: StdRng,
+}
+
+impl Bot {
+    pub fn new(strategy: Strategy, range: RangeInclusive<u32>, rng: StdRng) -> Bot {
+        Bot {
+            strategy,
+            max_lies: 0,
+            possible: vec![Candidates {
+                low: *range.start(),
+                high: *range.end(),
+                lies: 0,
+            }],
+            range,
+            last_guess: None,
+            rng,
+        }
+    }
+
+    /// Expects up to `max_lies` lies in the answers of the game.
+    pub fn with_lies(mut self, max_lies: u32) -> Bot {
+        self.max_lies = max_lies;
+        self
+    }
+
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Keeps the numbers still possible that are in one of `allowed`.
+    fn keep(&mut self, allowed: &[(u64, u64)]) {
+        let mut possible = Vec::new();
+        for c in &self.possible {
+            for &(low, high) in allowed {
+                let low = low.max(u64::from(c.low));
+                let high = high.min(u64::from(c.high));
+                if low <= high {
+                    possible.push(Candidates {
+                        low: low as u32,
+                        high: high as u32,
+                        lies: 0,
+                    });
+                }
+            }
+        }
+        self.possible = possible;
+    }
+
+    /// The next guess against a game that can lie: the binary search becomes
+    /// Berlekamp's [balanced guess](liar::balanced_guess).
+    fn guess_with_lies(&mut self, history: &[Attempt]) -> Option<u32> {
+        let candidates = liar::candidates(&self.range, history, self.max_lies);
+        let balanced = liar::balanced_guess(&candidates, self.max_lies)?;
+        let (first, last) = (candidates[0].low, candidates[candidates.len() - 1].high);
+        Some(match self.strategy {
+            Strategy::BinarySearch => balanced,
+            Strategy::Random => {
+                let count: u64 = candidates.iter().map(|c| c.count()).sum();
+                nth_candidate(&candidates, self.rng.gen_range(0, count))
+            }
+            // the smallest number that needs the fewest lies
+            Strategy::Linear => {
+                let fewest = candidates.iter().map(|c| c.lies).min()?;
+                candidates.iter().find(|c| c.lies == fewest)?.low
+            }
+            Strategy::HumanLike => {
+                let noise = (u64::from(last) - u64::from(first)) / 4;
+                let (low, high) = (
+                    u64::from(balanced)
+                        .saturating_sub(noise)
+                        .max(u64::from(first)),
+                    (u64::from(balanced) + noise).min(u64::from(last)),
+                );
+                self.rng.gen_range(low, high + 1) as u32
+            }
+        })
+    }
+}
+
+/// The candidate number `n` (from 0), counting all the intervals in order.
+fn nth_candidate(candidates: &[Candidates], mut n: u64) -> u32 {
+    for c in candidates {
+        if n < c.count() {
+            return (u64::from(c.low) + n) as u32;
+        }
+        n -= c.count();
+    }
+    candidates[candidates.len() - 1].high
+}
+
+impl Player for Bot {
+    /// `None` when no number is left: the answers were not consistent.
+    fn next_guess(&mut self, history: &[Attempt]) -> Option<u32> {
+        if self.max_lies > 0 {
+            return self.guess_with_lies(history);
+        }
+        let count: u64 = self.possible.iter().map(|c| c.count()).sum();
+        if count == 0 {
+            return None;
+        }
+        let middle = (count - 1) / 2;
+        let n = match self.strategy {
+            Strategy::BinarySearch => middle,
+            Strategy::Random => self.rng.gen_range(0, count),
+            Strategy::Linear => 0,
+            Strategy::HumanLike => {
+                // up to a quarter of the numbers away from the middle
+                let noise = (count - 1) / 4;
+                self.rng.gen_range(middle - noise, middle + noise + 1)
+            }
+        };
+        let guess = nth_candidate(&self.possible, n);
+        self.last_guess = Some(guess);
+        Some(guess)
+    }
+
+    /// Narrows the numbers still possible with the answer to the last guess:
+    /// by its direction, or else by its temperature.
+    fn observe(&mut self, feedback: Feedback) {
+        let guess = match self.last_guess {
+            Some(guess) => u64::from(guess),
+            None => return,
+        };
+        match (feedback.direction(), feedback) {
+            (Some(Outcome::Win), _) => self.keep(&[(guess, guess)]),
+            (Some(Outcome::TooSmall), _) => self.keep(&[(guess + 1, u64::from(u32::MAX))]),
+            // guess 0 too big: nothing is left
+            (Some(Outcome::TooBig), _) if guess == 0 => self.keep(&[]),
+            (Some(Outcome::TooBig), _) => self.keep(&[(0, guess - 1)]),
+            (None, Feedback::Temperature { temperature, .. }) => {
+                let distances = temperature.distances(&self.range);
+                // not the guess itself, which would have won
+                let (near, far) = ((*distances.start()).max(1), *distances.end());
+                let mut allowed = vec![(guess + near, guess + far)];
+                if let Some(high) = guess.checked_sub(near) {
+                    allowed.insert(0, (guess.saturating_sub(far), high));
+                }
+                self.keep(&allowed);
+            }
+            (None, _) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FeedbackMode, Game, Status, Temperature};
+    use rand::SeedableRng;
+
+    fn bot(strategy: Strategy) -> Bot {
+        Bot::new(strategy, 1..=100, StdRng::seed_from_u64(7))
+    }
+
+    /// Plays `game` with `bot`, which only sees the feedback of the game.
+    fn play(bot: &mut Bot, game: &mut Game) {
+        while !game.is_finished() {
+            let guess = bot.next_guess(game.history()).unwrap();
+            game.guess(guess).unwrap();
+            bot.observe(game.feedback().unwrap());
+        }
+    }
+
+    #[test]
+    fn the_binary_search_halves_the_interval() {
+        let mut bot = bot(Strategy::BinarySearch);
+        assert_eq!(bot.next_guess(&[]), Some(50));
+        bot.observe(Feedback::from(Outcome::TooSmall));
+        assert_eq!(bot.next_guess(&[]), Some(75));
+        bot.observe(Feedback::from(Outcome::TooBig));
+        assert_eq!(bot.next_guess(&[]), Some(62));
+    }
+
+    #[test]
+    fn inconsistent_answers_leave_nothing() {
+        let mut bot = Bot::new(Strategy::Linear, 0..=u32::MAX, StdRng::seed_from_u64(7));
+        assert_eq!(bot.next_guess(&[]), Some(0));
+        bot.observe(Feedback::from(Outcome::TooBig));
+        assert_eq!(bot.next_guess(&[]), None);
+    }
+
+    #[test]
+    fn a_hidden_direction_keeps_both_sides_of_the_guess() {
+        let mut bot = bot(Strategy::BinarySearch);
+        assert_eq!(bot.next_guess(&[]), Some(50));
+        // warm is 6 to 20 away from 50 on 1..=100
+        bot.observe(Feedback::Temperature {
+            temperature: Temperature::Warm,
+            trend: None,
+            direction: None,
+        });
+        let possible: Vec<(u32, u32)> = bot.possible.iter().map(|c| (c.low, c.high)).collect();
+        assert_eq!(possible, vec![(30, 44), (56, 70)]);
+    }
+
+    #[test]
+    fn every_bot_wins_with_the_temperature_only() {
+        for &strategy in Strategy::ALL.iter() {
+            for secret in [1, 2, 37, 50, 99, 100] {
+                let mut game =
+                    Game::with_secret(1..=100, secret).with_feedback(FeedbackMode::TemperatureOnly);
+                let mut bot = bot(strategy);
+                play(&mut bot, &mut game);
+                assert_eq!(game.status(), Status::Won, "{} bot, {}", strategy, secret);
+            }
+        }
+    }
+}
@@ -0,0 +1,1160 @@
+use crate::bot::{Bot, Strategy};
+use crate::client::RaceOutcome;
+use crate::i18n::{self, Lang};
+use crate::input::GuessParser;
+use crate::liar::{Liar, LieStrategy};
+use crate::mastermind::{
+    self, CodeBreaker, CodeGame, CodeRules, CodeRulesError, KnuthBot, StdinCodeBreaker,
+    MAX_SOLVER_CODES,
+};
+use crate::multiplayer::{self, Seat};
+use crate::player::{self, Player, ScriptedPlayer, StdinPlayer};
+use crate::replay::Replay;
+use crate::save::{self, AutoSave};
+use crate::score::{HighScores, ScoreEntry};
+use crate::server::ServerConfig;
+use crate::stats::{self, GameLog, GameRecord};
+use crate::tournament::{Round, Tournament};
+use crate::wordle::{self, WordGame, WordList};
+use crate::{date, Difficulty, FeedbackMode, Game, Loss, Status};
+use rand::rngs::StdRng;
+use rand::{FromEntropy, Rng, SeedableRng};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Environment variable read when `--seed` is not on the command line.
+pub const SEED_VAR: &str = "GUESS_SEED";
+
+/// Where `guess-server` listens and `guess-client` connects without `--addr`.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// Exit codes of the binaries, so that a script can tell how the game ended.
+pub mod exit_code {
+    pub const WON: i32 = 0;
+    /// The input ended before the end of the game.
+    pub const NOT_FINISHED: i32 = 1;
+    pub const USAGE: i32 = 2;
+    pub const OUT_OF_ATTEMPTS: i32 = 3;
+    pub const OUT_OF_TIME: i32 = 4;
+    /// Somebody else won the race.
+    pub const BEATEN: i32 = 5;
+    /// `replay` found that the current rules play the game differently.
+    pub const CHANGED: i32 = 6;
+    pub const GAVE_UP: i32 = 7;
+}
+
+/// The exit code telling how a game ended.
+pub fn exit_code(status: Status) -> i32 {
+    match status {
+        Status::Playing => exit_code::NOT_FINISHED,
+        Status::Won => exit_code::WON,
+        Status::Lost(Loss::OutOfAttempts) => exit_code::OUT_OF_ATTEMPTS,
+        Status::Lost(Loss::OutOfTime) => exit_code::OUT_OF_TIME,
+        Status::Lost(Loss::GaveUp) => exit_code::GAVE_UP,
+    }
+}
+
+/// The exit code telling how a race ended.
+pub fn race_exit_code(outcome: &RaceOutcome) -> i32 {
+    match outcome {
+        RaceOutcome::Won => exit_code::WON,
+        RaceOutcome::Beaten { .. } => exit_code::BEATEN,
+        RaceOutcome::Lost(loss) => exit_code(Status::Lost(*loss)),
+        RaceOutcome::Left => exit_code::NOT_FINISHED,
+    }
+}
+
+/// Something wrong in the command line arguments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgError {
+    MissingValue(String),
+    InvalidValue {
+        name: String,
+        value: String,
+    },
+    Unknown(String),
+    EmptyRange {
+        min: u32,
+        max: u32,
+    },
+    /// `--chooser` without anybody else left to guess.
+    NoGuessers,
+    /// Two options that cannot be used together.
+    Conflict(&'static str, &'static str),
+    /// An option of a feature the binary was built without.
+    NeedsFeature(&'static str, &'static str),
+    /// `--lies` that would allow more attempts than can be counted.
+    TooManyLies(u32),
+    /// `--solver` on rules with more codes than it can keep.
+    TooManyCodes(u64),
+    /// `--length`, `--alphabet` and `--repeats` make no code.
+    CodeRules(CodeRulesError),
+}
+
+impl fmt::Display for ArgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            ArgError::MissingValue(name) => i18n::fill("arg.missing-value", &[("name", name)]),
+            ArgError::InvalidValue { name, value } => {
+                i18n::fill("arg.invalid-value", &[("name", name), ("value", value)])
+            }
+            ArgError::Unknown(arg) => i18n::fill("arg.unknown", &[("arg", arg)]),
+            ArgError::EmptyRange { min, max } => {
+                i18n::fill("arg.empty-range", &[("min", min), ("max", max)])
+            }
+            ArgError::NoGuessers => i18n::text("arg.no-guessers").to_string(),
+            ArgError::Conflict(first, second) => {
+                i18n::fill("arg.conflict", &[("first", first), ("second", second)])
+            }
+            ArgError::NeedsFeature(option, feature) => i18n::fill(
+                "arg.needs-feature",
+                &[("option", option), ("feature", feature)],
+            ),
+            ArgError::TooManyLies(lies) => i18n::fill("arg.too-many-lies", &[("lies", lies)]),
+            ArgError::TooManyCodes(count) => i18n::fill(
+                "arg.too-many-codes",
+                &[("max", &MAX_SOLVER_CODES), ("codes", count)],
+            ),
+            ArgError::CodeRules(err) => i18n::fill("arg.code-rules", &[("error", err)]),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl std::error::Error for ArgError {}
+
+/// What the binary has to do.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Command {
+    #[default]
+    Play,
+    /// `scores`: print the best scores of each difficulty.
+    Scores { top: usize },
+    /// `stats`: print the statistics of the games played, of each player and
+    /// difficulty, or of the `--name` player only.
+    Stats,
+    /// `replay <file>`: play back a recorded game, `speed` times faster (0
+    /// does not wait), or only `check` it against the current rules.
+    Replay {
+        file: PathBuf,
+        speed: u32,
+        check: bool,
+    },
+    /// `mastermind`: guess a code of symbols instead of a number, or let the
+    /// Knuth `solver` guess it.
+    Mastermind { rules: CodeRules, solver: bool },
+    /// `wordle <file>`: guess a word of the list in `file`, in `hard` mode,
+    /// or the `daily` puzzle.
+    Wordle {
+        words: PathBuf,
+        hard: bool,
+        daily: bool,
+    },
+}
+
+/// Options shared by the guessing game binaries.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    pub command: Command,
+    /// Seed of the random generator: the same seed always gives the same secret number.
+    pub seed: Option<u64>,
+    /// Preset chosen with `--difficulty`: normal when missing.
+    pub difficulty: Option<Difficulty>,
+    /// `--min` and `--max` override the bounds of the preset.
+    pub min: Option<u32>,
+    pub max: Option<u32>,
+    /// `--max-attempts`: overrides the limit of the preset, 0 means no limit.
+    pub max_attempts: Option<u32>,
+    /// `--time-limit`, given in seconds.
+    pub time_limit: Option<Duration>,
+    /// `--name`: the player written in the high scores.
+    pub name: Option<String>,
+    /// `--bot`: the computer plays with this strategy.
+    pub bot: Option<Strategy>,
+    /// `--script`: a file with the guesses to play, one per line.
+    pub script: Option<PathBuf>,
+    /// `--players alice,bob`: the players taking turns at the terminal.
+    pub players: Vec<String>,
+    /// `--chooser`: the player who picks the secret number instead of guessing.
+    pub chooser: Option<String>,
+    /// `--addr`: the address of the server, [`DEFAULT_ADDR`] when missing.
+    pub addr: Option<String>,
+    /// `--idle-timeout`, given in seconds: the server closes the connections
+    /// that send nothing for this long, and `guess-http` forgets the games.
+    pub idle_timeout: Option<Duration>,
+    /// `--room`: the race room `guess-client` joins.
+    pub room: Option<String>,
+    /// `--resume`: a saved game to continue instead of a new one.
+    pub resume: Option<PathBuf>,
+    /// `--record`: where to write the replay of the game, besides the one
+    /// of the last game.
+    pub record: Option<PathBuf>,
+    /// `--feedback`: how the guesses are answered, classic by default.
+    pub feedback: FeedbackMode,
+    /// `--lies`: how many answers the game can lie in, 0 by default.
+    pub lies: u32,
+    /// `--lie-strategy`: when the game lies, at random by default.
+    pub lie_strategy: LieStrategy,
+    /// `--evil`: the secret number is not chosen at the start, the game
+    /// answers so that the player needs as many guesses as possible.
+    pub evil: bool,
+    /// `--lenient-input`: the guesses can also be written like `1_000`,
+    /// `0x1F` or `forty two`.
+    pub lenient_input: bool,
+    /// `--lang`: the language of the messages, the one of the locale when
+    /// missing.
+    pub lang: Option<Lang>,
+    /// `--tui`: the full-screen interface, in the binaries built with the
+    /// `tui` feature.
+    pub tui: bool,
+    /// `--games`: the games of each bot in each round of `tournament`.
+    pub games: Option<u32>,
+    /// `--threads`: the threads playing the `tournament`, one per CPU when
+    /// missing.
+    pub threads: Option<usize>,
+    /// `--csv` and `--json`: where `tournament` writes its results.
+    pub csv: Option<PathBuf>,
+    pub json: Option<PathBuf>,
+}
+
+impl Options {
+    /// Reads the options from the process arguments and environment.
+    ///
+    /// When no seed is given a random one is picked, so that every game can
+    /// be played again. The language of the messages is set here, from
+    /// `--lang` or the locale, see [`Lang::from_env`], before the other
+    /// arguments so that their errors are in it too.
+    pub fn from_env() -> Result<Options, ArgError> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        i18n::set_lang(lang_arg(&args).unwrap_or_else(Lang::from_env));
+        let mut options = Options::parse(args, env::var(SEED_VAR).ok())?;
+        options.seed.get_or_insert_with(|| rand::thread_rng().gen());
+        Ok(options)
+    }
+
+    /// Parses `args` (without the program name); `seed_var` is the value of
+    /// [`SEED_VAR`], used when there is no `--seed`.
+    pub fn parse<I>(args: I, seed_var: Option<String>) -> Result<Options, ArgError>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut options = Options::default();
+        if let Some(value) = seed_var {
+            options.seed = Some(parse_value(SEED_VAR, &value)?);
+        }
+
+        let mut scores = false;
+        let mut top = 10;
+        let mut replay = None;
+        let mut speed = 1;
+        let mut check = false;
+        let mut code = false;
+        let (mut length, mut alphabet, mut repeats, mut solver) =
+            (4, mastermind::DIGITS.to_string(), false, false);
+        let mut words = None;
+        let (mut hard, mut daily) = (false, false);
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            // both "--seed 42" and "--seed=42" are accepted
+            let (name, inline_value) = match arg.find('=') {
+                Some(i) if arg.starts_with("--") => {
+                    (arg[..i].to_string(), Some(arg[i + 1..].to_string()))
+                }
+                _ => (arg.clone(), None),
+            };
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ArgError::MissingValue(name.clone()))
+            };
+            match name.as_str() {
+                "--seed" => options.seed = Some(parse_value(&name, &value()?)?),
+                "--difficulty" => options.difficulty = Some(parse_value(&name, &value()?)?),
+                "--min" => options.min = Some(parse_value(&name, &value()?)?),
+                "--max" => options.max = Some(parse_value(&name, &value()?)?),
+                "--max-attempts" => options.max_attempts = Some(parse_value(&name, &value()?)?),
+                "--time-limit" => {
+                    let seconds = parse_value(&name, &value()?)?;
+                    options.time_limit = Some(Duration::from_secs(seconds));
+                }
+                "--name" => options.name = Some(value()?),
+                "--bot" => options.bot = Some(parse_value(&name, &value()?)?),
+                "--script" => options.script = Some(PathBuf::from(value()?)),
+                "--players" => {
+                    options.players = value()?
+                        .split(',')
+                        .map(|player| player.trim().to_string())
+                        .filter(|player| !player.is_empty())
+                        .collect()
+                }
+                "--chooser" => options.chooser = Some(value()?),
+                "--resume" => options.resume = Some(PathBuf::from(value()?)),
+                "--addr" => options.addr = Some(value()?),
+                "--room" => {
+                    let room = value()?;
+                    // the protocol separates words with spaces
+                    if room.is_empty() || room.contains(char::is_whitespace) {
+                        return Err(ArgError::InvalidValue { name, value: room });
+                    }
+                    options.room = Some(room);
+                }
+                "--idle-timeout" => {
+                    let seconds = parse_value(&name, &value()?)?;
+                    options.idle_timeout = Some(Duration::from_secs(seconds));
+                }
+                "--record" => options.record = Some(PathBuf::from(value()?)),
+                "--feedback" => options.feedback = parse_value(&name, &value()?)?,
+                "--lies" => options.lies = parse_value(&name, &value()?)?,
+                "--lie-strategy" => options.lie_strategy = parse_value(&name, &value()?)?,
+                "--top" => top = parse_value(&name, &value()?)?,
+                "--speed" => speed = parse_value(&name, &value()?)?,
+                "--check" => check = true,
+                "--evil" => options.evil = true,
+                "--tui" => options.tui = true,
+                "--lang" => options.lang = Some(parse_value(&name, &value()?)?),
+                "--lenient-input" => options.lenient_input = true,
+                "--games" => options.games = Some(parse_value(&name, &value()?)?),
+                "--threads" => options.threads = Some(parse_value(&name, &value()?)?),
+                "--csv" => options.csv = Some(PathBuf::from(value()?)),
+                "--json" => options.json = Some(PathBuf::from(value()?)),
+                "--length" => length = parse_value(&name, &value()?)?,
+                "--alphabet" => alphabet = value()?,
+                "--repeats" => repeats = true,
+                "--solver" => solver = true,
+                "mastermind" => code = true,
+                "--hard" => hard = true,
+                "--daily" => daily = true,
+                "wordle" => words = Some(PathBuf::from(value()?)),
+                "scores" => scores = true,
+                "stats" => options.command = Command::Stats,
+                "replay" => replay = Some(PathBuf::from(value()?)),
+                _ => return Err(ArgError::Unknown(arg)),
+            }
+        }
+        if scores {
+            options.command = Command::Scores { top };
+        }
+        if let Some(file) = replay {
+            options.command = Command::Replay { file, speed, check };
+        }
+        if code {
+            let rules = CodeRules::new(length, &alphabet, repeats).map_err(ArgError::CodeRules)?;
+            if solver && rules.count() > MAX_SOLVER_CODES {
+                return Err(ArgError::TooManyCodes(rules.count()));
+            }
+            options.command = Command::Mastermind { rules, solver };
+        }
+        if let Some(words) = words {
+            options.command = Command::Wordle { words, hard, daily };
+        }
+        if options.chooser.is_some() && options.guessers().is_empty() {
+            return Err(ArgError::NoGuessers);
+        }
+        // the hot-seat game is built from the options
+        if options.resume.is_some() && !options.players.is_empty() {
+            return Err(ArgError::Conflict("--resume", "--players"));
+        }
+        // the evil secret has no number to choose, or to measure the temperature from
+        if options.evil && options.chooser.is_some() {
+            return Err(ArgError::Conflict("--evil", "--chooser"));
+        }
+        if options.evil && options.feedback != FeedbackMode::Classic {
+            return Err(ArgError::Conflict("--evil", "--feedback"));
+        }
+        // the full screen is for the player at the terminal
+        if cfg!(not(feature = "tui")) && options.tui {
+            return Err(ArgError::NeedsFeature("--tui", "tui"));
+        }
+        let others = [
+            ("--bot", options.bot.is_some()),
+            ("--script", options.script.is_some()),
+            ("--players", !options.players.is_empty()),
+        ];
+        if let Some((other, _)) = others.iter().find(|(_, used)| options.tui && *used) {
+            return Err(ArgError::Conflict("--tui", other));
+        }
+
+        let range = options.range();
+        if range.is_empty() {
+            return Err(ArgError::EmptyRange {
+                min: *range.start(),
+                max: *range.end(),
+            });
+        }
+        options.checked_attempt_limit()?;
+        Ok(options)
+    }
+
+    /// Range of the secret number.
+    pub fn range(&self) -> RangeInclusive<u32> {
+        let preset = self.difficulty.unwrap_or(Difficulty::Normal).range();
+        self.min.unwrap_or(*preset.start())..=self.max.unwrap_or(*preset.end())
+    }
+
+    /// Attempts allowed: `--max-attempts`, then the limit of the chosen
+    /// difficulty, otherwise what a binary search needs on the range; the
+    /// last two twice when the answers hide the direction. Each lie the game
+    /// can tell adds the attempts of a binary search, except to
+    /// `--max-attempts`.
+    ///
+    /// At most `u32::MAX`: [`Options::parse`] refuses the `--lies` that need
+    /// more.
+    pub fn attempt_limit(&self) -> Option<u32> {
+        self.checked_attempt_limit().unwrap_or(Some(u32::MAX))
+    }
+
+    fn checked_attempt_limit(&self) -> Result<Option<u32>, ArgError> {
+        let bits = Game::default_max_attempts(&self.range());
+        let limit = match (self.max_attempts, self.difficulty) {
+            (Some(0), _) => return Ok(None),
+            (Some(max_attempts), _) => return Ok(Some(max_attempts)),
+            (None, Some(difficulty)) => difficulty.max_attempts(),
+            (None, None) => bits,
+        };
+        let limit = if self.feedback.shows_direction() {
+            limit
+        } else {
+            2 * limit
+        };
+        self.lies
+            .checked_mul(bits)
+            .and_then(|extra| limit.checked_add(extra))
+            .map(Some)
+            .ok_or(ArgError::TooManyLies(self.lies))
+    }
+
+    /// A new game with the range and the limits of these options.
+    pub fn new_game(&self) -> Game {
+        let mut rng = self.rng();
+        // the secret number comes first, so that a seed picks the same one with lies
+        let game = if self.evil {
+            Game::evil(self.range())
+        } else {
+            Game::new(self.range(), &mut rng)
+        };
+        game.with_max_attempts(self.attempt_limit())
+            .with_time_limit(self.time_limit)
+            .with_feedback(self.feedback)
+            .with_liar(self.liar(&mut rng))
+            .with_seed(self.seed)
+    }
+
+    /// The liar of `--lies` and `--lie-strategy`, seeded with `rng`.
+    pub fn liar<R: Rng>(&self, rng: &mut R) -> Option<Liar> {
+        Some(self.lies)
+            .filter(|&lies| lies > 0)
+            .map(|lies| Liar::new(lies, self.lie_strategy, rng.gen()))
+    }
+
+    /// The player chosen by the options for a game on `range`: a bot with
+    /// `--bot`, the guesses of a file with `--script`, otherwise the terminal.
+    pub fn player(&self, range: &RangeInclusive<u32>) -> io::Result<Box<dyn Player>> {
+        Ok(match (self.bot, &self.script) {
+            (Some(strategy), _) => {
+                let lies = bot_lies(self.lies, self.feedback);
+                Box::new(Bot::new(strategy, range.clone(), self.bot_rng()).with_lies(lies))
+            }
+            (None, Some(path)) => Box::new(ScriptedPlayer::from_file(path)?),
+            (None, None) => {
+                Box::new(StdinPlayer::new().with_parser(self.guess_parser(range, self.lies > 0)))
+            }
+        })
+    }
+
+    /// How the guesses typed at the terminal are read: with
+    /// `--lenient-input` they can also be `1_000`, `0x1F` or `forty two`.
+    /// A guess played again is refused, unless the game `lies`.
+    pub fn guess_parser(&self, range: &RangeInclusive<u32>, lies: bool) -> GuessParser {
+        GuessParser::new()
+            .with_lenient(self.lenient_input)
+            .with_range(range.clone())
+            .with_refused_duplicates(!lies)
+    }
+
+    pub fn addr(&self) -> &str {
+        self.addr.as_deref().unwrap_or(DEFAULT_ADDR)
+    }
+
+    /// The games of `guess-server`; connections are idle after 5 minutes
+    /// without `--idle-timeout`.
+    pub fn server_config(&self) -> ServerConfig {
+        ServerConfig {
+            range: self.range(),
+            max_attempts: self.attempt_limit(),
+            time_limit: self.time_limit,
+            idle_timeout: self
+                .idle_timeout
+                .unwrap_or_else(|| Duration::from_secs(300)),
+        }
+    }
+
+    /// The name of the difficulty of a game on `range` used in the high
+    /// scores: `custom MIN-MAX` when the range is not the one of a preset.
+    pub fn difficulty_label(&self, range: &RangeInclusive<u32>) -> String {
+        let difficulty = self.difficulty.unwrap_or(Difficulty::Normal);
+        if *range == difficulty.range() {
+            difficulty.name().to_string()
+        } else {
+            format!("custom {}-{}", range.start(), range.end())
+        }
+    }
+
+    /// `--name`, otherwise the user logged in.
+    pub fn player_name(&self) -> String {
+        self.name
+            .clone()
+            .or_else(|| env::var("USER").ok())
+            .or_else(|| env::var("USERNAME").ok())
+            .unwrap_or_else(|| "player".to_string())
+    }
+
+    /// The players guessing in a multiplayer game: everyone in `--players`
+    /// but the `--chooser`.
+    pub fn guessers(&self) -> Vec<&str> {
+        self.players
+            .iter()
+            .map(String::as_str)
+            .filter(|player| Some(*player) != self.chooser.as_deref())
+            .collect()
+    }
+
+    /// The random generator to use: seeded with [`Options::seed`] if there is
+    /// one, otherwise seeded by the operating system.
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
+    }
+
+    /// The random generator of the bots: it comes from the same seed, but
+    /// it does not follow the one that picked the secret number.
+    pub fn bot_rng(&self) -> StdRng {
+        StdRng::from_rng(self.rng()).expect("StdRng cannot fail to seed from StdRng")
+    }
+}
+
+fn parse_value<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, ArgError> {
+    value.trim().parse().map_err(|_| ArgError::InvalidValue {
+        name: name.to_string(),
+        value: value.to_string(),
+    })
+}
+
+/// Lets the player chosen by the options play `game`, see [`Options::player`];
+/// with `--players` it is a hot-seat game.
+pub fn play(options: &Options, game: &mut Game) -> io::Result<()> {
+    match game.feedback_mode() {
+        FeedbackMode::Classic => {}
+        FeedbackMode::Temperature => println!("{}", i18n::text("intro.temperature")),
+        FeedbackMode::TemperatureOnly => println!("{}", i18n::text("intro.temperature-only")),
+    }
+    if game.secret().is_evil() {
+        println!("{}", i18n::text("intro.evil"));
+    }
+    if let Some(liar) = game.liar() {
+        println!(
+            "{}",
+            i18n::plural("intro.lies", u64::from(liar.max_lies), &[])
+        );
+    }
+    if !options.players.is_empty() {
+        return play_hot_seat(options, game);
+    }
+    if let Some(strategy) = options.bot {
+        return play_bot(options, strategy, game);
+    }
+    if options.script.is_some() {
+        let mut player = options.player(game.range())?;
+        return player::play(game, &mut player, &mut io::stdout());
+    }
+
+    #[cfg(feature = "tui")]
+    if options.tui {
+        crate::tui::run(game)?;
+        // leaving the screen keeps the game, like Ctrl-C at the prompt
+        match options.resume.clone().or_else(save::default_path) {
+            Some(path) if !game.is_finished() => {
+                save::save(game, &path)?;
+                println!("{}", save::resume_hint(&path));
+            }
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    // a resumed game lies even without --lies
+    let parser = options.guess_parser(game.range(), game.liar().is_some());
+    let mut player = StdinPlayer::new().with_parser(parser);
+    // the player at the terminal does not lose the game with Ctrl-C or Ctrl-D
+    let path = match options.resume.clone().or_else(save::default_path) {
+        Some(path) => path,
+        None => return player::play(game, &mut player, &mut io::stdout()),
+    };
+    let autosave = AutoSave::install(path)?;
+    println!("{}", i18n::text("intro.commands"));
+    player::play_autosaving(game, &mut player, &autosave, &mut io::stdout())?;
+    if !game.is_finished() {
+        save::save(game, autosave.path())?;
+        println!("{}", save::resume_hint(autosave.path()));
+    }
+    Ok(())
+}
+
+/// The game to play: the one saved in the `--resume` file, otherwise a new
+/// one following the options.
+pub fn start_game(options: &Options) -> io::Result<Game> {
+    match &options.resume {
+        Some(path) => save::load(path),
+        None => Ok(options.new_game()),
+    }
+}
+
+/// Lets the `--players` take turns at the terminal. With a `--chooser`, the
+/// secret number of `game` is replaced by the one they type.
+pub fn play_hot_seat(options: &Options, game: &mut Game) -> io::Result<()> {
+    let range = game.range().clone();
+    // each player has the attempt limit, not the whole game
+    *game = match &options.chooser {
+        Some(chooser) => Game::with_secret(range.clone(), read_secret(chooser, &range)?)
+            .with_time_limit(options.time_limit)
+            .with_feedback(options.feedback)
+            .with_liar(game.liar()),
+        None => game.clone().with_max_attempts(None),
+    };
+
+    let parser = options.guess_parser(&range, game.liar().is_some());
+    let mut seats: Vec<Seat> = options
+        .guessers()
+        .into_iter()
+        .map(|name| Seat::new(name, StdinPlayer::new().with_parser(parser.clone())))
+        .collect();
+    multiplayer::play(game, &mut seats, options.attempt_limit(), &mut io::stdout())?;
+    Ok(())
+}
+
+/// Asks `chooser` for the secret number, without showing it on the terminal.
+fn read_secret(chooser: &str, range: &RangeInclusive<u32>) -> io::Result<u32> {
+    let (min, max) = (range.start(), range.end());
+    let prompt = i18n::fill(
+        "secret.prompt",
+        &[("chooser", &chooser), ("min", min), ("max", max)],
+    );
+    loop {
+        match rpassword::prompt_password(&prompt)?.trim().parse() {
+            Ok(secret_number) if range.contains(&secret_number) => return Ok(secret_number),
+            _ => println!(
+                "{}",
+                i18n::fill("secret.invalid", &[("min", min), ("max", max)])
+            ),
+        }
+    }
+}
+
+/// The language of the last valid `--lang` of `args`, before they are parsed:
+/// a wrong one is reported by [`Options::parse`], in the language of the locale.
+fn lang_arg(args: &[String]) -> Option<Lang> {
+    let mut lang = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--lang") {
+            Some("") => args.next().map(String::as_str),
+            Some(value) => value.strip_prefix('='),
+            None => None,
+        };
+        if let Some(value) = value.and_then(|value| value.parse().ok()) {
+            lang = Some(value);
+        }
+    }
+    lang
+}
+
+/// The lies a bot expects: none when the answers hide the direction, the
+/// only part of them that can lie, so that the bot only uses the temperature.
+fn bot_lies(max_lies: u32, feedback: FeedbackMode) -> u32 {
+    if feedback.shows_direction() {
+        max_lies
+    } else {
+        0
+    }
+}
+
+/// Lets a bot play `game`, then prints its guesses.
+pub fn play_bot(options: &Options, strategy: Strategy, game: &mut Game) -> io::Result<()> {
+    let max_lies = game.liar().map_or(0, |liar| liar.max_lies);
+    let shows_direction = game.feedback_mode().shows_direction();
+    let mut bot = Bot::new(strategy, game.range().clone(), options.bot_rng())
+        .with_lies(bot_lies(max_lies, game.feedback_mode()));
+    player::play(game, &mut bot, &mut io::sink())?;
+
+    println!("{}", i18n::fill("bot.played", &[("strategy", &strategy)]));
+    let lies = game.lies();
+    for (i, attempt) in game.history().iter().enumerate() {
+        // a lie is in the direction, not told when it is hidden
+        let lie = if shows_direction && lies.contains(&i) {
+            i18n::text("bot.lie")
+        } else {
+            ""
+        };
+        // what the bot was told, not the outcome behind it
+        println!(
+            "{:>4}: {:>10} {}{}",
+            i + 1,
+            attempt.guess,
+            game.feedback_at(i),
+            lie
+        );
+    }
+    let key = match game.status() {
+        Status::Won => "bot.found",
+        _ => "bot.not-found",
+    };
+    println!(
+        "{}",
+        i18n::plural(
+            key,
+            u64::from(game.attempts()),
+            &[("secret", &game.secret_number())]
+        )
+    );
+    Ok(())
+}
+
+/// Plays the `mastermind` command with `rules` and returns the exit code:
+/// the player at the terminal guesses, or the [`KnuthBot`] with `solver`.
+/// `--seed` picks the code and `--max-attempts` limits the guesses.
+pub fn play_mastermind(options: &Options, rules: &CodeRules, solver: bool) -> i32 {
+    let max_attempts = match options.max_attempts {
+        Some(0) => None,
+        Some(max) => Some(max),
+        None => Some(mastermind::DEFAULT_MAX_ATTEMPTS),
+    };
+    let mut game = CodeGame::new(rules.clone(), &mut options.rng()).with_max_attempts(max_attempts);
+
+    println!("{}", i18n::fill("mastermind.banner", &[("rules", rules)]));
+    println!("{}", i18n::text("mastermind.intro"));
+    if let Some(attempts_left) = game.attempts_left() {
+        println!(
+            "{}",
+            i18n::plural("attempts-left", u64::from(attempts_left), &[])
+        );
+    }
+    let mut breaker: Box<dyn CodeBreaker> = if solver {
+        Box::new(KnuthBot::new(rules.clone()))
+    } else {
+        println!("{}", i18n::text("play.commands"));
+        Box::new(StdinCodeBreaker::new(rules.clone()))
+    };
+    if let Err(err) = mastermind::play(&mut game, &mut breaker, &mut io::stdout()) {
+        eprintln!("{}", err);
+        return exit_code::NOT_FINISHED;
+    }
+    if game.status() == Status::Won {
+        println!(
+            "{}",
+            i18n::plural(
+                "mastermind.found",
+                u64::from(game.attempts()),
+                &[("code", game.secret())]
+            )
+        );
+    }
+    exit_code(game.status())
+}
+
+/// Plays the `wordle` command with the words of `file` and returns the exit
+/// code. `--seed` picks the word, unless it is the `daily` puzzle, and
+/// `--max-attempts` limits the guesses.
+pub fn play_wordle(options: &Options, file: &Path, hard: bool, daily: bool) -> i32 {
+    let words = match WordList::load(file) {
+        Ok(words) => words,
+        Err(err) => {
+            eprintln!("{}", i18n::fill("wordle.list-error", &[("error", &err)]));
+            return exit_code::USAGE;
+        }
+    };
+    let length = words.length();
+    let today = date::today();
+    let game = if daily {
+        WordGame::daily(words, &today)
+    } else {
+        WordGame::new(words, &mut options.rng())
+    };
+    let max_attempts = match options.max_attempts {
+        Some(0) => None,
+        Some(max) => Some(max),
+        None => Some(wordle::DEFAULT_MAX_ATTEMPTS),
+    };
+    let mut game = game.with_hard_mode(hard).with_max_attempts(max_attempts);
+
+    let length = length as u64;
+    if daily {
+        println!(
+            "{}",
+            i18n::plural("wordle.daily", length, &[("date", &today)])
+        );
+    } else {
+        println!("{}", i18n::plural("wordle.banner", length, &[]));
+    }
+    println!("{}", i18n::text("wordle.marks"));
+    if hard {
+        println!("{}", i18n::text("wordle.hard"));
+    }
+    if let Some(attempts_left) = game.attempts_left() {
+        println!(
+            "{}",
+            i18n::plural("attempts-left", u64::from(attempts_left), &[])
+        );
+    }
+    println!("{}", i18n::text("play.commands"));
+    if let Err(err) = wordle::play(&mut game, &mut io::stdin().lock(), &mut io::stdout()) {
+        eprintln!("{}", err);
+        return exit_code::NOT_FINISHED;
+    }
+    if daily && game.is_finished() {
+        println!("{} {}", today, wordle::summary(&game));
+    }
+    exit_code(game.status())
+}
+
+/// Runs the bots of [`Strategy::ALL`] against each other: on the range of
+/// `--difficulty`, `--min` and `--max` when given, otherwise on every
+/// difficulty. Prints the ranking and writes the `--csv` and `--json` files.
+pub fn run_tournament(options: &Options) -> io::Result<()> {
+    let custom = options.difficulty.is_some() || options.min.is_some() || options.max.is_some();
+    let rounds: Vec<Round> = if custom {
+        let range = options.range();
+        vec![Round {
+            name: options.difficulty_label(&range),
+            max_attempts: options.attempt_limit(),
+            range,
+        }]
+    } else {
+        Difficulty::ALL
+            .iter()
+            .map(|difficulty| Round {
+                name: difficulty.name().to_string(),
+                range: difficulty.range(),
+                max_attempts: match options.max_attempts {
+                    Some(0) => None,
+                    Some(max_attempts) => Some(max_attempts),
+                    None => Some(difficulty.max_attempts()),
+                },
+            })
+            .collect()
+    };
+
+    let seed = options.seed.unwrap_or(0);
+    let mut tournament = Tournament::new(seed).with_bots();
+    if let Some(games) = options.games {
+        tournament = tournament.with_games(games);
+    }
+    if let Some(threads) = options.threads {
+        tournament = tournament.with_threads(threads);
+    }
+    for round in rounds {
+        tournament = tournament.with_round(round);
+    }
+    let results = tournament.run();
+
+    println!("{}", i18n::fill("tournament.seed", &[("seed", &seed)]));
+    results.write_table(tournament.rounds(), &mut io::stdout())?;
+    if let Some(path) = &options.csv {
+        results.write_csv(&mut io::BufWriter::new(fs::File::create(path)?))?;
+        println!(
+            "{}",
+            i18n::fill("tournament.csv", &[("path", &path.display())])
+        );
+    }
+    if let Some(path) = &options.json {
+        results.write_json(&mut io::BufWriter::new(fs::File::create(path)?))?;
+        println!(
+            "{}",
+            i18n::fill("tournament.json", &[("path", &path.display())])
+        );
+    }
+    Ok(())
+}
+
+/// Prints the `top` best scores of each difficulty.
+pub fn print_high_scores(top: usize) -> io::Result<()> {
+    let path = HighScores::default_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, i18n::text("no-data-dir")))?;
+    HighScores::load(path)?.write_top(top, &mut io::stdout())
+}
+
+/// Writes the replay of `game` to `--record`, and to
+/// [`Replay::default_path`] to keep the last game; nothing is written before
+/// the first guess.
+pub fn record_replay(options: &Options, game: &Game) -> io::Result<()> {
+    if game.attempts() == 0 {
+        return Ok(());
+    }
+    let replay = Replay::record(game);
+    if let Some(path) = &options.record {
+        replay.save(path)?;
+        println!(
+            "{}",
+            i18n::fill("replay.saved", &[("path", &path.display())])
+        );
+    }
+    match Replay::default_path() {
+        Some(path) => replay.save(path),
+        None => Ok(()),
+    }
+}
+
+/// Adds a finished `game` to [`GameLog::default_path`]; the games of the
+/// bots are recorded as `binary bot` and so on, the multiplayer ones are not.
+pub fn record_stats(options: &Options, game: &Game) -> io::Result<()> {
+    if !options.players.is_empty() {
+        return Ok(());
+    }
+    let player = match options.bot {
+        Some(strategy) => format!("{} bot", strategy),
+        None => options.player_name(),
+    };
+    let difficulty = options.difficulty_label(game.range());
+    match (
+        GameRecord::new(&player, game, &difficulty),
+        GameLog::default_path(),
+    ) {
+        (Some(record), Some(path)) => GameLog::append(path, &record),
+        _ => Ok(()),
+    }
+}
+
+/// Prints the statistics of the recorded games, only of `player` when given.
+pub fn print_stats(player: Option<&str>) -> io::Result<()> {
+    let path = GameLog::default_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, i18n::text("no-data-dir")))?;
+    let log = GameLog::load(path)?;
+    if log.skipped > 0 {
+        println!(
+            "{}",
+            i18n::plural("report.skipped", log.skipped as u64, &[])
+        );
+    }
+    stats::write_report(&log.records, player, &mut io::stdout())
+}
+
+/// Plays back the replay in `file`, see [`Command::Replay`], and returns the
+/// exit code: [`exit_code::CHANGED`] when the current rules play the game
+/// differently.
+pub fn play_replay(file: &Path, speed: u32, check: bool) -> i32 {
+    let replay = match Replay::load(file) {
+        Ok(replay) => replay,
+        Err(err) => {
+            eprintln!("{}", i18n::fill("replay.read-error", &[("error", &err)]));
+            return exit_code::USAGE;
+        }
+    };
+    let differences = if check {
+        replay.check()
+    } else {
+        match replay.play_back(speed, &mut io::stdout()) {
+            Ok(differences) => differences,
+            Err(err) => {
+                eprintln!("{}", err);
+                return exit_code::NOT_FINISHED;
+            }
+        }
+    };
+
+    if differences.is_empty() {
+        println!("{}", i18n::text("replay.same"));
+        return exit_code::WON;
+    }
+    if check {
+        for difference in &differences {
+            println!("{}", difference);
+        }
+    }
+    println!(
+        "{}",
+        i18n::plural("replay.different", differences.len() as u64, &[])
+    );
+    exit_code::CHANGED
+}
+
+/// Prints the score of a won `game` and adds it to the high scores; the
+/// games of the bots and the multiplayer ones are not recorded.
+pub fn record_score(options: &Options, game: &Game) -> io::Result<()> {
+    if game.status() != Status::Won || options.bot.is_some() || !options.players.is_empty() {
+        return Ok(());
+    }
+    let entry = ScoreEntry::new(
+        &options.player_name(),
+        game,
+        &options.difficulty_label(game.range()),
+        game.seed(),
+    );
+    println!("{}", i18n::fill("score", &[("score", &entry.score)]));
+
+    let path = HighScores::default_path()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, i18n::text("no-data-dir")))?;
+    let mut high_scores = HighScores::load(path)?;
+    let rank = high_scores.add(entry);
+    high_scores.save()?;
+    if rank == 1 {
+        let difficulty = options.difficulty_label(game.range());
+        println!(
+            "{}",
+            i18n::fill("score.high", &[("difficulty", &difficulty)])
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(args: &[&str], seed_var: Option<&str>) -> Options {
+        Options::parse(
+            args.iter().map(|arg| arg.to_string()),
+            seed_var.map(str::to_string),
+        )
+        .unwrap()
+    }
+
+    /// The guesses of the bot of `options` until its game is finished.
+    fn bot_guesses(options: &Options) -> Vec<u32> {
+        let mut game = options.new_game();
+        let mut bot = options.player(game.range()).unwrap();
+        let mut guesses = Vec::new();
+        while let Some(guess) = bot.next_guess(game.history()) {
+            guesses.push(guess);
+            game.guess(guess).unwrap();
+            bot.observe(game.feedback().unwrap());
+            if game.is_finished() {
+                break;
+            }
+        }
+        guesses
+    }
+
+    #[test]
+    fn a_seed_always_picks_the_same_secret_number() {
+        let seeded = options(&["--seed", "42"], None);
+        let secret = seeded.new_game().secret_number();
+        // pinned, so that a new version of rand cannot change the games silently
+        assert_eq!(secret, 98);
+        for _ in 0..3 {
+            assert_eq!(seeded.new_game().secret_number(), secret);
+        }
+        assert_eq!(options(&[], Some("42")).new_game().secret_number(), secret);
+        assert_eq!(
+            options(&["--seed=42"], Some("7"))
+                .new_game()
+                .secret_number(),
+            secret
+        );
+        assert_eq!(
+            options(&["--seed", "42", "--lies", "1"], None)
+                .new_game()
+                .secret_number(),
+            secret
+        );
+    }
+
+    #[test]
+    fn a_seed_always_gives_the_same_bot_guesses() {
+        for strategy in &["random", "human"] {
+            let seeded = options(&["--seed", "42", "--bot", strategy], None);
+            let guesses = bot_guesses(&seeded);
+            assert_eq!(bot_guesses(&seeded), guesses, "{}", strategy);
+            let other = options(&["--seed", "43", "--bot", strategy], None);
+            assert_ne!(bot_guesses(&other), guesses, "{}", strategy);
+        }
+    }
+
+    #[test]
+    fn a_bot_finds_the_secret_number_from_the_temperature_only() {
+        for seed in 0..20 {
+            let seed = seed.to_string();
+            // the lies are in the hidden direction, the bot does not expect any
+            let args = [
+                "--seed",
+                &seed,
+                "--feedback",
+                "temperature-only",
+                "--lies",
+                "2",
+            ];
+            let options = options(&[&args[..], &["--bot", "binary"]].concat(), None);
+            let secret = options.new_game().secret_number();
+            let guesses = bot_guesses(&options);
+            assert_eq!(guesses.last(), Some(&secret), "seed {}", seed);
+            assert!(guesses.len() as u32 <= options.attempt_limit().unwrap());
+        }
+    }
+
+    #[test]
+    fn too_many_lies_are_refused() {
+        let parse = |args: &[&str]| Options::parse(args.iter().map(|arg| arg.to_string()), None);
+        let huge = u32::MAX.to_string();
+        assert_eq!(
+            parse(&["--lies", &huge]).unwrap_err(),
+            ArgError::TooManyLies(u32::MAX)
+        );
+        // 7 attempts and 7 more for each lie on 1..=100
+        let limit = parse(&["--lies", "3"]).unwrap().attempt_limit();
+        assert_eq!(limit, Some(28));
+        // --max-attempts is not raised by the lies
+        let limited = parse(&["--lies", &huge, "--max-attempts", "10"]).unwrap();
+        assert_eq!(limited.attempt_limit(), Some(10));
+    }
+
+    #[test]
+    fn the_temperature_only_doubles_every_limit_but_max_attempts() {
+        let limit = |args: &[&str]| options(args, None).attempt_limit();
+        let hidden = ["--feedback", "temperature-only"];
+        assert_eq!(limit(&[]), Some(7));
+        assert_eq!(limit(&hidden), Some(14));
+        assert_eq!(limit(&["--difficulty", "hard"]), Some(20));
+        assert_eq!(
+            limit(&[&hidden[..], &["--difficulty", "hard"]].concat()),
+            Some(40)
+        );
+        assert_eq!(
+            limit(&[&hidden[..], &["--max-attempts", "5"]].concat()),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn the_solver_is_refused_on_too_many_codes() {
+        let parse = |args: &[&str]| Options::parse(args.iter().map(|arg| arg.to_string()), None);
+        // 10^8 codes
+        assert_eq!(
+            parse(&["mastermind", "--solver", "--repeats", "--length", "8"]).unwrap_err(),
+            ArgError::TooManyCodes(100_000_000)
+        );
+        assert!(parse(&["mastermind", "--repeats", "--length", "8"]).is_ok());
+        assert!(parse(&["mastermind", "--solver", "--repeats", "--length", "4"]).is_ok());
+    }
+
+    #[test]
+    fn the_language_is_read_before_the_other_arguments() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            lang_arg(&args(&["--bogus", "--lang", "it"])),
+            Some(Lang::Italian)
+        );
+        assert_eq!(
+            lang_arg(&args(&["--lang=it", "--lang=en"])),
+            Some(Lang::English)
+        );
+        // a wrong or missing language is left to the parser
+        assert_eq!(lang_arg(&args(&["--lang", "klingon"])), None);
+        assert_eq!(lang_arg(&args(&["--lang"])), None);
+        assert_eq!(lang_arg(&args(&["--language=it"])), None);
+    }
+}
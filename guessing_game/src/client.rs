@@ -0,0 +1,376 @@
+use crate::i18n;
+use crate::player::Player;
+use crate::protocol::{Request, Response};
+use crate::{Attempt, Feedback, Loss, Outcome, Status};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::ops::RangeInclusive;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A connection to a `guess-server`, speaking the [`protocol`](crate::protocol).
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+}
+
+impl Client {
+    /// Connects to the server and waits for its first game.
+    pub fn connect<A: ToSocketAddrs>(addr: A) -> io::Result<Client> {
+        let stream = TcpStream::connect(addr)?;
+        let mut client = Client {
+            reader: BufReader::new(stream.try_clone()?),
+            writer: stream,
+            range: 0..=0,
+            max_attempts: None,
+        };
+        client.welcome()?;
+        Ok(client)
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn send(&mut self, request: &Request) -> io::Result<()> {
+        writeln!(self.writer, "{}", request)?;
+        self.writer.flush()
+    }
+
+    pub fn receive(&mut self) -> io::Result<Response> {
+        read_response(&mut self.reader)
+    }
+
+    pub fn guess(&mut self, guess: u32) -> io::Result<Response> {
+        self.send(&Request::Guess(guess))?;
+        self.receive()
+    }
+
+    /// Asks the server for another game.
+    pub fn new_game(&mut self) -> io::Result<()> {
+        self.send(&Request::New)?;
+        self.welcome()
+    }
+
+    /// Joins the race room `room` as `name`, and returns how many players
+    /// are in the room.
+    pub fn join(&mut self, room: &str, name: &str) -> io::Result<usize> {
+        self.send(&Request::Join {
+            room: room.to_string(),
+            name: name.to_string(),
+        })?;
+        let players = match self.receive()? {
+            Response::Joined { players, .. } => players,
+            Response::Error(message) => return Err(io::Error::other(message)),
+            response => return Err(unexpected(&response)),
+        };
+        self.welcome()?;
+        Ok(players)
+    }
+
+    pub fn quit(mut self) -> io::Result<()> {
+        self.send(&Request::Quit)?;
+        self.receive().map(|_| ())
+    }
+
+    fn welcome(&mut self) -> io::Result<()> {
+        match self.receive()? {
+            Response::Welcome {
+                range,
+                max_attempts,
+            } => {
+                self.range = range;
+                self.max_attempts = max_attempts;
+                Ok(())
+            }
+            response => Err(unexpected(&response)),
+        }
+    }
+}
+
+fn read_response<R: BufRead>(reader: &mut R) -> io::Result<Response> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "the server closed the connection",
+        ));
+    }
+    line.parse()
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn unexpected(response: &Response) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("unexpected answer from the server: {}", response),
+    )
+}
+
+/// Lets `player` play the current game of `client`, writing to `out` what
+/// happens at each guess, like [`player::play`](crate::player::play) does.
+pub fn play<P, W>(client: &mut Client, player: &mut P, out: &mut W) -> io::Result<Status>
+where
+    P: Player + ?Sized,
+    W: Write,
+{
+    let mut history = Vec::new();
+    loop {
+        let guess = match player.next_guess(&history) {
+            Some(guess) => guess,
+            None => return Ok(Status::Playing),
+        };
+        writeln!(out, "{}", i18n::fill("guessed", &[("guess", &guess)]))?;
+
+        match client.guess(guess)? {
+            Response::Answer { outcome, attempts } => {
+                history.push(Attempt { guess, outcome });
+                player.observe(Feedback::from(outcome));
+                writeln!(out, "{}", answer(outcome))?;
+                if outcome == Outcome::Win {
+                    return Ok(Status::Won);
+                }
+                // the last attempt is followed by the secret number
+                if client.max_attempts() == Some(attempts) {
+                    return match client.receive()? {
+                        Response::Lost {
+                            secret_number,
+                            loss,
+                        } => write_lost(secret_number, loss, out),
+                        response => Err(unexpected(&response)),
+                    };
+                }
+            }
+            Response::Lost {
+                secret_number,
+                loss,
+            } => return write_lost(secret_number, loss, out),
+            Response::Error(message) => writeln!(out, "{}", message)?,
+            response => return Err(unexpected(&response)),
+        }
+    }
+}
+
+/// The answer to a guess, as the number game shows it.
+fn answer(outcome: Outcome) -> &'static str {
+    i18n::text(match outcome {
+        Outcome::TooSmall => "feedback.too-small",
+        Outcome::TooBig => "feedback.too-big",
+        Outcome::Win => "feedback.win",
+    })
+}
+
+fn write_lost<W: Write>(secret_number: u32, loss: Loss, out: &mut W) -> io::Result<Status> {
+    let message = match loss {
+        Loss::OutOfAttempts => "loss.attempts",
+        Loss::OutOfTime => "loss.time",
+        Loss::GaveUp => "loss.gave-up",
+    };
+    writeln!(out, "{}", i18n::text(message))?;
+    writeln!(
+        out,
+        "{}",
+        i18n::fill("loss.secret", &[("secret", &secret_number)])
+    )?;
+    Ok(Status::Lost(loss))
+}
+
+/// How a race ended for one of its players.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RaceOutcome {
+    Won,
+    Beaten {
+        winner: String,
+    },
+    /// Nobody found the secret number.
+    Lost(Loss),
+    /// The player stopped before the end of the race.
+    Left,
+}
+
+/// What the main loop of [`race`] waits for.
+enum Event {
+    Answer { outcome: Outcome, attempts: u32 },
+    Error,
+    End(RaceOutcome),
+}
+
+/// Lets `player`, called `name`, race in the room joined by `client` until
+/// the race is over, then closes the connection.
+///
+/// The guesses of the other players and the standings are written to `out` as
+/// soon as they arrive, while `player` is thinking.
+pub fn race<P, W>(client: Client, name: &str, player: &mut P, out: W) -> io::Result<RaceOutcome>
+where
+    P: Player + ?Sized,
+    W: Write + Send + 'static,
+{
+    let Client {
+        mut reader,
+        mut writer,
+        max_attempts,
+        ..
+    } = client;
+    let out = Arc::new(Mutex::new(out));
+    let (events, received) = mpsc::channel();
+
+    let listener = {
+        let out = Arc::clone(&out);
+        let name = name.to_string();
+        thread::spawn(move || -> io::Result<()> {
+            let mut outcome = RaceOutcome::Left;
+            loop {
+                let response = read_response(&mut reader)?;
+                let mut out = lock(&out);
+                let event = match response {
+                    // written here, in the order of the messages
+                    Response::Answer { outcome, attempts } => {
+                        writeln!(out, "{}", answer(outcome))?;
+                        if outcome != Outcome::Win && max_attempts == Some(attempts) {
+                            writeln!(out, "{}", i18n::text("race.no-attempts"))?;
+                        }
+                        Event::Answer { outcome, attempts }
+                    }
+                    Response::Error(message) => {
+                        writeln!(out, "{}", message)?;
+                        Event::Error
+                    }
+                    Response::Hint {
+                        player,
+                        guess,
+                        outcome,
+                    } => {
+                        let hint = i18n::fill(
+                            "race.hint",
+                            &[
+                                ("player", &player),
+                                ("guess", &guess),
+                                ("outcome", &outcome),
+                            ],
+                        );
+                        writeln!(out, "{}", hint)?;
+                        continue;
+                    }
+                    Response::Joined { players, .. } => {
+                        writeln!(out, "{}", i18n::plural("race.players", players as u64, &[]))?;
+                        continue;
+                    }
+                    Response::Winner { player, attempts } => {
+                        let winner = i18n::plural(
+                            "race.winner",
+                            u64::from(attempts),
+                            &[("player", &player)],
+                        );
+                        writeln!(out, "{}", winner)?;
+                        outcome = if player == name {
+                            RaceOutcome::Won
+                        } else {
+                            RaceOutcome::Beaten { winner: player }
+                        };
+                        continue;
+                    }
+                    Response::Lost {
+                        secret_number,
+                        loss,
+                    } => {
+                        write_lost(secret_number, loss, &mut *out)?;
+                        outcome = RaceOutcome::Lost(loss);
+                        continue;
+                    }
+                    Response::Standing {
+                        rank,
+                        player,
+                        attempts,
+                        distance,
+                    } => {
+                        let distance = distance.map_or("-".to_string(), |d| d.to_string());
+                        // the columns padded here, the words of the language around them
+                        let standing = i18n::plural(
+                            "race.standing",
+                            u64::from(attempts),
+                            &[
+                                ("rank", &format!("{:>3}", rank)),
+                                ("player", &format!("{:<16}", player)),
+                                ("attempts", &format!("{:>4}", attempts)),
+                                ("distance", &distance),
+                            ],
+                        );
+                        writeln!(out, "{}", standing)?;
+                        continue;
+                    }
+                    Response::End => {
+                        writeln!(out, "{}", i18n::text("race.over"))?;
+                        Event::End(outcome.clone())
+                    }
+                    Response::Bye(_) => return Ok(()),
+                    response => return Err(unexpected(&response)),
+                };
+                // the main loop may have stopped already
+                let _ = events.send(event);
+            }
+        })
+    };
+
+    let mut history = Vec::new();
+    let mut pending = None;
+    let mut waiting = false;
+    let outcome = loop {
+        let event = if waiting {
+            received.recv()
+        } else {
+            // the race may have ended while the player was thinking
+            if let Ok(Event::End(outcome)) = received.try_recv() {
+                break outcome;
+            }
+            let guess = match player.next_guess(&history) {
+                Some(guess) => guess,
+                None => break RaceOutcome::Left,
+            };
+            if let Ok(Event::End(outcome)) = received.try_recv() {
+                break outcome;
+            }
+            writeln!(
+                lock(&out),
+                "{}",
+                i18n::fill("guessed", &[("guess", &guess)])
+            )?;
+            writeln!(writer, "{}", Request::Guess(guess))?;
+            writer.flush()?;
+            pending = Some(guess);
+            received.recv()
+        };
+
+        match event {
+            Ok(Event::Answer { outcome, attempts }) => {
+                if let Some(guess) = pending.take() {
+                    history.push(Attempt { guess, outcome });
+                }
+                player.observe(Feedback::from(outcome));
+                // until the end of the race
+                waiting = outcome == Outcome::Win || max_attempts == Some(attempts);
+            }
+            Ok(Event::Error) => pending = None,
+            Ok(Event::End(outcome)) => break outcome,
+            // the listener stopped: its error is returned below
+            Err(_) => break RaceOutcome::Left,
+        }
+    };
+
+    writeln!(writer, "{}", Request::Quit)?;
+    writer.flush()?;
+    match listener.join() {
+        Ok(Err(err)) if outcome == RaceOutcome::Left => Err(err),
+        _ => Ok(outcome),
+    }
+}
+
+fn lock<W>(out: &Mutex<W>) -> std::sync::MutexGuard<'_, W> {
+    out.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
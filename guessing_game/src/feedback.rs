@@ -0,0 +1,309 @@
+use crate::i18n;
+use crate::{Attempt, Outcome};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// How a game answers the guesses, chosen with `--feedback`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FeedbackMode {
+    /// Too small or too big, like in the tutorial.
+    #[default]
+    Classic,
+    /// Hot, warm or cold, warmer or colder than the last guess, and too
+    /// small or too big.
+    Temperature,
+    /// Only hot, warm or cold, and warmer or colder: the direction is hidden.
+    TemperatureOnly,
+}
+
+impl FeedbackMode {
+    pub const ALL: [FeedbackMode; 3] = [
+        FeedbackMode::Classic,
+        FeedbackMode::Temperature,
+        FeedbackMode::TemperatureOnly,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FeedbackMode::Classic => "classic",
+            FeedbackMode::Temperature => "temperature",
+            FeedbackMode::TemperatureOnly => "temperature-only",
+        }
+    }
+
+    /// Whether the answers tell if a guess is too small or too big.
+    pub fn shows_direction(self) -> bool {
+        self != FeedbackMode::TemperatureOnly
+    }
+}
+
+impl fmt::Display for FeedbackMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The name is not one of the [`FeedbackMode`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFeedbackModeError(String);
+
+impl fmt::Display for ParseFeedbackModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown feedback '{}' (expected classic, temperature or temperature-only)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseFeedbackModeError {}
+
+impl FromStr for FeedbackMode {
+    type Err = ParseFeedbackModeError;
+
+    fn from_str(s: &str) -> Result<FeedbackMode, ParseFeedbackModeError> {
+        FeedbackMode::ALL
+            .iter()
+            .copied()
+            .find(|mode| mode.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseFeedbackModeError(s.to_string()))
+    }
+}
+
+/// How close a guess is to the secret number, compared with the size of the
+/// range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Temperature {
+    /// Within 5% of the range, or 1.
+    Hot,
+    /// Within 20% of the range, or 2.
+    Warm,
+    Cold,
+}
+
+impl Temperature {
+    pub fn new(distance: u32, range: &RangeInclusive<u32>) -> Temperature {
+        let (hot, warm) = thresholds(range);
+        let distance = u64::from(distance);
+        if distance <= hot {
+            Temperature::Hot
+        } else if distance <= warm {
+            Temperature::Warm
+        } else {
+            Temperature::Cold
+        }
+    }
+
+    /// The distances from the secret number that get this temperature on
+    /// `range`.
+    pub fn distances(self, range: &RangeInclusive<u32>) -> RangeInclusive<u64> {
+        let (hot, warm) = thresholds(range);
+        match self {
+            Temperature::Hot => 0..=hot,
+            Temperature::Warm => hot + 1..=warm,
+            Temperature::Cold => warm + 1..=u64::from(u32::MAX),
+        }
+    }
+}
+
+/// The largest distances that are hot and warm on `range`.
+fn thresholds(range: &RangeInclusive<u32>) -> (u64, u64) {
+    let size = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+    ((size / 20).max(1), (size / 5).max(2))
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(i18n::text(match self {
+            Temperature::Hot => "temperature.hot",
+            Temperature::Warm => "temperature.warm",
+            Temperature::Cold => "temperature.cold",
+        }))
+    }
+}
+
+/// A guess compared with the previous one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Warmer,
+    Colder,
+    /// As far from the secret number as the previous guess.
+    Same,
+}
+
+impl fmt::Display for Trend {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(i18n::text(match self {
+            Trend::Warmer => "trend.warmer",
+            Trend::Colder => "trend.colder",
+            Trend::Same => "trend.same",
+        }))
+    }
+}
+
+/// What the player is told about a guess, depending on the
+/// [`FeedbackMode`] of the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Feedback {
+    Win,
+    /// Too small or too big.
+    Direction(Outcome),
+    Temperature {
+        temperature: Temperature,
+        /// `None` for the first guess.
+        trend: Option<Trend>,
+        /// `None` when the mode hides it.
+        direction: Option<Outcome>,
+    },
+}
+
+impl From<Outcome> for Feedback {
+    /// The classic answer, too small or too big.
+    fn from(outcome: Outcome) -> Feedback {
+        match outcome {
+            Outcome::Win => Feedback::Win,
+            _ => Feedback::Direction(outcome),
+        }
+    }
+}
+
+impl Feedback {
+    /// Too small or too big, when the answer tells it.
+    pub fn direction(self) -> Option<Outcome> {
+        match self {
+            Feedback::Win => Some(Outcome::Win),
+            Feedback::Direction(outcome) => Some(outcome),
+            Feedback::Temperature { direction, .. } => direction,
+        }
+    }
+
+    /// The answer to `attempt` in a game on `range`, after the guess
+    /// `previous` if there was one. The direction is the one of the attempt,
+    /// which may be a lie; the temperature is always true.
+    pub fn new(
+        mode: FeedbackMode,
+        range: &RangeInclusive<u32>,
+        secret_number: u32,
+        attempt: Attempt,
+        previous: Option<u32>,
+    ) -> Feedback {
+        let Attempt { guess, outcome } = attempt;
+        if outcome == Outcome::Win {
+            return Feedback::Win;
+        }
+        if mode == FeedbackMode::Classic {
+            return Feedback::Direction(outcome);
+        }
+
+        let distance = guess.abs_diff(secret_number);
+        let trend = previous.map(|previous| {
+            // closer to the secret number is warmer
+            match distance.cmp(&previous.abs_diff(secret_number)) {
+                Ordering::Less => Trend::Warmer,
+                Ordering::Greater => Trend::Colder,
+                Ordering::Equal => Trend::Same,
+            }
+        });
+        Feedback::Temperature {
+            temperature: Temperature::new(distance, range),
+            trend,
+            direction: Some(outcome).filter(|_| mode.shows_direction()),
+        }
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feedback::Win => f.write_str(i18n::text("feedback.win")),
+            Feedback::Direction(outcome) => f.write_str(direction(*outcome)),
+            Feedback::Temperature {
+                temperature,
+                trend,
+                direction,
+            } => {
+                write!(f, "{}", temperature)?;
+                if let Some(trend) = trend {
+                    write!(f, " {}", trend)?;
+                }
+                match direction {
+                    Some(outcome) => write!(f, " {}", self::direction(*outcome)),
+                    None => Ok(()),
+                }
+            }
+        }
+    }
+}
+
+/// Too small or too big, for a guess that did not win.
+fn direction(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooSmall => i18n::text("feedback.too-small"),
+        _ => i18n::text("feedback.too-big"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_temperature_depends_on_the_size_of_the_range() {
+        // hot within 5, warm within 20 on 1..=100
+        let range = 1..=100;
+        assert_eq!(Temperature::new(1, &range), Temperature::Hot);
+        assert_eq!(Temperature::new(5, &range), Temperature::Hot);
+        assert_eq!(Temperature::new(6, &range), Temperature::Warm);
+        assert_eq!(Temperature::new(20, &range), Temperature::Warm);
+        assert_eq!(Temperature::new(21, &range), Temperature::Cold);
+        // at least 1 and 2 on a small range
+        let small = 1..=3;
+        assert_eq!(Temperature::new(1, &small), Temperature::Hot);
+        assert_eq!(Temperature::new(2, &small), Temperature::Warm);
+        assert_eq!(Temperature::new(3, &small), Temperature::Cold);
+        // no overflow on the whole u32 range
+        assert_eq!(
+            Temperature::new(u32::MAX, &(0..=u32::MAX)),
+            Temperature::Cold
+        );
+    }
+
+    #[test]
+    fn the_distances_of_a_temperature_give_it_back() {
+        for range in &[1..=3, 1..=100, 0..=u32::MAX] {
+            for distance in (0..200).chain(vec![u32::MAX / 20, u32::MAX / 5, u32::MAX]) {
+                let temperature = Temperature::new(distance, range);
+                assert!(
+                    temperature.distances(range).contains(&u64::from(distance)),
+                    "{} on {:?} is {:?}",
+                    distance,
+                    range,
+                    temperature
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn only_the_temperature_only_mode_hides_the_direction() {
+        let attempt = Attempt {
+            guess: 40,
+            outcome: Outcome::TooSmall,
+        };
+        let feedback = |mode| Feedback::new(mode, &(1..=100), 42, attempt, None);
+        assert_eq!(
+            feedback(FeedbackMode::Classic),
+            Feedback::from(Outcome::TooSmall)
+        );
+        assert_eq!(
+            feedback(FeedbackMode::Temperature).direction(),
+            Some(Outcome::TooSmall)
+        );
+        assert_eq!(feedback(FeedbackMode::TemperatureOnly).direction(), None);
+    }
+}
@@ -0,0 +1,588 @@
+use crate::hint::{Hint, HintError};
+use crate::i18n;
+use crate::liar::{self, Liar};
+use crate::secret::{Evil, Fixed, Secret};
+use crate::{Feedback, FeedbackMode};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::time::{Duration, Instant};
+
+/// What the game answers to a single guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    TooSmall,
+    TooBig,
+    Win,
+}
+
+impl From<Ordering> for Outcome {
+    // `guess.cmp(&secret_number)` from the tutorial: Less means the guess is too small
+    fn from(ordering: Ordering) -> Outcome {
+        match ordering {
+            Ordering::Less => Outcome::TooSmall,
+            Ordering::Greater => Outcome::TooBig,
+            Ordering::Equal => Outcome::Win,
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(i18n::text(match self {
+            Outcome::TooSmall => "outcome.too-small",
+            Outcome::TooBig => "outcome.too-big",
+            Outcome::Win => "outcome.win",
+        }))
+    }
+}
+
+/// A guess already played, together with the answer it got.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Attempt {
+    pub guess: u32,
+    pub outcome: Outcome,
+}
+
+/// A hint bought during a [`Game`], see [`Game::use_hint`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HintUse {
+    /// The number of guesses made before the hint.
+    pub after: u32,
+    pub hint: String,
+    pub answer: String,
+    /// The percent of the score it cost.
+    pub penalty: u32,
+}
+
+/// Where a [`Game`] stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Playing,
+    Won,
+    Lost(Loss),
+}
+
+/// Why a [`Game`] was lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Loss {
+    /// All the attempts allowed have been used without finding the secret number.
+    OutOfAttempts,
+    /// The time budget ran out.
+    OutOfTime,
+    /// The player gave up.
+    GaveUp,
+}
+
+/// A guess that the game refused: it does not count as an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    OutOfRange {
+        guess: u32,
+        range: RangeInclusive<u32>,
+    },
+    /// The guess came after the end of the time budget: the game is lost.
+    TimeUp,
+    Finished,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::OutOfRange { guess, range } => f.write_str(&i18n::fill(
+                "guess.out-of-range",
+                &[
+                    ("guess", guess),
+                    ("min", range.start()),
+                    ("max", range.end()),
+                ],
+            )),
+            GuessError::TimeUp => f.write_str(i18n::text("guess.time-up")),
+            GuessError::Finished => f.write_str(i18n::text("finished")),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+/// One round of the guessing game.
+///
+/// The game only keeps the state: reading the guesses and printing the answers
+/// is up to the caller.
+#[derive(Debug, Clone)]
+pub struct Game {
+    range: RangeInclusive<u32>,
+    secret: Box<dyn Secret>,
+    max_attempts: Option<u32>,
+    time_limit: Option<Duration>,
+    seed: Option<u64>,
+    feedback: FeedbackMode,
+    liar: Option<Liar>,
+    started: Instant,
+    ended: Option<Instant>,
+    history: Vec<Attempt>,
+    /// When each attempt was made, since the start.
+    times: Vec<Duration>,
+    /// Whether each attempt got a lie.
+    lied: Vec<bool>,
+    hints: Vec<HintUse>,
+    status: Status,
+}
+
+impl Game {
+    /// Starts a new round, picking the secret number in `range` with `rng`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn new<R: Rng + ?Sized>(range: RangeInclusive<u32>, rng: &mut R) -> Game {
+        assert!(!range.is_empty(), "the range of the secret number is empty");
+        // gen_range excludes the upper bound, so we go through u64 to also allow u32::MAX
+        let secret_number =
+            rng.gen_range(u64::from(*range.start()), u64::from(*range.end()) + 1) as u32;
+        Game::with_secret(range, secret_number)
+    }
+
+    /// Starts a new round with a known secret number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secret_number` is not in `range`.
+    pub fn with_secret(range: RangeInclusive<u32>, secret_number: u32) -> Game {
+        assert!(
+            range.contains(&secret_number),
+            "the secret number is outside of the range"
+        );
+        Game::answered_by(range, Box::new(Fixed(secret_number)))
+    }
+
+    /// Starts a new round against an [`Evil`] secret, which answers so that
+    /// the player needs as many guesses as possible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is empty.
+    pub fn evil(range: RangeInclusive<u32>) -> Game {
+        assert!(!range.is_empty(), "the range of the secret number is empty");
+        let secret = Evil::new(&range);
+        Game::answered_by(range, Box::new(secret))
+    }
+
+    /// Starts a new round where `secret` answers the guesses, see
+    /// [`secret`](crate::secret).
+    pub fn answered_by(range: RangeInclusive<u32>, secret: Box<dyn Secret>) -> Game {
+        Game {
+            range,
+            secret,
+            max_attempts: None,
+            time_limit: None,
+            seed: None,
+            feedback: FeedbackMode::Classic,
+            liar: None,
+            started: Instant::now(),
+            ended: None,
+            history: Vec::new(),
+            times: Vec::new(),
+            lied: Vec::new(),
+            hints: Vec::new(),
+            status: Status::Playing,
+        }
+    }
+
+    /// Limits the number of attempts: once they are all used without a win
+    /// the game is lost. `None` means no limit.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> Game {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Gives a time budget to the game, counted from its creation: a guess
+    /// made after it runs out loses the game. `None` means no limit.
+    pub fn with_time_limit(mut self, time_limit: Option<Duration>) -> Game {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Remembers the seed the secret number was picked with, so that the
+    /// game can be played again.
+    pub fn with_seed(mut self, seed: Option<u64>) -> Game {
+        self.seed = seed;
+        self
+    }
+
+    /// Changes how the guesses are answered to the player, see
+    /// [`Game::feedback`].
+    pub fn with_feedback(mut self, feedback: FeedbackMode) -> Game {
+        self.feedback = feedback;
+        self
+    }
+
+    /// Lets the game lie in some of its answers, see [`liar`](crate::liar).
+    /// `None` means an honest game.
+    pub fn with_liar(mut self, liar: Option<Liar>) -> Game {
+        self.liar = liar;
+        self
+    }
+
+    /// Counts `elapsed` as already spent, for a game started in an earlier
+    /// session.
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Game {
+        if let Some(started) = Instant::now().checked_sub(elapsed) {
+            self.started = started;
+        }
+        self
+    }
+
+    /// A new round with the same range and limits, and a new secret number
+    /// picked with a new seed.
+    pub fn restart(&self) -> Game {
+        let seed = rand::thread_rng().gen();
+        let mut rng = StdRng::seed_from_u64(seed);
+        let game = if self.secret.is_evil() {
+            Game::evil(self.range.clone())
+        } else {
+            Game::new(self.range.clone(), &mut rng)
+        };
+        game.with_max_attempts(self.max_attempts)
+            .with_time_limit(self.time_limit)
+            .with_feedback(self.feedback)
+            .with_liar(self.liar.map(|liar| Liar {
+                seed: rng.gen(),
+                ..liar
+            }))
+            .with_seed(Some(seed))
+    }
+
+    /// Attempts a binary search needs in the worst case, `log2(size) + 1`.
+    pub fn default_max_attempts(range: &RangeInclusive<u32>) -> u32 {
+        let size = u64::from(*range.end()) - u64::from(*range.start()) + 1;
+        64 - size.leading_zeros()
+    }
+
+    /// Compares `guess` with the secret number and records the attempt.
+    ///
+    /// Guesses outside of the range, or made once the game is finished, are
+    /// refused without using an attempt.
+    pub fn guess(&mut self, guess: u32) -> Result<Outcome, GuessError> {
+        if self.is_finished() {
+            return Err(GuessError::Finished);
+        }
+        if self.time_left() == Some(Duration::from_secs(0)) {
+            self.finish(Status::Lost(Loss::OutOfTime));
+            return Err(GuessError::TimeUp);
+        }
+        if !self.range.contains(&guess) {
+            return Err(GuessError::OutOfRange {
+                guess,
+                range: self.range.clone(),
+            });
+        }
+
+        // the secret compares the guess with the secret number, see secret::Fixed
+        let truth = self.secret.answer(guess);
+        let lie = self.liar.is_some_and(|liar| {
+            liar.lies(&self.range, &self.history, self.lies_told(), guess, truth)
+        });
+        let outcome = if lie { liar::opposite(truth) } else { truth };
+        self.history.push(Attempt { guess, outcome });
+        self.times.push(self.elapsed());
+        self.lied.push(lie);
+        if outcome == Outcome::Win {
+            self.finish(Status::Won);
+        } else if self.attempts_left() == Some(0) {
+            self.finish(Status::Lost(Loss::OutOfAttempts));
+        }
+        Ok(outcome)
+    }
+
+    /// Asks `hint` about the secret number and logs its answer, together with
+    /// its penalty.
+    pub fn use_hint(&mut self, hint: &dyn Hint) -> Result<&HintUse, HintError> {
+        if self.is_finished() {
+            return Err(HintError::Finished);
+        }
+        if !self.secret.is_fixed() {
+            return Err(HintError::Unavailable(
+                i18n::text("hint.not-chosen").to_string(),
+            ));
+        }
+        let answer = hint.reveal(self).map_err(HintError::Unavailable)?;
+        self.log_hint(HintUse {
+            after: self.attempts(),
+            hint: hint.name(),
+            answer,
+            penalty: hint.penalty(),
+        });
+        Ok(self.hints.last().unwrap())
+    }
+
+    /// Logs a hint bought earlier, for a game played again.
+    pub(crate) fn log_hint(&mut self, used: HintUse) {
+        self.hints.push(used);
+    }
+
+    /// Ends the game as lost, for a reason decided outside of the game.
+    /// Nothing changes if the game is already finished.
+    pub fn lose(&mut self, loss: Loss) {
+        if !self.is_finished() {
+            self.finish(Status::Lost(loss));
+        }
+    }
+
+    fn finish(&mut self, status: Status) {
+        self.status = status;
+        self.ended = Some(Instant::now());
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    /// The secret number: only meant to be shown once the game is over.
+    /// Against an [`Evil`] secret it is one that agrees with the answers.
+    pub fn secret_number(&self) -> u32 {
+        self.secret.number()
+    }
+
+    /// What answers the guesses.
+    pub fn secret(&self) -> &dyn Secret {
+        self.secret.as_ref()
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn time_limit(&self) -> Option<Duration> {
+        self.time_limit
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    pub fn liar(&self) -> Option<Liar> {
+        self.liar
+    }
+
+    /// The lies the game told so far.
+    pub fn lies_told(&self) -> u32 {
+        self.lied.iter().filter(|&&lie| lie).count() as u32
+    }
+
+    /// The attempts of the [history](Game::history) that got a lie, by their
+    /// index: only meant to be shown once the game is over.
+    pub fn lies(&self) -> Vec<usize> {
+        (0..self.lied.len()).filter(|&i| self.lied[i]).collect()
+    }
+
+    pub fn feedback_mode(&self) -> FeedbackMode {
+        self.feedback
+    }
+
+    /// What the player is told about the last guess, `None` before the
+    /// first one.
+    pub fn feedback(&self) -> Option<Feedback> {
+        self.history
+            .len()
+            .checked_sub(1)
+            .map(|i| self.feedback_at(i))
+    }
+
+    /// What the player was told about the guess number `index` (from 0) of
+    /// the [history](Game::history).
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no such guess.
+    pub fn feedback_at(&self, index: usize) -> Feedback {
+        let previous = index.checked_sub(1).map(|i| self.history[i].guess);
+        Feedback::new(
+            self.feedback,
+            &self.range,
+            self.secret.number(),
+            self.history[index],
+            previous,
+        )
+    }
+
+    /// Time passed since the game was created, up to its end once finished.
+    pub fn elapsed(&self) -> Duration {
+        self.ended
+            .unwrap_or_else(Instant::now)
+            .duration_since(self.started)
+    }
+
+    pub fn time_left(&self) -> Option<Duration> {
+        self.time_limit.map(|limit| {
+            limit
+                .checked_sub(self.elapsed())
+                .unwrap_or_else(|| Duration::from_secs(0))
+        })
+    }
+
+    /// The numbers that the secret number can still be, after the answers
+    /// to the guesses so far. Without the direction in the answers it is
+    /// the whole range until the secret number is found, and when the game
+    /// can lie it spans the numbers the lies allowed leave possible.
+    pub fn known_range(&self) -> RangeInclusive<u32> {
+        if let (Some(liar), true) = (self.liar, self.feedback.shows_direction()) {
+            let candidates = liar::candidates(&self.range, &self.history, liar.max_lies);
+            return match (candidates.first(), candidates.last()) {
+                (Some(first), Some(last)) => first.low..=last.high,
+                _ => self.range.clone(),
+            };
+        }
+        let mut low = *self.range.start();
+        let mut high = *self.range.end();
+        for attempt in &self.history {
+            match attempt.outcome {
+                Outcome::Win => return attempt.guess..=attempt.guess,
+                _ if !self.feedback.shows_direction() => {}
+                Outcome::TooSmall => low = low.max(attempt.guess + 1),
+                Outcome::TooBig => high = high.min(attempt.guess - 1),
+            }
+        }
+        low..=high
+    }
+
+    pub fn history(&self) -> &[Attempt] {
+        &self.history
+    }
+
+    /// When each attempt of the [history](Game::history) was made, counted
+    /// from the start of the game.
+    pub fn attempt_times(&self) -> &[Duration] {
+        &self.times
+    }
+
+    /// The hints bought so far, in order.
+    pub fn hints_used(&self) -> &[HintUse] {
+        &self.hints
+    }
+
+    /// The percent of the score lost to hints, at most 100.
+    pub fn penalty(&self) -> u32 {
+        self.hints
+            .iter()
+            .map(|used| used.penalty)
+            .sum::<u32>()
+            .min(100)
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn guesses_are_compared_with_the_secret_number() {
+        let mut game = Game::with_secret(1..=100, 42);
+        assert_eq!(game.guess(10), Ok(Outcome::TooSmall));
+        assert_eq!(game.guess(90), Ok(Outcome::TooBig));
+        assert_eq!(game.status(), Status::Playing);
+        assert_eq!(game.known_range(), 11..=89);
+        assert_eq!(game.guess(42), Ok(Outcome::Win));
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(game.attempts(), 3);
+        assert_eq!(
+            game.history()[0],
+            Attempt {
+                guess: 10,
+                outcome: Outcome::TooSmall
+            }
+        );
+    }
+
+    #[test]
+    fn refused_guesses_do_not_count() {
+        let mut game = Game::with_secret(1..=100, 42);
+        assert_eq!(
+            game.guess(101),
+            Err(GuessError::OutOfRange {
+                guess: 101,
+                range: 1..=100
+            })
+        );
+        assert!(game.guess(0).is_err());
+        assert_eq!(game.attempts(), 0);
+        game.guess(42).unwrap();
+        assert_eq!(game.guess(42), Err(GuessError::Finished));
+        assert_eq!(game.attempts(), 1);
+    }
+
+    #[test]
+    fn the_last_attempt_loses() {
+        let mut game = Game::with_secret(1..=100, 42).with_max_attempts(Some(2));
+        assert_eq!(game.attempts_left(), Some(2));
+        game.guess(1).unwrap();
+        assert_eq!(game.status(), Status::Playing);
+        assert_eq!(game.guess(2), Ok(Outcome::TooSmall));
+        assert_eq!(game.attempts_left(), Some(0));
+        assert_eq!(game.status(), Status::Lost(Loss::OutOfAttempts));
+        assert_eq!(game.guess(42), Err(GuessError::Finished));
+    }
+
+    #[test]
+    fn a_win_on_the_last_attempt_is_a_win() {
+        let mut game = Game::with_secret(1..=100, 42).with_max_attempts(Some(1));
+        assert_eq!(game.guess(42), Ok(Outcome::Win));
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn a_guess_after_the_time_limit_loses() {
+        let mut game = Game::with_secret(1..=100, 42)
+            .with_time_limit(Some(Duration::from_secs(10)))
+            .with_elapsed(Duration::from_secs(11));
+        assert_eq!(game.time_left(), Some(Duration::from_secs(0)));
+        assert_eq!(game.guess(42), Err(GuessError::TimeUp));
+        assert_eq!(game.status(), Status::Lost(Loss::OutOfTime));
+        assert_eq!(game.attempts(), 0);
+    }
+
+    #[test]
+    fn giving_up_ends_the_game_once() {
+        let mut game = Game::with_secret(1..=100, 42);
+        game.lose(Loss::GaveUp);
+        assert_eq!(game.status(), Status::Lost(Loss::GaveUp));
+        game.lose(Loss::OutOfTime);
+        assert_eq!(game.status(), Status::Lost(Loss::GaveUp));
+    }
+
+    #[test]
+    fn the_default_limit_is_a_binary_search() {
+        assert_eq!(Game::default_max_attempts(&(1..=100)), 7);
+        assert_eq!(Game::default_max_attempts(&(1..=1)), 1);
+        assert_eq!(Game::default_max_attempts(&(0..=u32::MAX)), 33);
+    }
+
+    #[test]
+    fn the_whole_u32_range_can_be_played() {
+        let mut game = Game::with_secret(0..=u32::MAX, u32::MAX);
+        assert_eq!(game.guess(0), Ok(Outcome::TooSmall));
+        assert_eq!(game.known_range(), 1..=u32::MAX);
+        assert_eq!(game.guess(u32::MAX), Ok(Outcome::Win));
+    }
+}
@@ -0,0 +1,379 @@
+//! Hints the player can buy during a game, paying with a part of the score.
+//!
+//! A hint is anything implementing [`Hint`]: [`Game::use_hint`] asks it what
+//! it tells about the secret number and logs the answer in the game, where
+//! the [score](crate::score::score) finds the penalty.
+
+use crate::i18n;
+use crate::Game;
+use std::fmt;
+
+/// The primes that [`Divisibility`] accepts.
+pub const SMALL_PRIMES: [u32; 6] = [2, 3, 5, 7, 11, 13];
+
+/// Something the game can tell about its secret number, at a price.
+pub trait Hint {
+    /// How the hint is asked for at the prompt, like `parity`.
+    fn name(&self) -> String;
+
+    /// How the hint is listed in the help, when it takes an argument.
+    fn usage(&self) -> String {
+        self.name()
+    }
+
+    fn description(&self) -> &'static str;
+
+    /// The percent of the score the hint costs.
+    fn penalty(&self) -> u32;
+
+    /// What the hint tells about the secret number of `game`, or why it
+    /// cannot be given now.
+    fn reveal(&self, game: &Game) -> Result<String, String>;
+}
+
+/// Whether the secret number is even or odd.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parity;
+
+impl Hint for Parity {
+    fn name(&self) -> String {
+        "parity".to_string()
+    }
+
+    fn description(&self) -> &'static str {
+        i18n::text("hint.parity")
+    }
+
+    fn penalty(&self) -> u32 {
+        10
+    }
+
+    fn reveal(&self, game: &Game) -> Result<String, String> {
+        let answer = if game.secret_number().is_multiple_of(2) {
+            "hint.even"
+        } else {
+            "hint.odd"
+        };
+        Ok(i18n::text(answer).to_string())
+    }
+}
+
+/// Whether the secret number is divisible by a prime of [`SMALL_PRIMES`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Divisibility(pub u32);
+
+impl Hint for Divisibility {
+    fn name(&self) -> String {
+        format!("divisible {}", self.0)
+    }
+
+    fn usage(&self) -> String {
+        "divisible <prime>".to_string()
+    }
+
+    fn description(&self) -> &'static str {
+        i18n::text("hint.divisible")
+    }
+
+    fn penalty(&self) -> u32 {
+        10
+    }
+
+    fn reveal(&self, game: &Game) -> Result<String, String> {
+        let answer = if game.secret_number().is_multiple_of(self.0) {
+            "hint.divisible-by"
+        } else {
+            "hint.not-divisible-by"
+        };
+        Ok(i18n::fill(answer, &[("prime", &self.0)]))
+    }
+}
+
+/// The sum of the digits of the secret number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DigitSum;
+
+impl Hint for DigitSum {
+    fn name(&self) -> String {
+        "digits".to_string()
+    }
+
+    fn description(&self) -> &'static str {
+        i18n::text("hint.digits")
+    }
+
+    fn penalty(&self) -> u32 {
+        15
+    }
+
+    fn reveal(&self, game: &Game) -> Result<String, String> {
+        let mut number = game.secret_number();
+        let mut sum = 0;
+        while number > 0 {
+            sum += number % 10;
+            number /= 10;
+        }
+        Ok(i18n::fill("hint.digit-sum", &[("sum", &sum)]))
+    }
+}
+
+/// Whether the secret number is close to the last guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Within(pub u32);
+
+impl Hint for Within {
+    fn name(&self) -> String {
+        "within".to_string()
+    }
+
+    fn description(&self) -> &'static str {
+        i18n::text("hint.within")
+    }
+
+    fn penalty(&self) -> u32 {
+        20
+    }
+
+    fn reveal(&self, game: &Game) -> Result<String, String> {
+        let guess = match game.history().last() {
+            Some(attempt) => attempt.guess,
+            None => return Err(i18n::text("hint.no-guess").to_string()),
+        };
+        let answer = if game.secret_number().abs_diff(guess) <= self.0 {
+            "hint.close"
+        } else {
+            "hint.not-close"
+        };
+        Ok(i18n::fill(
+            answer,
+            &[("distance", &self.0), ("guess", &guess)],
+        ))
+    }
+}
+
+/// Which quarter of the numbers still possible holds the secret number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interval;
+
+impl Hint for Interval {
+    fn name(&self) -> String {
+        "interval".to_string()
+    }
+
+    fn description(&self) -> &'static str {
+        i18n::text("hint.interval")
+    }
+
+    fn penalty(&self) -> u32 {
+        25
+    }
+
+    fn reveal(&self, game: &Game) -> Result<String, String> {
+        let range = game.known_range();
+        let (start, end) = (u64::from(*range.start()), u64::from(*range.end()));
+        let size = end - start + 1;
+        if size <= 4 {
+            return Err(i18n::plural("hint.few", size, &[]));
+        }
+        // quarters of the same size, rounded up, the last one may be shorter
+        let quarter = size.div_ceil(4);
+        let low = start + (u64::from(game.secret_number()) - start) / quarter * quarter;
+        let high = (low + quarter - 1).min(end);
+        Ok(i18n::fill("hint.quarter", &[("min", &low), ("max", &high)]))
+    }
+}
+
+/// Every hint, in the order of the help; [`Divisibility`] is listed once.
+pub fn catalogue() -> Vec<Box<dyn Hint>> {
+    vec![
+        Box::new(Parity),
+        Box::new(Divisibility(SMALL_PRIMES[0])),
+        Box::new(DigitSum),
+        Box::new(Within(10)),
+        Box::new(Interval),
+    ]
+}
+
+/// The text given to [`parse`] is not a hint of the [`catalogue`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseHintError {
+    Unknown(String),
+    /// `divisible` without one of the [`SMALL_PRIMES`].
+    Prime(String),
+}
+
+impl fmt::Display for ParseHintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseHintError::Unknown(name) => {
+                f.write_str(&i18n::fill("hint.unknown", &[("name", name)]))
+            }
+            ParseHintError::Prime(prime) => {
+                f.write_str(&i18n::fill("hint.prime", &[("text", prime)]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseHintError {}
+
+/// The hint asked by `text`, like `parity` or `divisible 7`.
+pub fn parse(text: &str) -> Result<Box<dyn Hint>, ParseHintError> {
+    let mut words = text.split_whitespace();
+    let name = words.next().unwrap_or("");
+    let argument = words.collect::<Vec<_>>().join(" ");
+    match name {
+        "divisible" => match argument.parse() {
+            Ok(prime) if SMALL_PRIMES.contains(&prime) => Ok(Box::new(Divisibility(prime))),
+            _ => Err(ParseHintError::Prime(argument)),
+        },
+        _ if !argument.is_empty() => Err(ParseHintError::Unknown(text.trim().to_string())),
+        _ => catalogue()
+            .into_iter()
+            .find(|hint| hint.name() == name)
+            .ok_or_else(|| ParseHintError::Unknown(name.to_string())),
+    }
+}
+
+/// A hint that [`Game::use_hint`] did not give.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HintError {
+    Finished,
+    /// The hint makes no sense now, for the reason given.
+    Unavailable(String),
+}
+
+impl fmt::Display for HintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HintError::Finished => f.write_str(i18n::text("finished")),
+            HintError::Unavailable(reason) => {
+                f.write_str(&i18n::fill("hint.unavailable", &[("reason", reason)]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for HintError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ops::RangeInclusive;
+
+    fn reveal(hint: &dyn Hint, range: RangeInclusive<u32>, secret: u32) -> Result<String, String> {
+        hint.reveal(&Game::with_secret(range, secret))
+    }
+
+    #[test]
+    fn parity_tells_even_from_odd() {
+        assert_eq!(
+            reveal(&Parity, 1..=100, 42).unwrap(),
+            "The secret number is even."
+        );
+        assert_eq!(
+            reveal(&Parity, 1..=100, 7).unwrap(),
+            "The secret number is odd."
+        );
+    }
+
+    #[test]
+    fn divisibility_names_its_prime() {
+        assert_eq!(
+            reveal(&Divisibility(7), 1..=100, 42).unwrap(),
+            "The secret number is divisible by 7."
+        );
+        assert_eq!(
+            reveal(&Divisibility(5), 1..=100, 42).unwrap(),
+            "The secret number is not divisible by 5."
+        );
+    }
+
+    #[test]
+    fn digit_sum_adds_every_digit() {
+        assert_eq!(
+            reveal(&DigitSum, 1..=1000, 907).unwrap(),
+            "The digits of the secret number add up to 16."
+        );
+        assert_eq!(
+            reveal(&DigitSum, 1..=100, 5).unwrap(),
+            "The digits of the secret number add up to 5."
+        );
+    }
+
+    #[test]
+    fn within_measures_from_the_last_guess() {
+        let mut game = Game::with_secret(1..=100, 42);
+        assert_eq!(
+            Within(10).reveal(&game),
+            Err("make a guess first".to_string())
+        );
+        game.guess(30).unwrap();
+        game.guess(52).unwrap();
+        assert_eq!(
+            Within(10).reveal(&game).unwrap(),
+            "The secret number is within 10 of 52."
+        );
+        assert_eq!(
+            Within(9).reveal(&game).unwrap(),
+            "The secret number is not within 9 of 52."
+        );
+    }
+
+    #[test]
+    fn interval_refuses_four_numbers_or_less() {
+        assert_eq!(
+            reveal(&Interval, 1..=1, 1),
+            Err("only 1 number is still possible".to_string())
+        );
+        assert_eq!(
+            reveal(&Interval, 1..=3, 2),
+            Err("only 3 numbers are still possible".to_string())
+        );
+    }
+
+    #[test]
+    fn interval_quarters_are_rounded_up() {
+        // five numbers make quarters of two, the last one holds only 5
+        let quarters: Vec<_> = (1..=5)
+            .map(|secret| reveal(&Interval, 1..=5, secret).unwrap())
+            .collect();
+        assert_eq!(
+            quarters,
+            [
+                "The secret number is between 1 and 2.",
+                "The secret number is between 1 and 2.",
+                "The secret number is between 3 and 4.",
+                "The secret number is between 3 and 4.",
+                "The secret number is between 5 and 5.",
+            ]
+        );
+        assert_eq!(
+            reveal(&Interval, 1..=100, 42).unwrap(),
+            "The secret number is between 26 and 50."
+        );
+    }
+
+    #[test]
+    fn interval_only_splits_what_is_still_possible() {
+        let mut game = Game::with_secret(1..=100, 42);
+        game.guess(50).unwrap();
+        // 1..=49 makes quarters of 13: 1..=13, 14..=26, 27..=39, 40..=49
+        assert_eq!(
+            Interval.reveal(&game).unwrap(),
+            "The secret number is between 40 and 49."
+        );
+    }
+
+    #[test]
+    fn no_hint_while_the_evil_secret_is_open() {
+        let mut game = Game::evil(1..=100);
+        assert_eq!(
+            game.use_hint(&Parity),
+            Err(HintError::Unavailable(
+                "the secret number is not chosen yet".to_string()
+            ))
+        );
+        assert!(game.hints_used().is_empty());
+    }
+}
@@ -0,0 +1,474 @@
+//! A small HTTP/1.1 server exposing the guessing game as a JSON API, enabled
+//! by the `http` feature.
+//!
+//! ```text
+//! POST   /games               {"difficulty": "hard", "min": 1, "max": 100, "seed": 42,
+//!                              "max_attempts": 7, "time_limit": 60}  all optional
+//!                             201, the state of the new game
+//! POST   /games/{id}/guesses  {"guess": 42}
+//!                             200, {"result": "less", "attempts": 1, "attempts_left": 6, "status": "playing"}
+//! GET    /games/{id}          200, the state of the game and its history
+//! DELETE /games/{id}          204
+//! ```
+//!
+//! The options of `POST /games` mean the same as on the command line:
+//! `max_attempts` 0 is no limit and `time_limit` is in seconds. `less` means
+//! that the guess is less than the secret number, like in the
+//! [`protocol`](crate::protocol). The secret number is part of the state once
+//! the game is finished. Errors are `{"error": "message"}` with a 4xx status.
+//!
+//! Every request gets its own connection, which is closed after the response.
+//!
+//! A game without a request for [`DEFAULT_GAME_TTL`] is forgotten, finished
+//! or not, and while [`DEFAULT_MAX_GAMES`] games are kept `POST /games` gets
+//! a 503: a server running for months does not keep every game ever played.
+
+use crate::cli::Options;
+use crate::{Game, GuessError, Loss, Outcome, Status};
+use rand::rngs::StdRng;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Where `guess-http` listens without `--addr`.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:8080";
+
+/// How long a game is kept after its last request, see
+/// [`HttpServer::with_game_ttl`].
+pub const DEFAULT_GAME_TTL: Duration = Duration::from_secs(30 * 60);
+
+/// The games kept at most, see [`HttpServer::with_max_games`].
+pub const DEFAULT_MAX_GAMES: usize = 10_000;
+
+/// Request bodies bigger than this are refused.
+const MAX_BODY: usize = 64 * 1024;
+
+/// The body of `POST /games`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct NewGame {
+    difficulty: Option<String>,
+    min: Option<u32>,
+    max: Option<u32>,
+    seed: Option<u64>,
+    max_attempts: Option<u32>,
+    time_limit: Option<u64>,
+}
+
+/// The body of `POST /games/{id}/guesses`.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NewGuess {
+    guess: u32,
+}
+
+#[derive(Debug, Serialize)]
+struct GameState {
+    id: u64,
+    min: u32,
+    max: u32,
+    /// Plays the same game again with `POST /games`.
+    seed: Option<u64>,
+    max_attempts: Option<u32>,
+    attempts: u32,
+    attempts_left: Option<u32>,
+    time_limit: Option<u64>,
+    elapsed: f64,
+    status: &'static str,
+    loss: Option<&'static str>,
+    secret_number: Option<u32>,
+    history: Vec<GuessState>,
+}
+
+#[derive(Debug, Serialize)]
+struct GuessState {
+    guess: u32,
+    result: &'static str,
+}
+
+/// The games of a [`HttpServer`].
+struct Games {
+    next_id: u64,
+    games: HashMap<u64, Kept>,
+    /// Picks the seeds of the games created without one.
+    rng: StdRng,
+    ttl: Duration,
+    max_games: usize,
+}
+
+/// A game and the time of its last request.
+struct Kept {
+    game: Game,
+    used: Instant,
+}
+
+/// An HTTP request, as much of it as the API needs.
+#[derive(Debug)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+/// An HTTP response with a JSON body.
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Option<serde_json::Value>,
+    location: Option<String>,
+}
+
+impl Response {
+    fn json<T: Serialize>(status: u16, body: &T) -> Response {
+        Response {
+            status,
+            body: Some(serde_json::to_value(body).expect("the API types are valid JSON")),
+            location: None,
+        }
+    }
+
+    fn error<M: ToString>(status: u16, message: M) -> Response {
+        Response::json(status, &json!({ "error": message.to_string() }))
+    }
+
+    fn write_to<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let body = match &self.body {
+            Some(body) => body.to_string(),
+            None => String::new(),
+        };
+        write!(out, "HTTP/1.1 {} {}\r\n", self.status, reason(self.status))?;
+        if self.body.is_some() {
+            write!(out, "Content-Type: application/json\r\n")?;
+        }
+        if let Some(location) = &self.location {
+            write!(out, "Location: {}\r\n", location)?;
+        }
+        write!(out, "Content-Length: {}\r\n", body.len())?;
+        write!(out, "Connection: close\r\n\r\n{}", body)?;
+        out.flush()
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        422 => "Unprocessable Entity",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+/// The guessing game as an HTTP JSON API, see the [module](self) for the
+/// routes. Every connection is served in its own thread.
+pub struct HttpServer {
+    listener: TcpListener,
+    games: Arc<Mutex<Games>>,
+}
+
+impl HttpServer {
+    /// Listens on `addr`; the games created without a seed get one from `rng`.
+    pub fn bind<A: ToSocketAddrs>(addr: A, rng: StdRng) -> io::Result<HttpServer> {
+        Ok(HttpServer {
+            listener: TcpListener::bind(addr)?,
+            games: Arc::new(Mutex::new(Games {
+                next_id: 1,
+                games: HashMap::new(),
+                rng,
+                ttl: DEFAULT_GAME_TTL,
+                max_games: DEFAULT_MAX_GAMES,
+            })),
+        })
+    }
+
+    /// Forgets the games without a request for `ttl`, finished or not.
+    pub fn with_game_ttl(self, ttl: Duration) -> HttpServer {
+        lock(&self.games).ttl = ttl;
+        self
+    }
+
+    /// Refuses to create a game, with a 503, while `max_games` are kept.
+    pub fn with_max_games(self, max_games: usize) -> HttpServer {
+        lock(&self.games).max_games = max_games;
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections forever.
+    pub fn run(&self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let games = Arc::clone(&self.games);
+            thread::spawn(move || {
+                if let Err(err) = serve(stream, &games) {
+                    eprintln!("{}", err);
+                }
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Answers the request of one connection.
+fn serve(stream: TcpStream, games: &Mutex<Games>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    let response = match read_request(&mut reader) {
+        Ok(Some(request)) => {
+            eprintln!("{} {}", request.method, request.path);
+            handle(&request, games)
+        }
+        Ok(None) => return Ok(()),
+        Err(ref err) if err.kind() == io::ErrorKind::InvalidData => Response::error(400, err),
+        Err(err) => return Err(err),
+    };
+    response.write_to(&mut writer)
+}
+
+/// Reads the request line, the headers and the body; `None` when the
+/// connection is closed before a request.
+fn read_request<R: BufRead>(reader: &mut R) -> io::Result<Option<Request>> {
+    let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message.to_string());
+
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (method, target) = match words.as_slice() {
+        [method, target, version] if version.starts_with("HTTP/1.") => (*method, *target),
+        _ => return Err(invalid("malformed request line")),
+    };
+    // the query string is not used
+    let path = target.split('?').next().unwrap_or(target);
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            return Err(invalid("the headers are not finished"));
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let (name, value) = match header.find(':') {
+            Some(i) => (&header[..i], header[i + 1..].trim()),
+            None => return Err(invalid("malformed header")),
+        };
+        if name.eq_ignore_ascii_case("Content-Length") {
+            content_length = value
+                .parse()
+                .map_err(|_| invalid("malformed Content-Length"))?;
+        }
+    }
+    if content_length > MAX_BODY {
+        return Err(invalid("the body is too large"));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    }))
+}
+
+fn handle(request: &Request, games: &Mutex<Games>) -> Response {
+    let segments: Vec<&str> = request
+        .path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let id = |segment: &str| segment.parse::<u64>().ok();
+    lock(games).expire();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("POST", ["games"]) => create(request, &mut lock(games)),
+        ("GET", ["games", game]) => match id(game).and_then(|id| lock(games).state(id)) {
+            Some(state) => Response::json(200, &state),
+            None => not_found(),
+        },
+        ("DELETE", ["games", game]) => {
+            match id(game).and_then(|id| lock(games).games.remove(&id)) {
+                Some(_) => Response {
+                    status: 204,
+                    body: None,
+                    location: None,
+                },
+                None => not_found(),
+            }
+        }
+        ("POST", ["games", game, "guesses"]) => match id(game) {
+            Some(id) => guess(request, id, &mut lock(games)),
+            None => not_found(),
+        },
+        (_, ["games"]) | (_, ["games", _]) | (_, ["games", _, "guesses"]) => {
+            Response::error(405, format!("{} is not allowed here", request.method))
+        }
+        _ => not_found(),
+    }
+}
+
+fn create(request: &Request, games: &mut Games) -> Response {
+    let new_game: NewGame = if request.body.iter().all(u8::is_ascii_whitespace) {
+        NewGame::default()
+    } else {
+        match serde_json::from_slice(&request.body) {
+            Ok(new_game) => new_game,
+            Err(err) => return Response::error(400, err),
+        }
+    };
+
+    // the same rules as the command line
+    let difficulty = match new_game.difficulty.as_deref().map(str::parse).transpose() {
+        Ok(difficulty) => difficulty,
+        Err(err) => return Response::error(422, err),
+    };
+    let seed = new_game.seed.unwrap_or_else(|| games.rng.gen());
+    let options = Options {
+        seed: Some(seed),
+        difficulty,
+        min: new_game.min,
+        max: new_game.max,
+        max_attempts: new_game.max_attempts,
+        time_limit: new_game.time_limit.map(Duration::from_secs),
+        ..Options::default()
+    };
+    let range = options.range();
+    if range.is_empty() {
+        let message = format!("min {} is bigger than max {}", range.start(), range.end());
+        return Response::error(422, message);
+    }
+    if games.games.len() >= games.max_games {
+        return Response::error(503, "too many games, try again later");
+    }
+
+    let id = games.next_id;
+    games.next_id += 1;
+    let kept = Kept {
+        game: options.new_game(),
+        used: Instant::now(),
+    };
+    games.games.insert(id, kept);
+
+    let mut response = Response::json(201, &games.state(id));
+    response.location = Some(format!("/games/{}", id));
+    response
+}
+
+fn guess(request: &Request, id: u64, games: &mut Games) -> Response {
+    let game = match games.games.get_mut(&id) {
+        Some(kept) => {
+            kept.used = Instant::now();
+            &mut kept.game
+        }
+        None => return not_found(),
+    };
+    let new_guess: NewGuess = match serde_json::from_slice(&request.body) {
+        Ok(new_guess) => new_guess,
+        Err(err) => return Response::error(400, err),
+    };
+
+    match game.guess(new_guess.guess) {
+        Ok(outcome) => Response::json(
+            200,
+            &json!({
+                "result": result(outcome),
+                "attempts": game.attempts(),
+                "attempts_left": game.attempts_left(),
+                "status": status(game.status()),
+            }),
+        ),
+        Err(err @ GuessError::OutOfRange { .. }) => Response::error(422, err),
+        Err(err @ GuessError::TimeUp) | Err(err @ GuessError::Finished) => {
+            Response::error(409, err)
+        }
+    }
+}
+
+fn not_found() -> Response {
+    Response::error(404, "not found")
+}
+
+impl Games {
+    /// Forgets the games without a request for the TTL.
+    fn expire(&mut self) {
+        let ttl = self.ttl;
+        self.games.retain(|_, kept| kept.used.elapsed() < ttl);
+    }
+
+    /// The state of the game `id`, which counts as a request to it.
+    fn state(&mut self, id: u64) -> Option<GameState> {
+        let kept = self.games.get_mut(&id)?;
+        kept.used = Instant::now();
+        let game = &kept.game;
+        let loss = match game.status() {
+            Status::Lost(Loss::OutOfAttempts) => Some("attempts"),
+            Status::Lost(Loss::OutOfTime) => Some("time"),
+            Status::Lost(Loss::GaveUp) => Some("gave_up"),
+            _ => None,
+        };
+        Some(GameState {
+            id,
+            min: *game.range().start(),
+            max: *game.range().end(),
+            seed: game.seed(),
+            max_attempts: game.max_attempts(),
+            attempts: game.attempts(),
+            attempts_left: game.attempts_left(),
+            time_limit: game.time_limit().map(|limit| limit.as_secs()),
+            elapsed: game.elapsed().as_secs_f64(),
+            status: status(game.status()),
+            loss,
+            secret_number: Some(game.secret_number()).filter(|_| game.is_finished()),
+            history: game
+                .history()
+                .iter()
+                .map(|attempt| GuessState {
+                    guess: attempt.guess,
+                    result: result(attempt.outcome),
+                })
+                .collect(),
+        })
+    }
+}
+
+fn result(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooSmall => "less",
+        Outcome::TooBig => "greater",
+        Outcome::Win => "equal",
+    }
+}
+
+fn status(status: Status) -> &'static str {
+    match status {
+        Status::Playing => "playing",
+        Status::Won => "won",
+        Status::Lost(_) => "lost",
+    }
+}
+
+fn lock(games: &Mutex<Games>) -> MutexGuard<'_, Games> {
+    games
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
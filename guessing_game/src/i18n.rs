@@ -0,0 +1,1179 @@
+//! The messages of the number game, in English and in Italian.
+//!
+//! Every message has a key in a table of each language, like `banner`, and
+//! its placeholders are names in braces, like `{min}`. A message that depends
+//! on a count has a key for each plural form, `attempts-left.one` and
+//! `attempts-left.other`, and [`plural`] picks the one of the language.
+//!
+//! The language is chosen once, by [`Options::from_env`](crate::cli::Options::from_env)
+//! from `--lang` or the locale of the environment, and English is the default.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A language of the messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Lang {
+    #[default]
+    English,
+    Italian,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::English, Lang::Italian];
+
+    /// The ISO 639-1 code, as in `--lang it` or `LANG=it_IT.UTF-8`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::English => "en",
+            Lang::Italian => "it",
+        }
+    }
+
+    /// The language of a locale like `it_IT.UTF-8`; `None` for the ones
+    /// without messages, like `C` or `fr_FR`.
+    pub fn from_locale(locale: &str) -> Option<Lang> {
+        let code = locale.split(['_', '.', '@', '-']).next().unwrap_or("");
+        Lang::ALL
+            .iter()
+            .copied()
+            .find(|lang| lang.code().eq_ignore_ascii_case(code))
+    }
+
+    /// The language of the locale of the environment: `LC_ALL`, then
+    /// `LC_MESSAGES`, then `LANG`, the first that is set.
+    pub fn from_env() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|locale| !locale.is_empty())
+            .and_then(|locale| Lang::from_locale(&locale))
+            .unwrap_or_default()
+    }
+
+    /// The plural form of `count` things, `one` or `other`: English and
+    /// Italian only have a singular for 1, other languages would need more.
+    pub fn plural_form(self, count: u64) -> &'static str {
+        if count == 1 {
+            "one"
+        } else {
+            "other"
+        }
+    }
+
+    /// Every key of the language and its message.
+    pub fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::English => ENGLISH,
+            Lang::Italian => ITALIAN,
+        }
+    }
+}
+
+impl fmt::Display for Lang {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// The name is not the code of one of the [`Lang`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLangError(String);
+
+impl fmt::Display for ParseLangError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown language '{}' (expected en or it)", self.0)
+    }
+}
+
+impl std::error::Error for ParseLangError {}
+
+impl FromStr for Lang {
+    type Err = ParseLangError;
+
+    fn from_str(s: &str) -> Result<Lang, ParseLangError> {
+        Lang::from_locale(s).ok_or_else(|| ParseLangError(s.to_string()))
+    }
+}
+
+// the index of the language in Lang::ALL
+static LANG: AtomicUsize = AtomicUsize::new(0);
+
+/// Makes `lang` the language of the messages, for the whole process.
+pub fn set_lang(lang: Lang) {
+    let index = Lang::ALL.iter().position(|&l| l == lang).unwrap_or(0);
+    LANG.store(index, Ordering::Relaxed);
+}
+
+/// The language of the messages, English until [`set_lang`] is called.
+pub fn lang() -> Lang {
+    Lang::ALL[LANG.load(Ordering::Relaxed)]
+}
+
+/// The message of `key` in the current language, or in English when the
+/// language misses it; the key itself when English misses it too.
+pub fn text(key: &'static str) -> &'static str {
+    find(lang(), key)
+        .or_else(|| find(Lang::English, key))
+        .unwrap_or(key)
+}
+
+fn find(lang: Lang, key: &str) -> Option<&'static str> {
+    lang.catalog()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// The message of `key` with its placeholders replaced by `args`.
+pub fn fill(key: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    substitute(text(key), args)
+}
+
+/// The message of `key` for `count` things, in the plural form of the
+/// current language, with `{count}` and the placeholders of `args` replaced.
+pub fn plural(key: &'static str, count: u64, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let form = format!("{}.{}", key, lang().plural_form(count));
+    let template = find(lang(), &form)
+        .or_else(|| find(Lang::English, &form))
+        .unwrap_or(key);
+    let mut all: Vec<(&str, &dyn fmt::Display)> = vec![("count", &count)];
+    all.extend_from_slice(args);
+    substitute(template, &all)
+}
+
+/// `template` with every `{name}` of `args` replaced by its value; the other
+/// braces are left as they are.
+fn substitute(template: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        rest = &rest[open..];
+        let value = rest.find('}').and_then(|close| {
+            let name = &rest[1..close];
+            args.iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, value)| (close, value))
+        });
+        match value {
+            Some((close, value)) => {
+                out.push_str(&value.to_string());
+                rest = &rest[close + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// The reference catalog: every key has a message here.
+const ENGLISH: &[(&str, &str)] = &[
+    // the binaries
+    ("banner", "Guess the number between {min} and {max}!"),
+    ("attempts-left.one", "You have {count} attempt."),
+    ("attempts-left.other", "You have {count} attempts."),
+    ("seconds-left.one", "You have {count} second."),
+    ("seconds-left.other", "You have {count} seconds."),
+    ("error.high-scores", "Cannot read the high scores: {error}"),
+    ("error.stats", "Cannot read the statistics: {error}"),
+    ("error.resume", "Cannot resume the game: {error}"),
+    ("error.score", "Cannot save the score: {error}"),
+    ("error.replay", "Cannot save the replay: {error}"),
+    ("error.record", "Cannot record the game: {error}"),
+    ("no-data-dir", "no data directory"),
+    ("listening", "Listening on {addr}"),
+    ("listen-error", "Cannot listen on {addr}: {error}"),
+    // before the game
+    (
+        "intro.temperature",
+        "Every answer says if the guess is hot, warm or cold, if it is warmer or colder than the last one, and if it is too small or too big.",
+    ),
+    (
+        "intro.temperature-only",
+        "Every answer says if the guess is hot, warm or cold, and if it is warmer or colder than the last one: nothing else!",
+    ),
+    (
+        "intro.evil",
+        "Beware: this game has no secret number yet, it picks one as late as it can.",
+    ),
+    (
+        "intro.lies.one",
+        "Careful: up to {count} of the answers can be a lie, only \"You win!\" is always true.",
+    ),
+    (
+        "intro.lies.other",
+        "Careful: up to {count} of the answers can be lies, only \"You win!\" is always true.",
+    ),
+    (
+        "intro.commands",
+        "Type /help for the commands, like /hint, /save or /giveup.",
+    ),
+    (
+        "secret.prompt",
+        "{chooser}, choose the secret number between {min} and {max}: ",
+    ),
+    ("secret.invalid", "That is not a number between {min} and {max}."),
+    // the guesses
+    ("prompt", "Please input your guess."),
+    ("guessed", "You guessed: {guess}"),
+    ("input.empty", "Please type a number."),
+    (
+        "input.negative",
+        "{text} is negative, the secret number is never below 0.",
+    ),
+    ("input.overflow", "{text} is too big, a guess is at most {max}."),
+    ("input.not-a-number", "'{text}' is not a number."),
+    (
+        "input.out-of-range",
+        "{guess} is out of range, the secret number is between {min} and {max}.",
+    ),
+    (
+        "input.duplicate",
+        "You already guessed {guess} at attempt {attempt}, the answer is the same.",
+    ),
+    ("input.help", "{error} Type /help for the commands."),
+    ("input.unreadable", "Cannot read the input: {error}"),
+    ("input.script", "line {line}: '{text}' is not a guess"),
+    (
+        "guess.out-of-range",
+        "{guess} is out of range, the secret number is between {min} and {max}",
+    ),
+    ("guess.time-up", "time is up"),
+    ("finished", "the game is already finished"),
+    // the answers
+    ("feedback.win", "You win!"),
+    ("feedback.too-small", "Too small!"),
+    ("feedback.too-big", "Too big!"),
+    ("temperature.hot", "Hot!"),
+    ("temperature.warm", "Warm."),
+    ("temperature.cold", "Cold."),
+    ("trend.warmer", "Warmer than the last guess."),
+    ("trend.colder", "Colder than the last guess."),
+    ("trend.same", "As far as the last guess."),
+    ("outcome.too-small", "too small"),
+    ("outcome.too-big", "too big"),
+    ("outcome.win", "correct"),
+    // the end of the game
+    ("loss.attempts", "No attempts left!"),
+    ("loss.time", "Time is up!"),
+    ("loss.gave-up", "You gave up!"),
+    ("loss.secret", "The secret number was {secret}."),
+    ("lies.none", "The game told no lies."),
+    ("lies.one", "The game lied once, about {lies}."),
+    ("lies.other", "The game lied {count} times, about {lies}."),
+    ("score", "Your score: {score}"),
+    ("score.high", "New high score for {difficulty}!"),
+    ("replay.saved", "Replay saved to {path}"),
+    (
+        "save.resume",
+        "Game saved to {path}, continue it with --resume {path}",
+    ),
+    ("save.error", "Cannot save the game: {error}"),
+    ("save.finished", "the game is finished, there is nothing to save"),
+    ("save.not-saved", "not a saved game ({error})"),
+    ("save.no-version", "not a saved game (no format version)"),
+    ("save.version", "saved with format version {version}, but only versions {oldest} to {newest} can be read"),
+    ("save.over", "the saved game is already finished"),
+    ("corrupt", "corrupt ({reason})"),
+    ("corrupt.range", "the secret number {secret} is not between {min} and {max}"),
+    ("corrupt.guess", "guess {index}: {guess} was {recorded}, but it is {current}"),
+    ("corrupt.refused", "guess {index}: {error}"),
+    ("corrupt.hint.one", "hint {hint} after guess {after}, but there is {count} guess"),
+    ("corrupt.hint.other", "hint {hint} after guess {after}, but there are {count} guesses"),
+    // the bots
+    ("bot.played", "The {strategy} bot played:"),
+    ("bot.lie", " (a lie)"),
+    ("bot.found.one", "Found {secret} in {count} attempt."),
+    ("bot.found.other", "Found {secret} in {count} attempts."),
+    (
+        "bot.not-found.one",
+        "Not found in {count} attempt, the secret number was {secret}.",
+    ),
+    (
+        "bot.not-found.other",
+        "Not found in {count} attempts, the secret number was {secret}.",
+    ),
+    // the players of a hot-seat game
+    ("seat.turn", "{name}, your turn."),
+    ("seat.leaves", "{name} leaves the game."),
+    ("seat.guessed", "{name} guessed: {guess}"),
+    ("seat.wins", "{name} wins!"),
+    ("seat.attempts", "Attempts:"),
+    ("seat.winner.one", "The winner is {name} with {count} attempt!"),
+    ("seat.winner.other", "The winner is {name} with {count} attempts!"),
+    ("seat.nobody", "Nobody won."),
+    // the commands of the prompt
+    (
+        "help.header",
+        "Type a number to guess it, or one of these commands (the beginning of a name is enough, like /his):",
+    ),
+    (
+        "command.hint",
+        "suggest a guess and list the hints, or buy one for a part of the score",
+    ),
+    (
+        "command.history",
+        "list the guesses made so far and their answers",
+    ),
+    (
+        "command.range",
+        "show the interval where the secret number still is",
+    ),
+    ("command.stats", "show the attempts and the time used and left"),
+    ("command.giveup", "give up and reveal the secret number"),
+    (
+        "command.restart",
+        "start a new game with another secret number",
+    ),
+    (
+        "command.save",
+        "save the game, to continue it later with --resume",
+    ),
+    ("command.load", "continue a saved game instead of this one"),
+    ("command.help", "show this help"),
+    ("command.quit", "stop playing, the game is saved"),
+    (
+        "command.unknown",
+        "unknown command /{name}, type /help for the list",
+    ),
+    ("command.ambiguous", "/{name} could be {names}"),
+    ("command.or", " or "),
+    ("command.argument", "/{name} takes no argument"),
+    ("range", "The secret number is between {min} and {max}."),
+    (
+        "restart",
+        "New game: guess the number between {min} and {max}!",
+    ),
+    (
+        "load.done",
+        "Game loaded: the number is between {min} and {max}.",
+    ),
+    ("load.error", "Cannot load the game: {error}"),
+    ("history.empty", "No guesses yet."),
+    ("history.hint", "hint"),
+    ("stats.attempts", "Attempts: {count}"),
+    ("stats.attempts-of", " of {max}, {left} left"),
+    ("stats.time.one", "Time: {count} second"),
+    ("stats.time.other", "Time: {count} seconds"),
+    ("stats.time-of", " of {limit}, {left} left"),
+    ("stats.hints", "Hints: {count}, the score is cut by {penalty}%"),
+    (
+        "stats.possible",
+        "Numbers still possible: {count}, from {min} to {max}",
+    ),
+    (
+        "stats.binary-search.one",
+        "A binary search needs at most {count} more guess.",
+    ),
+    (
+        "stats.binary-search.other",
+        "A binary search needs at most {count} more guesses.",
+    ),
+    // the hints
+    (
+        "hint.try",
+        "Try {guess}: whatever the answer, half of the numbers still possible are left.",
+    ),
+    (
+        "hint.list",
+        "Hints to buy with /hint <name>, for a part of the score:",
+    ),
+    (
+        "hint.bought",
+        "{answer} (-{penalty}% of the score, -{total}% in total)",
+    ),
+    ("hint.parity", "whether the secret number is even or odd"),
+    (
+        "hint.divisible",
+        "whether the secret number is divisible by 2, 3, 5, 7, 11 or 13",
+    ),
+    ("hint.digits", "the sum of the digits of the secret number"),
+    (
+        "hint.within",
+        "whether the secret number is within 10 of the last guess",
+    ),
+    (
+        "hint.interval",
+        "which quarter of the numbers still possible holds the secret number",
+    ),
+    ("hint.even", "The secret number is even."),
+    ("hint.odd", "The secret number is odd."),
+    ("hint.divisible-by", "The secret number is divisible by {prime}."),
+    (
+        "hint.not-divisible-by",
+        "The secret number is not divisible by {prime}.",
+    ),
+    (
+        "hint.digit-sum",
+        "The digits of the secret number add up to {sum}.",
+    ),
+    (
+        "hint.close",
+        "The secret number is within {distance} of {guess}.",
+    ),
+    (
+        "hint.not-close",
+        "The secret number is not within {distance} of {guess}.",
+    ),
+    ("hint.quarter", "The secret number is between {min} and {max}."),
+    ("hint.no-guess", "make a guess first"),
+    ("hint.few.one", "only {count} number is still possible"),
+    ("hint.few.other", "only {count} numbers are still possible"),
+    (
+        "hint.unknown",
+        "there is no hint called {name}, type /hint for the list",
+    ),
+    (
+        "hint.prime",
+        "'{text}' is not a small prime, choose one of 2, 3, 5, 7, 11 or 13",
+    ),
+    ("hint.unavailable", "no hint now: {reason}"),
+    // the full screen of --tui
+    ("tui.start", "Type a number and press Enter, Esc to leave."),
+    ("tui.not-a-digit", "'{key}' is not a digit."),
+    ("tui.leading-zero", "No leading zeros."),
+    (
+        "tui.too-big",
+        "{typed} is bigger than {max}, the end of the range.",
+    ),
+    ("tui.empty", "Type a number first."),
+    ("tui.won.one", "You win! Found in {count} attempt."),
+    ("tui.won.other", "You win! Found in {count} attempts."),
+    ("tui.time-up", "Time is up! The secret number was {secret}."),
+    (
+        "tui.no-attempts",
+        "No attempts left! The secret number was {secret}.",
+    ),
+    ("tui.leave", "{end} Press any key to leave."),
+    ("tui.possible", "Still possible: {min} to {max}"),
+    ("tui.history", "History"),
+    ("tui.status", "Status"),
+    ("tui.attempts-of", "Attempts: {count} of {max}"),
+    ("tui.time-left", "Time left: {clock}"),
+    ("tui.time", "Time: {clock}"),
+    ("tui.attempts-left", "Attempts left: {count}"),
+    ("tui.your-guess", "Your guess"),
+    ("hint.not-chosen", "the secret number is not chosen yet"),
+    // the arguments
+    ("arg.missing-value", "{name} needs a value"),
+    ("arg.invalid-value", "invalid value '{value}' for {name}"),
+    ("arg.unknown", "unknown argument '{arg}'"),
+    ("arg.empty-range", "--min {min} is bigger than --max {max}"),
+    ("arg.no-guessers", "--players needs someone other than the --chooser"),
+    ("arg.conflict", "{first} cannot be used with {second}"),
+    (
+        "arg.needs-feature",
+        "{option} needs the {feature} feature, build with --features {feature}",
+    ),
+    (
+        "arg.too-many-lies",
+        "--lies {lies} needs more attempts than can be counted, use --max-attempts",
+    ),
+    (
+        "arg.too-many-codes",
+        "--solver plays with at most {max} codes, but there are {codes}",
+    ),
+    ("arg.code-rules", "no Mastermind code: {error}"),
+    // the high scores and the statistics
+    ("scores.empty", "No high scores yet."),
+    (
+        "scores.entry.one",
+        "{rank}. {player} {score}  {date}  {attempts} attempt   seed {seed}",
+    ),
+    (
+        "scores.entry.other",
+        "{rank}. {player} {score}  {date}  {attempts} attempts  seed {seed}",
+    ),
+    ("report.empty", "No games recorded yet."),
+    ("report.games", "Games: {games}, won {won} ({rate}%)"),
+    (
+        "report.attempts",
+        "Attempts to win: mean {mean}, median {median}, optimum {optimum} ({difference} from the optimum)",
+    ),
+    (
+        "report.wrong",
+        "Wrong guesses: {too_big} too big ({too_big_rate}%), {too_small} too small ({too_small_rate}%)",
+    ),
+    ("report.distance", "Distance from the secret number, of the range:"),
+    (
+        "report.skipped.one",
+        "Skipped {count} line of the log that is not a game.",
+    ),
+    (
+        "report.skipped.other",
+        "Skipped {count} lines of the log that are not games.",
+    ),
+    // the replays
+    ("replay.header", "Replay of a game between {min} and {max}."),
+    (
+        "replay.header-seed",
+        "Replay of a game between {min} and {max}, seed {seed}.",
+    ),
+    (
+        "replay.end.one",
+        "The game was {ending} after {count} attempt and {seconds} seconds.",
+    ),
+    (
+        "replay.end.other",
+        "The game was {ending} after {count} attempts and {seconds} seconds.",
+    ),
+    ("replay.now", "Now {difference}."),
+    ("replay.read-error", "Cannot read the replay: {error}"),
+    ("replay.not-replay", "not a replay ({error})"),
+    ("replay.no-version", "not a replay (no format version)"),
+    ("replay.version", "recorded with format version {version}, but only versions {oldest} to {newest} can be read"),
+    ("replay.same", "The current rules play the game the same way."),
+    (
+        "replay.different.one",
+        "The current rules play the game differently ({count} difference).",
+    ),
+    (
+        "replay.different.other",
+        "The current rules play the game differently ({count} differences).",
+    ),
+    ("ending.won", "won"),
+    ("ending.out-of-attempts", "lost, no attempts left"),
+    ("ending.out-of-time", "lost, time is up"),
+    ("ending.gave-up", "given up"),
+    ("ending.unfinished", "not finished"),
+    (
+        "difference.secret",
+        "the seed picks {current} as the secret number instead of {recorded}",
+    ),
+    ("difference.guess", "guess {index} was {recorded}, but now it is {current}"),
+    (
+        "difference.refused",
+        "guess {index} was {recorded}, but now it is refused: {error}",
+    ),
+    ("difference.ending", "the game was {recorded}, but now it is {current}"),
+    // the tournament of the bots
+    ("tournament.seed", "Seed {seed}: every bot played the same games."),
+    ("tournament.csv", "Games written to {path}"),
+    ("tournament.json", "Results written to {path}"),
+    // Mastermind and Wordle
+    (
+        "play.commands",
+        "Type /history for the guesses so far, /giveup or /quit.",
+    ),
+    ("play.commands-list", "The commands are /history, /giveup and /quit."),
+    ("mastermind.banner", "Guess the code of {rules}!"),
+    (
+        "mastermind.intro",
+        "Bulls are right symbols in the right place, cows right symbols in the wrong place.",
+    ),
+    ("mastermind.rules.one", "{count} symbol of {alphabet}"),
+    ("mastermind.rules.other", "{count} symbols of {alphabet}"),
+    ("mastermind.rules.different", "{rules}, all different"),
+    ("mastermind.bulls.one", "{count} bull"),
+    ("mastermind.bulls.other", "{count} bulls"),
+    ("mastermind.cows.one", "{count} cow"),
+    ("mastermind.cows.other", "{count} cows"),
+    ("mastermind.score", "{bulls}, {cows}"),
+    ("mastermind.found.one", "Found {code} in {count} attempt."),
+    ("mastermind.found.other", "Found {code} in {count} attempts."),
+    ("mastermind.secret", "The secret code was {code}."),
+    ("mastermind.invalid", "Please insert a code of {rules}: {error}"),
+    ("mastermind.no-length", "the code needs at least one symbol"),
+    ("mastermind.no-symbols", "the alphabet is empty"),
+    ("mastermind.duplicate", "'{symbol}' is twice in the alphabet"),
+    (
+        "mastermind.too-long",
+        "a code of {length} different symbols needs more than {symbols} symbols, allow repeats",
+    ),
+    ("mastermind.length.one", "the code has {count} symbol, not {found}"),
+    ("mastermind.length.other", "the code has {count} symbols, not {found}"),
+    ("mastermind.symbol", "'{symbol}' is not in the alphabet"),
+    (
+        "mastermind.repeat",
+        "'{symbol}' is repeated, the symbols are all different",
+    ),
+    ("wordle.banner.one", "Guess the word of {count} letter!"),
+    ("wordle.banner.other", "Guess the word of {count} letters!"),
+    (
+        "wordle.daily.one",
+        "The puzzle of {date}: guess the word of {count} letter!",
+    ),
+    (
+        "wordle.daily.other",
+        "The puzzle of {date}: guess the word of {count} letters!",
+    ),
+    (
+        "wordle.marks",
+        "Below each letter: = in the right place, + in another place, . not in the word.",
+    ),
+    (
+        "wordle.hard",
+        "Hard mode: the letters found must be used in every next guess.",
+    ),
+    ("wordle.list-error", "Cannot read the word list: {error}"),
+    ("wordle.invalid", "Please insert a word of the list: {error}"),
+    ("wordle.secret", "The secret word was {word}."),
+    ("wordle.length.one", "the word has {count} letter, not {found}"),
+    ("wordle.length.other", "the word has {count} letters, not {found}"),
+    ("wordle.unknown", "'{word}' is not in the word list"),
+    ("wordle.moved", "hard mode: letter {place} must be {letter}"),
+    ("wordle.missing", "hard mode: the guess must contain {letter}"),
+    // the client of guess-server
+    ("client.connect-error", "Cannot connect to {addr}: {error}"),
+    (
+        "client.joined.one",
+        "{name} joined the room {room} with {count} player.",
+    ),
+    (
+        "client.joined.other",
+        "{name} joined the room {room} with {count} players.",
+    ),
+    ("client.join-error", "Cannot join the room {room}: {error}"),
+    ("race.no-attempts", "You have no attempts left."),
+    ("race.hint", "{player} guessed {guess}: {outcome}."),
+    ("race.players.one", "{count} player in the room."),
+    ("race.players.other", "{count} players in the room."),
+    ("race.winner.one", "{player} wins with {count} attempt!"),
+    ("race.winner.other", "{player} wins with {count} attempts!"),
+    (
+        "race.standing.one",
+        "{rank}. {player} {attempts} attempt,  closest guess {distance} away",
+    ),
+    (
+        "race.standing.other",
+        "{rank}. {player} {attempts} attempts, closest guess {distance} away",
+    ),
+    ("race.over", "The race is over."),
+];
+
+const ITALIAN: &[(&str, &str)] = &[
+    // i binari
+    ("banner", "Indovina il numero tra {min} e {max}!"),
+    ("attempts-left.one", "Hai {count} tentativo."),
+    ("attempts-left.other", "Hai {count} tentativi."),
+    ("seconds-left.one", "Hai {count} secondo."),
+    ("seconds-left.other", "Hai {count} secondi."),
+    (
+        "error.high-scores",
+        "Impossibile leggere i punteggi migliori: {error}",
+    ),
+    ("error.stats", "Impossibile leggere le statistiche: {error}"),
+    ("error.resume", "Impossibile riprendere la partita: {error}"),
+    ("error.score", "Impossibile salvare il punteggio: {error}"),
+    ("error.replay", "Impossibile salvare il replay: {error}"),
+    ("error.record", "Impossibile registrare la partita: {error}"),
+    ("no-data-dir", "nessuna cartella per i dati"),
+    ("listening", "In ascolto su {addr}"),
+    ("listen-error", "Impossibile ascoltare su {addr}: {error}"),
+    // prima della partita
+    (
+        "intro.temperature",
+        "Ogni risposta dice se il tentativo è caldo, tiepido o freddo, se è più caldo o più freddo del precedente, e se è troppo piccolo o troppo grande.",
+    ),
+    (
+        "intro.temperature-only",
+        "Ogni risposta dice se il tentativo è caldo, tiepido o freddo, e se è più caldo o più freddo del precedente: nient'altro!",
+    ),
+    (
+        "intro.evil",
+        "Attenzione: questa partita non ha ancora un numero segreto, lo sceglie il più tardi possibile.",
+    ),
+    (
+        "intro.lies.one",
+        "Occhio: fino a {count} risposta può essere una bugia, solo \"Hai vinto!\" è sempre vero.",
+    ),
+    (
+        "intro.lies.other",
+        "Occhio: fino a {count} risposte possono essere bugie, solo \"Hai vinto!\" è sempre vero.",
+    ),
+    (
+        "intro.commands",
+        "Scrivi /help per i comandi, come /hint, /save o /giveup.",
+    ),
+    (
+        "secret.prompt",
+        "{chooser}, scegli il numero segreto tra {min} e {max}: ",
+    ),
+    ("secret.invalid", "Non è un numero tra {min} e {max}."),
+    // i tentativi
+    ("prompt", "Inserisci il tuo tentativo."),
+    ("guessed", "Hai provato: {guess}"),
+    ("input.empty", "Scrivi un numero."),
+    (
+        "input.negative",
+        "{text} è negativo, il numero segreto non è mai minore di 0.",
+    ),
+    (
+        "input.overflow",
+        "{text} è troppo grande, un tentativo è al massimo {max}.",
+    ),
+    ("input.not-a-number", "'{text}' non è un numero."),
+    (
+        "input.out-of-range",
+        "{guess} è fuori dall'intervallo, il numero segreto è tra {min} e {max}.",
+    ),
+    (
+        "input.duplicate",
+        "Hai già provato {guess} al tentativo {attempt}, la risposta è la stessa.",
+    ),
+    ("input.help", "{error} Scrivi /help per i comandi."),
+    ("input.unreadable", "Impossibile leggere l'input: {error}"),
+    ("input.script", "riga {line}: '{text}' non è un tentativo"),
+    (
+        "guess.out-of-range",
+        "{guess} è fuori dall'intervallo, il numero segreto è tra {min} e {max}",
+    ),
+    ("guess.time-up", "il tempo è scaduto"),
+    ("finished", "la partita è già finita"),
+    // le risposte
+    ("feedback.win", "Hai vinto!"),
+    ("feedback.too-small", "Troppo piccolo!"),
+    ("feedback.too-big", "Troppo grande!"),
+    ("temperature.hot", "Caldo!"),
+    ("temperature.warm", "Tiepido."),
+    ("temperature.cold", "Freddo."),
+    ("trend.warmer", "Più caldo del tentativo precedente."),
+    ("trend.colder", "Più freddo del tentativo precedente."),
+    ("trend.same", "Lontano quanto il tentativo precedente."),
+    ("outcome.too-small", "troppo piccolo"),
+    ("outcome.too-big", "troppo grande"),
+    ("outcome.win", "giusto"),
+    // la fine della partita
+    ("loss.attempts", "Tentativi finiti!"),
+    ("loss.time", "Tempo scaduto!"),
+    ("loss.gave-up", "Hai rinunciato!"),
+    ("loss.secret", "Il numero segreto era {secret}."),
+    ("lies.none", "La partita non ha detto bugie."),
+    ("lies.one", "La partita ha mentito una volta, su {lies}."),
+    ("lies.other", "La partita ha mentito {count} volte, su {lies}."),
+    ("score", "Il tuo punteggio: {score}"),
+    ("score.high", "Nuovo record per {difficulty}!"),
+    ("replay.saved", "Replay salvato in {path}"),
+    (
+        "save.resume",
+        "Partita salvata in {path}, riprendila con --resume {path}",
+    ),
+    ("save.error", "Impossibile salvare la partita: {error}"),
+    (
+        "save.finished",
+        "la partita è finita, non c'è niente da salvare",
+    ),
+    ("save.not-saved", "non è una partita salvata ({error})"),
+    ("save.no-version", "non è una partita salvata (manca la versione del formato)"),
+    ("save.version", "salvata con la versione {version} del formato, ma si leggono solo le versioni da {oldest} a {newest}"),
+    ("save.over", "la partita salvata è già finita"),
+    ("corrupt", "danneggiato ({reason})"),
+    ("corrupt.range", "il numero segreto {secret} non è tra {min} e {max}"),
+    ("corrupt.guess", "tentativo {index}: {guess} era {recorded}, ma è {current}"),
+    ("corrupt.refused", "tentativo {index}: {error}"),
+    ("corrupt.hint.one", "aiuto {hint} dopo il tentativo {after}, ma c'è {count} tentativo"),
+    ("corrupt.hint.other", "aiuto {hint} dopo il tentativo {after}, ma ci sono {count} tentativi"),
+    // i bot
+    ("bot.played", "Il bot {strategy} ha giocato:"),
+    ("bot.lie", " (una bugia)"),
+    ("bot.found.one", "Trovato {secret} in {count} tentativo."),
+    ("bot.found.other", "Trovato {secret} in {count} tentativi."),
+    (
+        "bot.not-found.one",
+        "Non trovato in {count} tentativo, il numero segreto era {secret}.",
+    ),
+    (
+        "bot.not-found.other",
+        "Non trovato in {count} tentativi, il numero segreto era {secret}.",
+    ),
+    // i giocatori di una partita a turni
+    ("seat.turn", "{name}, tocca a te."),
+    ("seat.leaves", "{name} lascia la partita."),
+    ("seat.guessed", "{name} ha provato: {guess}"),
+    ("seat.wins", "{name} vince!"),
+    ("seat.attempts", "Tentativi:"),
+    ("seat.winner.one", "Vince {name} con {count} tentativo!"),
+    ("seat.winner.other", "Vince {name} con {count} tentativi!"),
+    ("seat.nobody", "Nessuno ha vinto."),
+    // i comandi
+    (
+        "help.header",
+        "Scrivi un numero per provarlo, o uno di questi comandi (basta l'inizio del nome, come /his):",
+    ),
+    (
+        "command.hint",
+        "suggerisce un tentativo ed elenca gli aiuti, o ne compra uno per una parte del punteggio",
+    ),
+    (
+        "command.history",
+        "elenca i tentativi fatti finora e le loro risposte",
+    ),
+    (
+        "command.range",
+        "mostra l'intervallo dove si trova ancora il numero segreto",
+    ),
+    (
+        "command.stats",
+        "mostra i tentativi e il tempo usati e rimasti",
+    ),
+    ("command.giveup", "si arrende e rivela il numero segreto"),
+    (
+        "command.restart",
+        "inizia una nuova partita con un altro numero segreto",
+    ),
+    (
+        "command.save",
+        "salva la partita, per riprenderla più tardi con --resume",
+    ),
+    (
+        "command.load",
+        "riprende una partita salvata al posto di questa",
+    ),
+    ("command.help", "mostra questo aiuto"),
+    ("command.quit", "smette di giocare, la partita è salvata"),
+    (
+        "command.unknown",
+        "comando sconosciuto /{name}, scrivi /help per l'elenco",
+    ),
+    ("command.ambiguous", "/{name} può essere {names}"),
+    ("command.or", " o "),
+    ("command.argument", "/{name} non vuole argomenti"),
+    ("range", "Il numero segreto è tra {min} e {max}."),
+    (
+        "restart",
+        "Nuova partita: indovina il numero tra {min} e {max}!",
+    ),
+    (
+        "load.done",
+        "Partita caricata: il numero è tra {min} e {max}.",
+    ),
+    ("load.error", "Impossibile caricare la partita: {error}"),
+    ("history.empty", "Ancora nessun tentativo."),
+    ("history.hint", "aiuto"),
+    ("stats.attempts", "Tentativi: {count}"),
+    ("stats.attempts-of", " su {max}, ne restano {left}"),
+    ("stats.time.one", "Tempo: {count} secondo"),
+    ("stats.time.other", "Tempo: {count} secondi"),
+    ("stats.time-of", " su {limit}, ne restano {left}"),
+    (
+        "stats.hints",
+        "Aiuti: {count}, il punteggio è ridotto del {penalty}%",
+    ),
+    (
+        "stats.possible",
+        "Numeri ancora possibili: {count}, da {min} a {max}",
+    ),
+    (
+        "stats.binary-search.one",
+        "Una ricerca binaria ha bisogno al massimo di {count} tentativo in più.",
+    ),
+    (
+        "stats.binary-search.other",
+        "Una ricerca binaria ha bisogno al massimo di {count} tentativi in più.",
+    ),
+    // gli aiuti
+    (
+        "hint.try",
+        "Prova {guess}: qualunque sia la risposta, resta la metà dei numeri ancora possibili.",
+    ),
+    (
+        "hint.list",
+        "Aiuti da comprare con /hint <nome>, per una parte del punteggio:",
+    ),
+    (
+        "hint.bought",
+        "{answer} (-{penalty}% del punteggio, -{total}% in totale)",
+    ),
+    ("hint.parity", "se il numero segreto è pari o dispari"),
+    (
+        "hint.divisible",
+        "se il numero segreto è divisibile per 2, 3, 5, 7, 11 o 13",
+    ),
+    ("hint.digits", "la somma delle cifre del numero segreto"),
+    (
+        "hint.within",
+        "se il numero segreto dista al massimo 10 dall'ultimo tentativo",
+    ),
+    (
+        "hint.interval",
+        "quale quarto dei numeri ancora possibili contiene il numero segreto",
+    ),
+    ("hint.even", "Il numero segreto è pari."),
+    ("hint.odd", "Il numero segreto è dispari."),
+    (
+        "hint.divisible-by",
+        "Il numero segreto è divisibile per {prime}.",
+    ),
+    (
+        "hint.not-divisible-by",
+        "Il numero segreto non è divisibile per {prime}.",
+    ),
+    (
+        "hint.digit-sum",
+        "La somma delle cifre del numero segreto è {sum}.",
+    ),
+    (
+        "hint.close",
+        "Il numero segreto dista al massimo {distance} da {guess}.",
+    ),
+    (
+        "hint.not-close",
+        "Il numero segreto dista più di {distance} da {guess}.",
+    ),
+    ("hint.quarter", "Il numero segreto è tra {min} e {max}."),
+    ("hint.no-guess", "prima fai un tentativo"),
+    ("hint.few.one", "è possibile ancora solo {count} numero"),
+    ("hint.few.other", "sono possibili ancora solo {count} numeri"),
+    (
+        "hint.unknown",
+        "non c'è nessun aiuto chiamato {name}, scrivi /hint per l'elenco",
+    ),
+    (
+        "hint.prime",
+        "'{text}' non è un primo piccolo, scegline uno tra 2, 3, 5, 7, 11 e 13",
+    ),
+    ("hint.unavailable", "nessun aiuto adesso: {reason}"),
+    // lo schermo intero di --tui
+    ("tui.start", "Scrivi un numero e premi Invio, Esc per uscire."),
+    ("tui.not-a-digit", "'{key}' non è una cifra."),
+    ("tui.leading-zero", "Niente zeri all'inizio."),
+    (
+        "tui.too-big",
+        "{typed} è più grande di {max}, la fine dell'intervallo.",
+    ),
+    ("tui.empty", "Prima scrivi un numero."),
+    ("tui.won.one", "Hai vinto! Trovato in {count} tentativo."),
+    ("tui.won.other", "Hai vinto! Trovato in {count} tentativi."),
+    (
+        "tui.time-up",
+        "Tempo scaduto! Il numero segreto era {secret}.",
+    ),
+    (
+        "tui.no-attempts",
+        "Tentativi finiti! Il numero segreto era {secret}.",
+    ),
+    ("tui.leave", "{end} Premi un tasto per uscire."),
+    ("tui.possible", "Ancora possibili: da {min} a {max}"),
+    ("tui.history", "Tentativi"),
+    ("tui.status", "Stato"),
+    ("tui.attempts-of", "Tentativi: {count} su {max}"),
+    ("tui.time-left", "Tempo rimasto: {clock}"),
+    ("tui.time", "Tempo: {clock}"),
+    ("tui.attempts-left", "Tentativi rimasti: {count}"),
+    ("tui.your-guess", "Il tuo tentativo"),
+    ("hint.not-chosen", "il numero segreto non è ancora stato scelto"),
+    // gli argomenti
+    ("arg.missing-value", "{name} vuole un valore"),
+    ("arg.invalid-value", "valore '{value}' non valido per {name}"),
+    ("arg.unknown", "argomento sconosciuto '{arg}'"),
+    ("arg.empty-range", "--min {min} è più grande di --max {max}"),
+    ("arg.no-guessers", "--players vuole qualcuno oltre a --chooser"),
+    ("arg.conflict", "{first} non si può usare con {second}"),
+    (
+        "arg.needs-feature",
+        "{option} vuole la feature {feature}, compila con --features {feature}",
+    ),
+    (
+        "arg.too-many-lies",
+        "--lies {lies} vuole più tentativi di quanti se ne possano contare, usa --max-attempts",
+    ),
+    (
+        "arg.too-many-codes",
+        "--solver gioca con al massimo {max} codici, ma ce ne sono {codes}",
+    ),
+    ("arg.code-rules", "nessun codice di Mastermind: {error}"),
+    // i punteggi migliori e le statistiche
+    ("scores.empty", "Ancora nessun punteggio."),
+    (
+        "scores.entry.one",
+        "{rank}. {player} {score}  {date}  {attempts} tentativo  seme {seed}",
+    ),
+    (
+        "scores.entry.other",
+        "{rank}. {player} {score}  {date}  {attempts} tentativi  seme {seed}",
+    ),
+    ("report.empty", "Ancora nessuna partita registrata."),
+    ("report.games", "Partite: {games}, vinte {won} ({rate}%)"),
+    (
+        "report.attempts",
+        "Tentativi per vincere: media {mean}, mediana {median}, ottimo {optimum} ({difference} dall'ottimo)",
+    ),
+    (
+        "report.wrong",
+        "Tentativi sbagliati: {too_big} troppo grandi ({too_big_rate}%), {too_small} troppo piccoli ({too_small_rate}%)",
+    ),
+    (
+        "report.distance",
+        "Distanza dal numero segreto, rispetto all'intervallo:",
+    ),
+    (
+        "report.skipped.one",
+        "Saltata {count} riga del registro che non è una partita.",
+    ),
+    (
+        "report.skipped.other",
+        "Saltate {count} righe del registro che non sono partite.",
+    ),
+    // i replay
+    ("replay.header", "Replay di una partita tra {min} e {max}."),
+    (
+        "replay.header-seed",
+        "Replay di una partita tra {min} e {max}, seme {seed}.",
+    ),
+    (
+        "replay.end.one",
+        "Esito della partita: {ending}, dopo {count} tentativo e {seconds} secondi.",
+    ),
+    (
+        "replay.end.other",
+        "Esito della partita: {ending}, dopo {count} tentativi e {seconds} secondi.",
+    ),
+    ("replay.now", "Adesso: {difference}."),
+    ("replay.read-error", "Impossibile leggere il replay: {error}"),
+    ("replay.not-replay", "non è un replay ({error})"),
+    ("replay.no-version", "non è un replay (manca la versione del formato)"),
+    ("replay.version", "registrato con la versione {version} del formato, ma si leggono solo le versioni da {oldest} a {newest}"),
+    (
+        "replay.same",
+        "Le regole attuali giocano la partita allo stesso modo.",
+    ),
+    (
+        "replay.different.one",
+        "Le regole attuali giocano la partita diversamente ({count} differenza).",
+    ),
+    (
+        "replay.different.other",
+        "Le regole attuali giocano la partita diversamente ({count} differenze).",
+    ),
+    ("ending.won", "vinta"),
+    ("ending.out-of-attempts", "persa, tentativi finiti"),
+    ("ending.out-of-time", "persa, tempo scaduto"),
+    ("ending.gave-up", "abbandonata"),
+    ("ending.unfinished", "non finita"),
+    (
+        "difference.secret",
+        "il seme sceglie {current} come numero segreto invece di {recorded}",
+    ),
+    (
+        "difference.guess",
+        "il tentativo {index} era {recorded}, ma adesso è {current}",
+    ),
+    (
+        "difference.refused",
+        "il tentativo {index} era {recorded}, ma adesso è rifiutato: {error}",
+    ),
+    (
+        "difference.ending",
+        "la partita era {recorded}, ma adesso è {current}",
+    ),
+    // il torneo dei bot
+    (
+        "tournament.seed",
+        "Seme {seed}: tutti i bot hanno giocato le stesse partite.",
+    ),
+    ("tournament.csv", "Partite scritte in {path}"),
+    ("tournament.json", "Risultati scritti in {path}"),
+    // Mastermind e Wordle
+    (
+        "play.commands",
+        "Scrivi /history per i tentativi fatti, /giveup o /quit.",
+    ),
+    ("play.commands-list", "I comandi sono /history, /giveup e /quit."),
+    ("mastermind.banner", "Indovina il codice di {rules}!"),
+    (
+        "mastermind.intro",
+        "I tori sono simboli giusti al posto giusto, le mucche simboli giusti al posto sbagliato.",
+    ),
+    ("mastermind.rules.one", "{count} simbolo tra {alphabet}"),
+    ("mastermind.rules.other", "{count} simboli tra {alphabet}"),
+    ("mastermind.rules.different", "{rules}, tutti diversi"),
+    ("mastermind.bulls.one", "{count} toro"),
+    ("mastermind.bulls.other", "{count} tori"),
+    ("mastermind.cows.one", "{count} mucca"),
+    ("mastermind.cows.other", "{count} mucche"),
+    ("mastermind.score", "{bulls}, {cows}"),
+    ("mastermind.found.one", "Trovato {code} in {count} tentativo."),
+    ("mastermind.found.other", "Trovato {code} in {count} tentativi."),
+    ("mastermind.secret", "Il codice segreto era {code}."),
+    ("mastermind.invalid", "Inserisci un codice di {rules}: {error}"),
+    ("mastermind.no-length", "il codice vuole almeno un simbolo"),
+    ("mastermind.no-symbols", "l'alfabeto è vuoto"),
+    ("mastermind.duplicate", "'{symbol}' è due volte nell'alfabeto"),
+    (
+        "mastermind.too-long",
+        "un codice di {length} simboli diversi vuole più di {symbols} simboli, permetti le ripetizioni",
+    ),
+    (
+        "mastermind.length.one",
+        "il codice ha {count} simbolo, non {found}",
+    ),
+    (
+        "mastermind.length.other",
+        "il codice ha {count} simboli, non {found}",
+    ),
+    ("mastermind.symbol", "'{symbol}' non è nell'alfabeto"),
+    (
+        "mastermind.repeat",
+        "'{symbol}' è ripetuto, i simboli sono tutti diversi",
+    ),
+    ("wordle.banner.one", "Indovina la parola di {count} lettera!"),
+    ("wordle.banner.other", "Indovina la parola di {count} lettere!"),
+    (
+        "wordle.daily.one",
+        "Il puzzle del {date}: indovina la parola di {count} lettera!",
+    ),
+    (
+        "wordle.daily.other",
+        "Il puzzle del {date}: indovina la parola di {count} lettere!",
+    ),
+    (
+        "wordle.marks",
+        "Sotto ogni lettera: = al posto giusto, + in un altro posto, . non è nella parola.",
+    ),
+    (
+        "wordle.hard",
+        "Modalità difficile: le lettere trovate vanno usate in ogni tentativo successivo.",
+    ),
+    (
+        "wordle.list-error",
+        "Impossibile leggere l'elenco di parole: {error}",
+    ),
+    ("wordle.invalid", "Inserisci una parola dell'elenco: {error}"),
+    ("wordle.secret", "La parola segreta era {word}."),
+    ("wordle.length.one", "la parola ha {count} lettera, non {found}"),
+    ("wordle.length.other", "la parola ha {count} lettere, non {found}"),
+    ("wordle.unknown", "'{word}' non è nell'elenco di parole"),
+    (
+        "wordle.moved",
+        "modalità difficile: la lettera {place} deve essere {letter}",
+    ),
+    (
+        "wordle.missing",
+        "modalità difficile: il tentativo deve contenere {letter}",
+    ),
+    // il client di guess-server
+    ("client.connect-error", "Impossibile connettersi a {addr}: {error}"),
+    (
+        "client.joined.one",
+        "{name} è entrato nella stanza {room} con {count} giocatore.",
+    ),
+    (
+        "client.joined.other",
+        "{name} è entrato nella stanza {room} con {count} giocatori.",
+    ),
+    (
+        "client.join-error",
+        "Impossibile entrare nella stanza {room}: {error}",
+    ),
+    ("race.no-attempts", "Non hai più tentativi."),
+    ("race.hint", "{player} ha provato {guess}: {outcome}."),
+    ("race.players.one", "{count} giocatore nella stanza."),
+    ("race.players.other", "{count} giocatori nella stanza."),
+    ("race.winner.one", "{player} vince con {count} tentativo!"),
+    ("race.winner.other", "{player} vince con {count} tentativi!"),
+    (
+        "race.standing.one",
+        "{rank}. {player} {attempts} tentativo, tentativo più vicino a {distance}",
+    ),
+    (
+        "race.standing.other",
+        "{rank}. {player} {attempts} tentativi, tentativo più vicino a {distance}",
+    ),
+    ("race.over", "La gara è finita."),
+];
@@ -0,0 +1,413 @@
+//! The guesses typed at the terminal, and why a line is not one.
+//!
+//! By default a guess is written in digits, like `42`. With
+//! `--lenient-input` the [`GuessParser`] also takes what people write in
+//! code or in words: `1_000`, `0x1F`, `0o17`, `0b101` and `forty two`.
+
+use crate::i18n;
+use crate::Attempt;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::num::IntErrorKind;
+use std::ops::RangeInclusive;
+
+/// Why a line typed as a guess is not played.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessInputError {
+    Empty,
+    /// The text of the negative number.
+    Negative(String),
+    /// The text of a number bigger than `u32::MAX`.
+    Overflow(String),
+    NotANumber(String),
+    OutOfRange {
+        guess: u32,
+        range: RangeInclusive<u32>,
+    },
+    /// The guess was already played at `attempt`, counted from 1.
+    Duplicate {
+        guess: u32,
+        attempt: usize,
+    },
+}
+
+impl fmt::Display for GuessInputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            GuessInputError::Empty => i18n::text("input.empty").to_string(),
+            GuessInputError::Negative(text) => i18n::fill("input.negative", &[("text", text)]),
+            GuessInputError::Overflow(text) => {
+                i18n::fill("input.overflow", &[("text", text), ("max", &u32::MAX)])
+            }
+            GuessInputError::NotANumber(text) => {
+                i18n::fill("input.not-a-number", &[("text", text)])
+            }
+            GuessInputError::OutOfRange { guess, range } => i18n::fill(
+                "input.out-of-range",
+                &[
+                    ("guess", guess),
+                    ("min", range.start()),
+                    ("max", range.end()),
+                ],
+            ),
+            GuessInputError::Duplicate { guess, attempt } => {
+                i18n::fill("input.duplicate", &[("guess", guess), ("attempt", attempt)])
+            }
+        };
+        f.write_str(&message)
+    }
+}
+
+impl Error for GuessInputError {}
+
+/// Turns the lines typed at the terminal into guesses.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GuessParser {
+    lenient: bool,
+    range: Option<RangeInclusive<u32>>,
+    refuse_duplicates: bool,
+}
+
+impl GuessParser {
+    /// Digits only, any number of `u32`, the same guess any number of times.
+    pub fn new() -> GuessParser {
+        GuessParser::default()
+    }
+
+    /// Also takes underscores between digits, `0x`, `0o` and `0b` numbers and
+    /// numbers written in English words.
+    pub fn with_lenient(mut self, lenient: bool) -> GuessParser {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Refuses the guesses outside of `range`, before they cost an attempt.
+    pub fn with_range(mut self, range: RangeInclusive<u32>) -> GuessParser {
+        self.range = Some(range);
+        self
+    }
+
+    /// Refuses a guess already played: its answer cannot change, unless the
+    /// game lies.
+    pub fn with_refused_duplicates(mut self, refuse: bool) -> GuessParser {
+        self.refuse_duplicates = refuse;
+        self
+    }
+
+    /// The guess typed in `line`, after the attempts of `history`.
+    pub fn parse(&self, line: &str, history: &[Attempt]) -> Result<u32, GuessInputError> {
+        let text = line.trim();
+        let guess = if self.lenient {
+            parse_lenient(text)?
+        } else {
+            parse_radix(text, text, 10)?
+        };
+        if let Some(range) = self.range.as_ref().filter(|range| !range.contains(&guess)) {
+            return Err(GuessInputError::OutOfRange {
+                guess,
+                range: range.clone(),
+            });
+        }
+        if self.refuse_duplicates {
+            if let Some(i) = history.iter().position(|attempt| attempt.guess == guess) {
+                return Err(GuessInputError::Duplicate {
+                    guess,
+                    attempt: i + 1,
+                });
+            }
+        }
+        Ok(guess)
+    }
+}
+
+/// `digits` as a number in `radix`; `text` is what the player typed, for the
+/// errors.
+fn parse_radix(text: &str, digits: &str, radix: u32) -> Result<u32, GuessInputError> {
+    if text.is_empty() {
+        return Err(GuessInputError::Empty);
+    }
+    // "-0" is 0, like in the lenient mode: not worth a lesson on negative numbers
+    if let Some(rest) = digits.strip_prefix('-') {
+        if !rest.is_empty() && rest.chars().all(|c| c.is_digit(radix)) {
+            if rest.chars().all(|c| c == '0') {
+                return Ok(0);
+            }
+            return Err(GuessInputError::Negative(text.to_string()));
+        }
+    }
+    u32::from_str_radix(digits, radix).map_err(|err| match err.kind() {
+        IntErrorKind::PosOverflow => GuessInputError::Overflow(text.to_string()),
+        _ => GuessInputError::NotANumber(text.to_string()),
+    })
+}
+
+fn parse_lenient(text: &str) -> Result<u32, GuessInputError> {
+    let (negative, unsigned) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, text),
+    };
+    let lower = unsigned.to_lowercase();
+    let (radix, body) = match lower.get(..2) {
+        Some("0x") => (16, &lower[2..]),
+        Some("0o") => (8, &lower[2..]),
+        Some("0b") => (2, &lower[2..]),
+        _ => (10, &lower[..]),
+    };
+    // underscores only between digits, like in Rust: not "_1" nor "0x_"
+    if body.starts_with(|c: char| c.is_digit(radix)) {
+        let digits: String = body.chars().filter(|&c| c != '_').collect();
+        let number = parse_radix(text, &digits, radix)?;
+        if negative && number > 0 {
+            return Err(GuessInputError::Negative(text.to_string()));
+        }
+        return Ok(number);
+    }
+    if text.is_empty() {
+        return Err(GuessInputError::Empty);
+    }
+    let (negative, words) = match lower.strip_prefix("minus ") {
+        Some(rest) => (true, rest),
+        None => (negative, &lower[..]),
+    };
+    match parse_words(words) {
+        Some(Ok(number)) if negative && number > 0 => {
+            Err(GuessInputError::Negative(text.to_string()))
+        }
+        Some(Ok(number)) => Ok(number),
+        Some(Err(())) => Err(GuessInputError::Overflow(text.to_string())),
+        None => Err(GuessInputError::NotANumber(text.to_string())),
+    }
+}
+
+const UNITS: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 8] = [
+    "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(&str, u64); 3] = [
+    ("thousand", 1_000),
+    ("million", 1_000_000),
+    ("billion", 1_000_000_000),
+];
+
+/// A number in English words, like `forty-two` or `one hundred and five`:
+/// `None` when the words are not a number, `Some(Err(()))` when it does not
+/// fit in a `u32`.
+fn parse_words(words: &str) -> Option<Result<u32, ()>> {
+    let mut total: u64 = 0;
+    // the part below the next scale, like the 105 of "105 thousand"
+    let mut group: u64 = 0;
+    let mut any = false;
+    // the scale of the last "thousand" or "million": they go down
+    let mut last_scale = u64::MAX;
+    let tokens = words
+        .split(|c: char| c.is_whitespace() || c == '-' || c == ',')
+        .filter(|word| !word.is_empty() && *word != "and");
+    for word in tokens {
+        // the part below a hundred, that a unit or a ten can follow
+        let tail = group % 100;
+        if let Some(unit) = UNITS.iter().position(|&unit| unit == word) {
+            let unit = unit as u64;
+            // "forty two", but not "two two" nor "twelve three"
+            let after_ten = unit < 10 && tail >= 20 && tail.is_multiple_of(10);
+            if tail != 0 && !after_ten {
+                return None;
+            }
+            group += unit;
+        } else if let Some(ten) = TENS.iter().position(|&ten| ten == word) {
+            if tail != 0 {
+                return None;
+            }
+            group += (ten as u64 + 2) * 10;
+        } else if word == "hundred" {
+            // "nineteen hundred" is fine, "one hundred hundred" is not
+            if group >= 100 {
+                return None;
+            }
+            group = group.max(1) * 100;
+        } else if let Some(&(_, scale)) = SCALES.iter().find(|(name, _)| *name == word) {
+            if scale >= last_scale {
+                return None;
+            }
+            last_scale = scale;
+            total += group.max(1) * scale;
+            group = 0;
+        } else {
+            return None;
+        }
+        any = true;
+    }
+    if !any {
+        return None;
+    }
+    Some(u32::try_from(total + group).map_err(|_| ()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Outcome;
+
+    fn strict(line: &str) -> Result<u32, GuessInputError> {
+        GuessParser::new().parse(line, &[])
+    }
+
+    fn lenient(line: &str) -> Result<u32, GuessInputError> {
+        GuessParser::new().with_lenient(true).parse(line, &[])
+    }
+
+    #[test]
+    fn digits_are_a_guess_in_both_modes() {
+        for parse in &[strict, lenient] {
+            assert_eq!(parse("42"), Ok(42));
+            assert_eq!(parse("  42 \n"), Ok(42));
+            assert_eq!(parse("0"), Ok(0));
+            assert_eq!(parse("4294967295"), Ok(u32::MAX));
+            assert_eq!(parse(""), Err(GuessInputError::Empty));
+            assert_eq!(parse("   "), Err(GuessInputError::Empty));
+            assert_eq!(
+                parse("-5"),
+                Err(GuessInputError::Negative("-5".to_string()))
+            );
+            assert_eq!(
+                parse("4294967296"),
+                Err(GuessInputError::Overflow("4294967296".to_string()))
+            );
+            assert_eq!(
+                parse("4 2"),
+                Err(GuessInputError::NotANumber("4 2".to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn minus_zero_is_zero_in_both_modes() {
+        for parse in &[strict, lenient] {
+            assert_eq!(parse("-0"), Ok(0));
+            assert_eq!(parse("-000"), Ok(0));
+            assert_eq!(
+                parse("-01"),
+                Err(GuessInputError::Negative("-01".to_string()))
+            );
+        }
+        assert_eq!(lenient("minus zero"), Ok(0));
+        assert_eq!(lenient("-0x0"), Ok(0));
+    }
+
+    #[test]
+    fn only_the_lenient_mode_takes_code_and_words() {
+        let forms = [
+            ("1_000", 1_000),
+            ("1__", 1),
+            ("0x1F", 31),
+            ("0o17", 15),
+            ("0b101", 5),
+            ("forty two", 42),
+            ("forty-two", 42),
+            ("one hundred and five", 105),
+            ("nineteen hundred", 1_900),
+            ("two thousand, one hundred", 2_100),
+            ("four billion", 4_000_000_000),
+            ("zero", 0),
+        ];
+        for &(line, number) in forms.iter() {
+            assert_eq!(lenient(line), Ok(number), "{}", line);
+            assert_eq!(
+                strict(line),
+                Err(GuessInputError::NotANumber(line.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn the_lenient_mode_refuses_what_is_not_a_number() {
+        let not_numbers = [
+            "_1",
+            "0x",
+            "0x_1",
+            "0xg",
+            "two two",
+            "twelve three",
+            "forty forty",
+            "one hundred hundred",
+            "thousand million",
+            "and",
+            "fourty",
+        ];
+        for &line in not_numbers.iter() {
+            assert_eq!(
+                lenient(line),
+                Err(GuessInputError::NotANumber(line.to_string())),
+                "{}",
+                line
+            );
+        }
+        assert_eq!(
+            lenient("minus three"),
+            Err(GuessInputError::Negative("minus three".to_string()))
+        );
+        assert_eq!(
+            lenient("-0x10"),
+            Err(GuessInputError::Negative("-0x10".to_string()))
+        );
+        assert_eq!(
+            lenient("five billion"),
+            Err(GuessInputError::Overflow("five billion".to_string()))
+        );
+        assert_eq!(
+            lenient("0x1_0000_0000"),
+            Err(GuessInputError::Overflow("0x1_0000_0000".to_string()))
+        );
+    }
+
+    #[test]
+    fn the_range_and_the_duplicates_are_checked_after_the_number() {
+        let parser = GuessParser::new()
+            .with_range(1..=100)
+            .with_refused_duplicates(true);
+        let history = [Attempt {
+            guess: 50,
+            outcome: Outcome::TooSmall,
+        }];
+        assert_eq!(
+            parser.parse("101", &history),
+            Err(GuessInputError::OutOfRange {
+                guess: 101,
+                range: 1..=100
+            })
+        );
+        assert_eq!(
+            parser.parse("50", &history),
+            Err(GuessInputError::Duplicate {
+                guess: 50,
+                attempt: 1
+            })
+        );
+        assert_eq!(parser.parse("51", &history), Ok(51));
+        let again = parser.with_refused_duplicates(false);
+        assert_eq!(again.parse("50", &history), Ok(50));
+    }
+}
@@ -0,0 +1,381 @@
+//! Ulam's game: the secret number is guessed from answers that may lie.
+//!
+//! A game with a [`Liar`] can answer too small instead of too big, or the
+//! other way round, up to [`Liar::max_lies`] times. A correct guess is always
+//! told, so the game is still won by guessing the secret number.
+//!
+//! The numbers the secret can still be are the ones that make the answers
+//! lie at most `max_lies` times: [`candidates`] keeps them as intervals, and
+//! [`balanced_guess`] picks the question of Berlekamp's strategy, the one
+//! that leaves the same [`volume`] whatever the answer.
+
+use crate::{Attempt, Outcome};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// When a [`Liar`] lies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LieStrategy {
+    /// One answer out of four, at random.
+    #[default]
+    Random,
+    /// Whenever the lie helps the player less than the truth.
+    Adversarial,
+}
+
+impl LieStrategy {
+    pub const ALL: [LieStrategy; 2] = [LieStrategy::Random, LieStrategy::Adversarial];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LieStrategy::Random => "random",
+            LieStrategy::Adversarial => "adversarial",
+        }
+    }
+}
+
+impl fmt::Display for LieStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The name is not one of the [`LieStrategy`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLieStrategyError(String);
+
+impl fmt::Display for ParseLieStrategyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "unknown lie strategy '{}' (expected random or adversarial)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseLieStrategyError {}
+
+impl FromStr for LieStrategy {
+    type Err = ParseLieStrategyError;
+
+    fn from_str(s: &str) -> Result<LieStrategy, ParseLieStrategyError> {
+        LieStrategy::ALL
+            .iter()
+            .copied()
+            .find(|strategy| strategy.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseLieStrategyError(s.to_string()))
+    }
+}
+
+/// Decides which answers of a game are lies.
+///
+/// The decision only depends on the seed and on the guesses so far, so a
+/// saved or recorded game gets the same answers when it is played again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Liar {
+    pub max_lies: u32,
+    pub strategy: LieStrategy,
+    pub seed: u64,
+}
+
+impl Liar {
+    pub fn new(max_lies: u32, strategy: LieStrategy, seed: u64) -> Liar {
+        Liar {
+            max_lies,
+            strategy,
+            seed,
+        }
+    }
+
+    /// Whether to lie about `guess`, answered `truth` (too small or too big),
+    /// after the attempts of `history` and `lies_told` lies.
+    pub fn lies(
+        &self,
+        range: &RangeInclusive<u32>,
+        history: &[Attempt],
+        lies_told: u32,
+        guess: u32,
+        truth: Outcome,
+    ) -> bool {
+        if truth == Outcome::Win || lies_told >= self.max_lies {
+            return false;
+        }
+        match self.strategy {
+            LieStrategy::Random => {
+                let mut rng = StdRng::seed_from_u64(self.seed.wrapping_add(history.len() as u64));
+                rng.gen_range(0, 4) == 0
+            }
+            LieStrategy::Adversarial => {
+                let answered = |outcome| {
+                    let mut history = history.to_vec();
+                    history.push(Attempt { guess, outcome });
+                    let candidates = candidates(range, &history, self.max_lies);
+                    volume(
+                        &candidates,
+                        self.max_lies,
+                        questions(&candidates, self.max_lies),
+                    )
+                };
+                answered(opposite(truth)) > answered(truth)
+            }
+        }
+    }
+}
+
+/// The other answer between too small and too big.
+pub(crate) fn opposite(outcome: Outcome) -> Outcome {
+    match outcome {
+        Outcome::TooSmall => Outcome::TooBig,
+        Outcome::TooBig => Outcome::TooSmall,
+        Outcome::Win => Outcome::Win,
+    }
+}
+
+/// The numbers from `low` to `high`, which would make the answers lie
+/// `lies` times.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidates {
+    pub low: u32,
+    pub high: u32,
+    pub lies: u32,
+}
+
+impl Candidates {
+    /// How many numbers there are.
+    pub fn count(&self) -> u64 {
+        u64::from(self.high) - u64::from(self.low) + 1
+    }
+}
+
+/// The numbers of `range` that can still be the secret number after the
+/// answers of `history`, when up to `max_lies` of them are lies, in order.
+pub fn candidates(
+    range: &RangeInclusive<u32>,
+    history: &[Attempt],
+    max_lies: u32,
+) -> Vec<Candidates> {
+    if let Some(win) = history
+        .iter()
+        .find(|attempt| attempt.outcome == Outcome::Win)
+    {
+        return vec![Candidates {
+            low: win.guess,
+            high: win.guess,
+            lies: 0,
+        }];
+    }
+    let lies = |x: u32| {
+        history
+            .iter()
+            .filter(|attempt| match attempt.outcome {
+                Outcome::TooSmall => x < attempt.guess,
+                _ => x > attempt.guess,
+            })
+            .count() as u32
+    };
+
+    // the lies only change at the guesses, which cannot be the secret number
+    let mut guesses: Vec<u32> = history.iter().map(|attempt| attempt.guess).collect();
+    guesses.sort_unstable();
+    guesses.dedup();
+    let mut result = Vec::new();
+    let mut low = u64::from(*range.start());
+    let end = u64::from(*range.end());
+    for guess in guesses
+        .into_iter()
+        .map(u64::from)
+        .chain(std::iter::once(end + 1))
+    {
+        if guess > end {
+            if low <= end {
+                result.push((low as u32, end as u32));
+            }
+            break;
+        }
+        if low < guess {
+            result.push((low as u32, (guess - 1) as u32));
+        }
+        low = low.max(guess + 1);
+    }
+    result
+        .into_iter()
+        .map(|(low, high)| Candidates {
+            low,
+            high,
+            lies: lies(low),
+        })
+        .filter(|candidates| candidates.lies <= max_lies)
+        .collect()
+}
+
+/// The questions still needed, roughly: a binary search on the candidates,
+/// plus the lies.
+fn questions(candidates: &[Candidates], max_lies: u32) -> u32 {
+    let count: u64 = candidates.iter().map(Candidates::count).sum();
+    64 - count.leading_zeros() + max_lies
+}
+
+/// The ways `lies_left` lies can be spread over `questions` answers, at
+/// most: Berlekamp's weight of a number.
+fn weight(lies_left: i64, questions: u32) -> f64 {
+    let mut binomial = 1.0;
+    let mut sum = 0.0;
+    for j in 0..=lies_left {
+        sum += binomial;
+        binomial = binomial * f64::from(questions.saturating_sub(j as u32)) / (j + 1) as f64;
+    }
+    sum
+}
+
+/// How much is left to find out with `questions` answers: the sum of the
+/// weights of the candidates.
+pub fn volume(candidates: &[Candidates], max_lies: u32, questions: u32) -> f64 {
+    candidates
+        .iter()
+        .map(|c| c.count() as f64 * weight(i64::from(max_lies - c.lies), questions))
+        .sum()
+}
+
+/// The guess that leaves about the same [`volume`] whether it is too small
+/// or too big; `None` when there are no candidates.
+pub fn balanced_guess(candidates: &[Candidates], max_lies: u32) -> Option<u32> {
+    let first = candidates.first()?.low;
+    let last = candidates.last()?.high;
+    if first == last {
+        return Some(first);
+    }
+    let questions = questions(candidates, max_lies).saturating_sub(1);
+    // the volume left by each answer to `guess`: the numbers on the wrong
+    // side of the guess need one more lie
+    let volumes = |guess: u32| {
+        let (mut too_small, mut too_big) = (0.0, 0.0);
+        for c in candidates {
+            let full = weight(i64::from(max_lies - c.lies), questions);
+            let lied = weight(i64::from(max_lies - c.lies) - 1, questions);
+            let (start, end) = (u64::from(c.low), u64::from(c.high));
+            let below = u64::from(guess).clamp(start, end + 1) - start;
+            let above = c.count() - below - u64::from((c.low..=c.high).contains(&guess));
+            too_small += above as f64 * full + below as f64 * lied;
+            too_big += below as f64 * full + above as f64 * lied;
+        }
+        (too_small, too_big)
+    };
+    // "too small" leaves less and "too big" more as the guess grows
+    let (mut low, mut high) = (first, last);
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let (too_small, too_big) = volumes(middle);
+        if too_small > too_big {
+            low = middle + 1;
+        } else {
+            high = middle;
+        }
+    }
+    Some(low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attempt(guess: u32, outcome: Outcome) -> Attempt {
+        Attempt { guess, outcome }
+    }
+
+    fn intervals(candidates: &[Candidates]) -> Vec<(u32, u32, u32)> {
+        candidates.iter().map(|c| (c.low, c.high, c.lies)).collect()
+    }
+
+    #[test]
+    fn without_lies_the_candidates_are_the_interval_of_a_binary_search() {
+        let history = [attempt(50, Outcome::TooSmall), attempt(75, Outcome::TooBig)];
+        let candidates = candidates(&(1..=100), &history, 0);
+        assert_eq!(intervals(&candidates), vec![(51, 74, 0)]);
+    }
+
+    #[test]
+    fn the_candidates_count_the_lies_they_need() {
+        let history = [attempt(50, Outcome::TooSmall), attempt(75, Outcome::TooBig)];
+        assert_eq!(
+            intervals(&candidates(&(1..=100), &history, 1)),
+            vec![(1, 49, 1), (51, 74, 0), (76, 100, 1)]
+        );
+        // an answer and its opposite: one of them is a lie
+        let history = [attempt(50, Outcome::TooSmall), attempt(50, Outcome::TooBig)];
+        assert_eq!(
+            intervals(&candidates(&(1..=100), &history, 1)),
+            vec![(1, 49, 1), (51, 100, 1)]
+        );
+        assert!(candidates(&(1..=100), &history, 0).is_empty());
+    }
+
+    #[test]
+    fn a_win_leaves_only_the_guess() {
+        let history = [attempt(50, Outcome::TooBig), attempt(7, Outcome::Win)];
+        assert_eq!(
+            intervals(&candidates(&(1..=100), &history, 2)),
+            vec![(7, 7, 0)]
+        );
+    }
+
+    #[test]
+    fn the_candidates_reach_the_ends_of_the_u32_range() {
+        let history = [attempt(0, Outcome::TooSmall)];
+        let range = 0..=u32::MAX;
+        assert_eq!(
+            intervals(&candidates(&range, &history, 0)),
+            vec![(1, u32::MAX, 0)]
+        );
+        let history = [attempt(u32::MAX, Outcome::TooBig)];
+        assert_eq!(
+            intervals(&candidates(&range, &history, 0)),
+            vec![(0, u32::MAX - 1, 0)]
+        );
+    }
+
+    #[test]
+    fn without_lies_the_balanced_guess_is_the_middle() {
+        let all = candidates(&(1..=100), &[], 0);
+        let guess = balanced_guess(&all, 0).unwrap();
+        assert!((50..=51).contains(&guess), "{}", guess);
+        assert_eq!(balanced_guess(&[], 0), None);
+        let one = candidates(&(42..=42), &[], 0);
+        assert_eq!(balanced_guess(&one, 0), Some(42));
+    }
+
+    #[test]
+    fn the_balanced_guess_leaves_the_same_volume_whatever_the_answer() {
+        let range = 1..=1000;
+        let history = [
+            attempt(500, Outcome::TooSmall),
+            attempt(750, Outcome::TooBig),
+        ];
+        for max_lies in 1..=3 {
+            let before = candidates(&range, &history, max_lies);
+            let guess = balanced_guess(&before, max_lies).unwrap();
+            let questions = questions(&before, max_lies) - 1;
+            let left = |outcome| {
+                let mut history = history.to_vec();
+                history.push(attempt(guess, outcome));
+                volume(&candidates(&range, &history, max_lies), max_lies, questions)
+            };
+            let (too_small, too_big) = (left(Outcome::TooSmall), left(Outcome::TooBig));
+            let difference = (too_small - too_big).abs() / too_small.max(too_big);
+            assert!(
+                difference < 0.05,
+                "{} lies: {} leaves {} or {}",
+                max_lies,
+                guess,
+                too_small,
+                too_big
+            );
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! The guessing game of [chapter 2](https://doc.rust-lang.org/book/ch02-00-guessing-game-tutorial.html)
+//! as a library, so that it can be embedded in other tools and tested.
+//!
+//! The binaries in `src/bin` are only front-ends: they read the guesses from
+//! the terminal and print what the [`Game`] answers.
+
+pub mod bot;
+pub mod cli;
+pub mod client;
+mod date;
+mod difficulty;
+mod feedback;
+mod game;
+pub mod hint;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod i18n;
+pub mod input;
+pub mod liar;
+pub mod mastermind;
+pub mod multiplayer;
+pub mod player;
+pub mod prompt;
+pub mod protocol;
+pub mod race;
+pub mod replay;
+pub mod save;
+pub mod score;
+pub mod secret;
+pub mod server;
+pub mod stats;
+pub mod tournament;
+#[cfg(feature = "tui")]
+pub mod tui;
+pub mod wordle;
+
+pub use difficulty::{Difficulty, ParseDifficultyError};
+pub use feedback::{Feedback, FeedbackMode, ParseFeedbackModeError, Temperature, Trend};
+pub use game::{Attempt, Game, GuessError, HintUse, Loss, Outcome, Status};
+pub use input::GuessInputError;
+pub use player::Player;
@@ -0,0 +1,760 @@
+//! Mastermind, or Bulls and Cows: the secret is a code of symbols instead of
+//! a number.
+//!
+//! Each guess is answered with its bulls, the right symbols in the right
+//! place, and its cows, the right symbols in the wrong place. The game is
+//! played like the number game: a [`CodeGame`] keeps the state, a
+//! [`CodeBreaker`] makes the guesses and [`play`] runs the loop between them.
+
+use crate::i18n;
+use crate::player;
+use crate::Status;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+/// The symbols of the codes of Bulls and Cows.
+pub const DIGITS: &str = "0123456789";
+
+/// Guesses allowed when nothing else is chosen, like the rows of the board.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 10;
+
+/// The codes of a game: how long they are, the symbols they are made of,
+/// and whether a symbol can appear more than once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeRules {
+    length: usize,
+    alphabet: Vec<char>,
+    repeats: bool,
+}
+
+/// Rules that cannot make any code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeRulesError {
+    NoLength,
+    NoSymbols,
+    /// The same symbol twice in the alphabet.
+    Duplicate(char),
+    /// Codes without repeats longer than the alphabet.
+    TooLong {
+        length: usize,
+        symbols: usize,
+    },
+}
+
+impl fmt::Display for CodeRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodeRulesError::NoLength => f.write_str(i18n::text("mastermind.no-length")),
+            CodeRulesError::NoSymbols => f.write_str(i18n::text("mastermind.no-symbols")),
+            CodeRulesError::Duplicate(symbol) => {
+                f.write_str(&i18n::fill("mastermind.duplicate", &[("symbol", symbol)]))
+            }
+            CodeRulesError::TooLong { length, symbols } => f.write_str(&i18n::fill(
+                "mastermind.too-long",
+                &[("length", length), ("symbols", symbols)],
+            )),
+        }
+    }
+}
+
+impl std::error::Error for CodeRulesError {}
+
+impl CodeRules {
+    pub fn new(length: usize, alphabet: &str, repeats: bool) -> Result<CodeRules, CodeRulesError> {
+        let alphabet: Vec<char> = alphabet.chars().collect();
+        if length == 0 {
+            return Err(CodeRulesError::NoLength);
+        }
+        if alphabet.is_empty() {
+            return Err(CodeRulesError::NoSymbols);
+        }
+        if let Some(i) = (1..alphabet.len()).find(|&i| alphabet[..i].contains(&alphabet[i])) {
+            return Err(CodeRulesError::Duplicate(alphabet[i]));
+        }
+        if !repeats && length > alphabet.len() {
+            return Err(CodeRulesError::TooLong {
+                length,
+                symbols: alphabet.len(),
+            });
+        }
+        Ok(CodeRules {
+            length,
+            alphabet,
+            repeats,
+        })
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    pub fn repeats(&self) -> bool {
+        self.repeats
+    }
+
+    /// Reads a code typed by the player; spaces between the symbols are
+    /// allowed.
+    pub fn parse(&self, text: &str) -> Result<Code, CodeError> {
+        let symbols: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let code = Code(symbols);
+        self.check(&code)?;
+        Ok(code)
+    }
+
+    /// Whether `code` follows the rules.
+    pub fn check(&self, code: &Code) -> Result<(), CodeError> {
+        if code.0.len() != self.length {
+            return Err(CodeError::Length {
+                expected: self.length,
+                found: code.0.len(),
+            });
+        }
+        if let Some(&symbol) = code.0.iter().find(|c| !self.alphabet.contains(c)) {
+            return Err(CodeError::Symbol(symbol));
+        }
+        if !self.repeats {
+            if let Some(i) = (1..code.0.len()).find(|&i| code.0[..i].contains(&code.0[i])) {
+                return Err(CodeError::Repeat(code.0[i]));
+            }
+        }
+        Ok(())
+    }
+
+    /// How many codes there are, saturating at `u64::MAX`.
+    pub fn count(&self) -> u64 {
+        let symbols = self.alphabet.len() as u64;
+        (0..self.length as u64).fold(1u64, |count, i| {
+            let choices = if self.repeats { symbols } else { symbols - i };
+            count.saturating_mul(choices)
+        })
+    }
+
+    /// Every code, in the order of the alphabet.
+    pub fn all_codes(&self) -> Vec<Code> {
+        let mut codes = vec![Vec::new()];
+        for _ in 0..self.length {
+            let mut longer = Vec::new();
+            for code in &codes {
+                for &symbol in &self.alphabet {
+                    if self.repeats || !code.contains(&symbol) {
+                        let mut code = code.clone();
+                        code.push(symbol);
+                        longer.push(code);
+                    }
+                }
+            }
+            codes = longer;
+        }
+        codes.into_iter().map(Code).collect()
+    }
+
+    /// A code picked at random.
+    pub fn random_code<R: Rng + ?Sized>(&self, rng: &mut R) -> Code {
+        let mut left = self.alphabet.clone();
+        let symbols = (0..self.length)
+            .map(|_| {
+                let i = rng.gen_range(0, left.len());
+                if self.repeats {
+                    left[i]
+                } else {
+                    left.remove(i)
+                }
+            })
+            .collect();
+        Code(symbols)
+    }
+}
+
+impl Default for CodeRules {
+    /// Bulls and Cows: 4 different digits.
+    fn default() -> CodeRules {
+        CodeRules::new(4, DIGITS, false).expect("the default rules are valid")
+    }
+}
+
+impl fmt::Display for CodeRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let alphabet: String = self.alphabet.iter().collect();
+        let rules = i18n::plural(
+            "mastermind.rules",
+            self.length as u64,
+            &[("alphabet", &alphabet)],
+        );
+        if self.repeats {
+            f.write_str(&rules)
+        } else {
+            f.write_str(&i18n::fill(
+                "mastermind.rules.different",
+                &[("rules", &rules)],
+            ))
+        }
+    }
+}
+
+/// A code, made of the symbols of the alphabet of its [`CodeRules`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Code(pub Vec<char>);
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // padded, so that the history lines up
+        f.pad(&self.0.iter().collect::<String>())
+    }
+}
+
+/// A code that the game refused: it does not count as an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeError {
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    /// A symbol out of the alphabet.
+    Symbol(char),
+    /// A symbol twice, when the rules do not allow repeats.
+    Repeat(char),
+    Finished,
+}
+
+impl fmt::Display for CodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CodeError::Length { expected, found } => f.write_str(&i18n::plural(
+                "mastermind.length",
+                *expected as u64,
+                &[("found", found)],
+            )),
+            CodeError::Symbol(symbol) => {
+                f.write_str(&i18n::fill("mastermind.symbol", &[("symbol", symbol)]))
+            }
+            CodeError::Repeat(symbol) => {
+                f.write_str(&i18n::fill("mastermind.repeat", &[("symbol", symbol)]))
+            }
+            CodeError::Finished => f.write_str(i18n::text("finished")),
+        }
+    }
+}
+
+impl std::error::Error for CodeError {}
+
+/// The answer to a guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Score {
+    /// Right symbols in the right place.
+    pub bulls: usize,
+    /// Right symbols in the wrong place.
+    pub cows: usize,
+}
+
+impl Score {
+    /// The score of `guess` against `secret`, two codes of the same length.
+    pub fn new(secret: &Code, guess: &Code) -> Score {
+        let bulls = secret
+            .0
+            .iter()
+            .zip(&guess.0)
+            .filter(|(s, g)| s == g)
+            .count();
+        // each symbol matches as many times as it is in both codes, counted
+        // at its first place in the guess
+        let count = |code: &Code, symbol| code.0.iter().filter(|&&c| c == symbol).count();
+        let common: usize = guess
+            .0
+            .iter()
+            .enumerate()
+            .filter(|&(i, symbol)| !guess.0[..i].contains(symbol))
+            .map(|(_, &symbol)| count(secret, symbol).min(count(guess, symbol)))
+            .sum();
+        Score {
+            bulls,
+            cows: common - bulls,
+        }
+    }
+}
+
+impl fmt::Display for Score {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bulls = i18n::plural("mastermind.bulls", self.bulls as u64, &[]);
+        let cows = i18n::plural("mastermind.cows", self.cows as u64, &[]);
+        f.write_str(&i18n::fill(
+            "mastermind.score",
+            &[("bulls", &bulls), ("cows", &cows)],
+        ))
+    }
+}
+
+/// A guess already played, together with its score.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeAttempt {
+    pub guess: Code,
+    pub score: Score,
+}
+
+/// One round of Mastermind: like [`Game`](crate::Game), it only keeps the
+/// state.
+#[derive(Debug, Clone)]
+pub struct CodeGame {
+    rules: CodeRules,
+    secret: Code,
+    max_attempts: Option<u32>,
+    history: Vec<CodeAttempt>,
+    status: Status,
+}
+
+impl CodeGame {
+    /// Starts a new round, picking the secret code with `rng`.
+    pub fn new<R: Rng + ?Sized>(rules: CodeRules, rng: &mut R) -> CodeGame {
+        let secret = rules.random_code(rng);
+        CodeGame::with_secret(rules, secret)
+    }
+
+    /// Starts a new round with a known secret code.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secret` does not follow `rules`.
+    pub fn with_secret(rules: CodeRules, secret: Code) -> CodeGame {
+        if let Err(err) = rules.check(&secret) {
+            panic!("the secret code is not valid: {}", err);
+        }
+        CodeGame {
+            rules,
+            secret,
+            max_attempts: None,
+            history: Vec::new(),
+            status: Status::Playing,
+        }
+    }
+
+    /// Limits the number of attempts, `None` means no limit.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> CodeGame {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Scores `guess` and records the attempt; codes that do not follow the
+    /// rules are refused without using an attempt.
+    pub fn guess(&mut self, guess: Code) -> Result<Score, CodeError> {
+        if self.is_finished() {
+            return Err(CodeError::Finished);
+        }
+        self.rules.check(&guess)?;
+
+        let score = Score::new(&self.secret, &guess);
+        self.history.push(CodeAttempt { guess, score });
+        if score.bulls == self.rules.length {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost(crate::Loss::OutOfAttempts);
+        }
+        Ok(score)
+    }
+
+    /// Ends the game as lost, unless it is already finished.
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::Lost(crate::Loss::GaveUp);
+        }
+    }
+
+    pub fn rules(&self) -> &CodeRules {
+        &self.rules
+    }
+
+    /// The secret code: only meant to be shown once the game is over.
+    pub fn secret(&self) -> &Code {
+        &self.secret
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn history(&self) -> &[CodeAttempt] {
+        &self.history
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+/// Someone, or something, breaking codes: the [`Player`](crate::Player) of
+/// Mastermind.
+pub trait CodeBreaker {
+    /// The next guess, knowing the attempts already played. `None` means the
+    /// player stops playing, an error that the player could not be asked.
+    fn next_guess(&mut self, history: &[CodeAttempt]) -> io::Result<Option<Code>>;
+
+    /// Whether the player gave up instead of stopping, when
+    /// [`CodeBreaker::next_guess`] returned `None`.
+    fn gave_up(&self) -> bool {
+        false
+    }
+}
+
+impl<B: CodeBreaker + ?Sized> CodeBreaker for Box<B> {
+    fn next_guess(&mut self, history: &[CodeAttempt]) -> io::Result<Option<Code>> {
+        (**self).next_guess(history)
+    }
+
+    fn gave_up(&self) -> bool {
+        (**self).gave_up()
+    }
+}
+
+/// Runs `game` until it is finished or `breaker` stops, writing to `out`
+/// what happens at each guess.
+pub fn play<B, W>(game: &mut CodeGame, breaker: &mut B, out: &mut W) -> io::Result<()>
+where
+    B: CodeBreaker + ?Sized,
+    W: Write,
+{
+    while !game.is_finished() {
+        let guess = match breaker.next_guess(game.history())? {
+            Some(guess) => guess,
+            None if breaker.gave_up() => {
+                game.give_up();
+                break;
+            }
+            None => break,
+        };
+        writeln!(out, "{}", i18n::fill("guessed", &[("guess", &guess)]))?;
+        match game.guess(guess) {
+            Ok(_) if game.status() == Status::Won => {
+                writeln!(out, "{}", i18n::text("feedback.win"))?
+            }
+            Ok(score) => writeln!(out, "{}", score)?,
+            Err(err) => writeln!(out, "{}", err)?,
+        }
+    }
+
+    match game.status() {
+        Status::Lost(crate::Loss::GaveUp) => writeln!(out, "{}", i18n::text("loss.gave-up"))?,
+        Status::Lost(_) => writeln!(out, "{}", i18n::text("loss.attempts"))?,
+        _ => return Ok(()),
+    }
+    writeln!(
+        out,
+        "{}",
+        i18n::fill("mastermind.secret", &[("code", game.secret())])
+    )
+}
+
+/// Writes the guesses so far and their scores.
+pub fn write_history<W: Write>(history: &[CodeAttempt], out: &mut W) -> io::Result<()> {
+    if history.is_empty() {
+        return writeln!(out, "{}", i18n::text("history.empty"));
+    }
+    for (i, attempt) in history.iter().enumerate() {
+        writeln!(out, "{:>4}. {:>8}  {}", i + 1, attempt.guess, attempt.score)?;
+    }
+    Ok(())
+}
+
+/// The player at the terminal: one code per line, or `/history`,
+/// `/giveup` and `/quit`.
+pub struct StdinCodeBreaker {
+    rules: CodeRules,
+    // None reads the standard input
+    input: Option<Box<dyn BufRead>>,
+    gave_up: bool,
+}
+
+impl StdinCodeBreaker {
+    pub fn new(rules: CodeRules) -> StdinCodeBreaker {
+        StdinCodeBreaker {
+            rules,
+            input: None,
+            gave_up: false,
+        }
+    }
+
+    pub fn from_reader<R: BufRead + 'static>(rules: CodeRules, input: R) -> StdinCodeBreaker {
+        StdinCodeBreaker {
+            rules,
+            input: Some(Box::new(input)),
+            gave_up: false,
+        }
+    }
+}
+
+impl CodeBreaker for StdinCodeBreaker {
+    fn next_guess(&mut self, history: &[CodeAttempt]) -> io::Result<Option<Code>> {
+        loop {
+            println!("{}", i18n::text("prompt"));
+
+            let mut guess = String::new();
+            // 0 bytes read is the end of the input: the player stops
+            let read = match &mut self.input {
+                Some(input) => player::read_text_line(input, &mut guess),
+                None => player::read_text_line(&mut io::stdin().lock(), &mut guess),
+            };
+            match read {
+                Ok(0) => return Ok(None),
+                Ok(_) => {}
+                Err(err) => {
+                    println!("{}", i18n::fill("input.unreadable", &[("error", &err)]));
+                    return Ok(None);
+                }
+            }
+
+            // the same trim and parse as the number game, with the rules of the code
+            match guess.trim() {
+                "/history" => {
+                    write_history(history, &mut io::stdout())?;
+                    continue;
+                }
+                "/giveup" => {
+                    self.gave_up = true;
+                    return Ok(None);
+                }
+                "/quit" => return Ok(None),
+                command if command.starts_with('/') => {
+                    println!("{}", i18n::text("play.commands-list"));
+                    continue;
+                }
+                _ => {}
+            }
+            match self.rules.parse(&guess) {
+                Ok(code) => return Ok(Some(code)),
+                Err(err) => {
+                    println!(
+                        "{}",
+                        i18n::fill(
+                            "mastermind.invalid",
+                            &[("rules", &self.rules), ("error", &err)]
+                        )
+                    );
+                    continue;
+                }
+            }
+        }
+    }
+
+    fn gave_up(&self) -> bool {
+        self.gave_up
+    }
+}
+
+/// The score evaluations a [`KnuthBot`] allows itself for a guess: beyond
+/// them it only looks at the codes still possible, then it takes the first.
+const MINIMAX_BUDGET: usize = 4_000_000;
+
+/// The most codes a [`KnuthBot`] plays with: it keeps all of them in memory.
+pub const MAX_SOLVER_CODES: u64 = 200_000;
+
+/// Knuth's solver: among the codes, it guesses the one whose worst answer
+/// leaves the fewest codes still possible, preferring a code that can be
+/// the secret.
+///
+/// It holds every code of its rules, so it is meant for the rules with at
+/// most [`MAX_SOLVER_CODES`] codes.
+#[derive(Debug, Clone)]
+pub struct KnuthBot {
+    rules: CodeRules,
+    codes: Vec<Code>,
+    candidates: Vec<Code>,
+    /// The attempts of the history already used to narrow the candidates.
+    seen: usize,
+}
+
+impl KnuthBot {
+    pub fn new(rules: CodeRules) -> KnuthBot {
+        let codes = rules.all_codes();
+        KnuthBot {
+            rules,
+            candidates: codes.clone(),
+            codes,
+            seen: 0,
+        }
+    }
+
+    /// The codes that agree with every score so far.
+    pub fn candidates(&self) -> &[Code] {
+        &self.candidates
+    }
+
+    /// Knuth's opening: two symbols in pairs (`1122` on the classic board),
+    /// or the first symbols when they cannot repeat.
+    fn opening(&self) -> Code {
+        let symbols = &self.rules.alphabet;
+        Code(
+            (0..self.rules.length)
+                .map(|i| {
+                    if self.rules.repeats {
+                        symbols[(i / 2).min(1).min(symbols.len() - 1)]
+                    } else {
+                        symbols[i]
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// The size of the largest group of candidates that get the same score
+    /// against `guess`.
+    fn worst_case(&self, guess: &Code) -> usize {
+        let length = self.rules.length;
+        let mut groups = vec![0; (length + 1) * (length + 1)];
+        for candidate in &self.candidates {
+            let score = Score::new(candidate, guess);
+            groups[score.bulls * (length + 1) + score.cows] += 1;
+        }
+        groups.into_iter().max().unwrap_or(0)
+    }
+
+    /// The next guess of Knuth's strategy, `None` when no code agrees with
+    /// the scores.
+    fn guess(&mut self, history: &[CodeAttempt]) -> Option<Code> {
+        for attempt in &history[self.seen.min(history.len())..] {
+            self.candidates
+                .retain(|code| Score::new(code, &attempt.guess) == attempt.score);
+        }
+        self.seen = history.len();
+
+        match self.candidates.len() {
+            0 => return None,
+            1 | 2 => return self.candidates.first().cloned(),
+            _ => {}
+        }
+        if history.is_empty() {
+            return Some(self.opening());
+        }
+        let pool = if self.codes.len() * self.candidates.len() <= MINIMAX_BUDGET {
+            &self.codes
+        } else if self.candidates.len() * self.candidates.len() <= MINIMAX_BUDGET {
+            &self.candidates
+        } else {
+            return self.candidates.first().cloned();
+        };
+        // the smallest worst case, then a candidate, then the first in order
+        let candidates: HashSet<&Code> = self.candidates.iter().collect();
+        pool.iter()
+            .min_by_key(|&code| (self.worst_case(code), !candidates.contains(code)))
+            .cloned()
+    }
+}
+
+impl CodeBreaker for KnuthBot {
+    fn next_guess(&mut self, history: &[CodeAttempt]) -> io::Result<Option<Code>> {
+        Ok(self.guess(history))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn code(text: &str) -> Code {
+        Code(text.chars().collect())
+    }
+
+    fn score(secret: &str, guess: &str) -> (usize, usize) {
+        let score = Score::new(&code(secret), &code(guess));
+        (score.bulls, score.cows)
+    }
+
+    #[test]
+    fn repeated_symbols_match_as_many_times_as_in_both_codes() {
+        assert_eq!(score("1122", "2211"), (0, 4));
+        assert_eq!(score("1123", "1111"), (2, 0));
+        assert_eq!(score("1234", "1111"), (1, 0));
+        assert_eq!(score("1112", "2111"), (2, 2));
+        assert_eq!(score("1222", "2111"), (0, 2));
+        assert_eq!(score("6543", "1122"), (0, 0));
+        assert_eq!(score("3456", "3456"), (4, 0));
+    }
+
+    #[test]
+    fn the_rules_need_codes_that_can_be_made() {
+        assert_eq!(
+            CodeRules::new(0, DIGITS, false),
+            Err(CodeRulesError::NoLength)
+        );
+        assert_eq!(CodeRules::new(4, "", true), Err(CodeRulesError::NoSymbols));
+        assert_eq!(
+            CodeRules::new(4, "12341", true),
+            Err(CodeRulesError::Duplicate('1'))
+        );
+        assert_eq!(
+            CodeRules::new(5, "1234", false),
+            Err(CodeRulesError::TooLong {
+                length: 5,
+                symbols: 4
+            })
+        );
+        assert!(CodeRules::new(5, "1234", true).is_ok());
+
+        assert_eq!(CodeRules::default().count(), 5040);
+        let classic = CodeRules::new(4, "123456", true).unwrap();
+        assert_eq!(classic.count(), 1296);
+        assert_eq!(classic.all_codes().len(), 1296);
+        assert_eq!(CodeRules::default().parse("1 2 3 4"), Ok(code("1234")));
+        assert_eq!(
+            CodeRules::default().parse("1231"),
+            Err(CodeError::Repeat('1'))
+        );
+        assert_eq!(classic.parse("1237"), Err(CodeError::Symbol('7')));
+    }
+
+    /// The most guesses `bot` needs for any of `secrets`, after `history`:
+    /// every game of the bot, walked as the tree of its answers.
+    fn most_guesses(bot: &KnuthBot, history: &mut Vec<CodeAttempt>, secrets: &[Code]) -> usize {
+        let mut bot = bot.clone();
+        let guess = bot.next_guess(history).unwrap().expect("a code is left");
+        let mut groups: BTreeMap<(usize, usize), Vec<Code>> = BTreeMap::new();
+        for secret in secrets {
+            let score = Score::new(secret, &guess);
+            groups
+                .entry((score.bulls, score.cows))
+                .or_default()
+                .push(secret.clone());
+        }
+        let mut most = 0;
+        for ((bulls, cows), secrets) in groups {
+            let guesses = if bulls == guess.0.len() {
+                history.len() + 1
+            } else {
+                history.push(CodeAttempt {
+                    guess: guess.clone(),
+                    score: Score { bulls, cows },
+                });
+                let guesses = most_guesses(&bot, history, &secrets);
+                history.pop();
+                guesses
+            };
+            most = most.max(guesses);
+        }
+        most
+    }
+
+    #[test]
+    fn knuth_needs_at_most_five_guesses_on_the_classic_board() {
+        let rules = CodeRules::new(4, "123456", true).unwrap();
+        let secrets = rules.all_codes();
+        let bot = KnuthBot::new(rules);
+        let most = most_guesses(&bot, &mut Vec::new(), &secrets);
+        assert!(most <= 5, "{} guesses", most);
+    }
+}
@@ -0,0 +1,231 @@
+use crate::i18n;
+use crate::player::{self, Player};
+use crate::{Game, GuessError, Loss, Outcome};
+use std::io::{self, Write};
+
+/// One of the players taking turns at the same game.
+pub struct Seat {
+    pub name: String,
+    pub player: Box<dyn Player>,
+    /// Guesses made so far.
+    pub attempts: u32,
+    /// The player stopped playing.
+    pub left: bool,
+}
+
+impl Seat {
+    pub fn new<P: Player + 'static>(name: &str, player: P) -> Seat {
+        Seat {
+            name: name.to_string(),
+            player: Box::new(player),
+            attempts: 0,
+            left: false,
+        }
+    }
+}
+
+/// Lets `seats` take turns guessing the secret number of `game`, each with
+/// up to `max_attempts` guesses, and returns the index of the winner.
+///
+/// A refused guess does not end the turn. When every player has used all the
+/// attempts the game is lost.
+pub fn play<W: Write>(
+    game: &mut Game,
+    seats: &mut [Seat],
+    max_attempts: Option<u32>,
+    out: &mut W,
+) -> io::Result<Option<usize>> {
+    let can_play = |seat: &Seat| !seat.left && max_attempts.is_none_or(|max| seat.attempts < max);
+
+    let mut winner = None;
+    let mut turn = 0;
+    while !game.is_finished() {
+        if !seats.iter().any(can_play) {
+            if seats.iter().any(|seat| !seat.left) {
+                game.lose(Loss::OutOfAttempts);
+            }
+            break;
+        }
+
+        let index = turn % seats.len();
+        turn += 1;
+        let seat = &mut seats[index];
+        if !can_play(seat) {
+            continue;
+        }
+        writeln!(out, "{}", i18n::fill("seat.turn", &[("name", &seat.name)]))?;
+
+        loop {
+            seat.player.set_deadline(player::deadline(game));
+            let guess = match seat.player.next_guess(game.history()) {
+                Some(guess) => guess,
+                // the time ran out while the player was thinking
+                None if player::check_time(game) => break,
+                None => {
+                    writeln!(
+                        out,
+                        "{}",
+                        i18n::fill("seat.leaves", &[("name", &seat.name)])
+                    )?;
+                    seat.left = true;
+                    break;
+                }
+            };
+            let guessed = i18n::fill("seat.guessed", &[("name", &seat.name), ("guess", &guess)]);
+            writeln!(out, "{}", guessed)?;
+
+            match game.guess(guess) {
+                Ok(outcome) => {
+                    seat.attempts += 1;
+                    let feedback = game.feedback().expect("the game has just been answered");
+                    seat.player.observe(feedback);
+                    if outcome == Outcome::Win {
+                        writeln!(out, "{}", i18n::fill("seat.wins", &[("name", &seat.name)]))?;
+                        winner = Some(index);
+                    } else {
+                        writeln!(out, "{}", feedback)?;
+                    }
+                    break;
+                }
+                Err(GuessError::TimeUp) => break,
+                // the same player tries again
+                Err(err) => writeln!(out, "{}", err)?,
+            }
+        }
+    }
+
+    player::write_loss(game, out)?;
+    write_tally(seats, winner, out)?;
+    Ok(winner)
+}
+
+/// Prints the attempts of every player and who won.
+pub fn write_tally<W: Write>(seats: &[Seat], winner: Option<usize>, out: &mut W) -> io::Result<()> {
+    writeln!(out, "{}", i18n::text("seat.attempts"))?;
+    for seat in seats {
+        writeln!(out, "  {:<16} {:>4}", seat.name, seat.attempts)?;
+    }
+    match winner {
+        Some(index) => {
+            let seat = &seats[index];
+            let winner = i18n::plural(
+                "seat.winner",
+                u64::from(seat.attempts),
+                &[("name", &seat.name)],
+            );
+            writeln!(out, "{}", winner)
+        }
+        None => writeln!(out, "{}", i18n::text("seat.nobody")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::ScriptedPlayer;
+    use crate::Status;
+    use std::time::Duration;
+
+    fn seats(alice: &[u32], bob: &[u32]) -> Vec<Seat> {
+        vec![
+            Seat::new("alice", ScriptedPlayer::new(alice.to_vec())),
+            Seat::new("bob", ScriptedPlayer::new(bob.to_vec())),
+        ]
+    }
+
+    fn lines_with(out: &[u8], text: &str) -> Vec<String> {
+        String::from_utf8(out.to_vec())
+            .unwrap()
+            .lines()
+            .filter(|line| line.contains(text))
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn the_players_take_turns_until_one_wins() {
+        let mut game = Game::with_secret(1..=100, 42);
+        let mut seats = seats(&[50, 40, 42], &[30, 45, 42]);
+        let mut out = Vec::new();
+        let winner = play(&mut game, &mut seats, None, &mut out).unwrap();
+
+        assert_eq!(winner, Some(0));
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(
+            lines_with(&out, "guessed"),
+            [
+                "alice guessed: 50",
+                "bob guessed: 30",
+                "alice guessed: 40",
+                "bob guessed: 45",
+                "alice guessed: 42",
+            ]
+        );
+        assert_eq!((seats[0].attempts, seats[1].attempts), (3, 2));
+        assert_eq!(
+            lines_with(&out, "winner"),
+            ["The winner is alice with 3 attempts!"]
+        );
+    }
+
+    #[test]
+    fn a_refused_guess_keeps_the_turn() {
+        let mut game = Game::with_secret(1..=100, 42);
+        let mut seats = seats(&[500, 42], &[41]);
+        let mut out = Vec::new();
+        assert_eq!(
+            play(&mut game, &mut seats, None, &mut out).unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            lines_with(&out, "guessed"),
+            ["alice guessed: 500", "alice guessed: 42"]
+        );
+        assert_eq!(seats[0].attempts, 1);
+    }
+
+    #[test]
+    fn a_player_out_of_guesses_leaves() {
+        let mut game = Game::with_secret(1..=100, 42);
+        let mut seats = seats(&[], &[10, 42]);
+        let mut out = Vec::new();
+        assert_eq!(
+            play(&mut game, &mut seats, None, &mut out).unwrap(),
+            Some(1)
+        );
+        assert!(seats[0].left);
+        assert_eq!(lines_with(&out, "leaves"), ["alice leaves the game."]);
+    }
+
+    #[test]
+    fn the_game_is_lost_when_every_attempt_is_used() {
+        let mut game = Game::with_secret(1..=100, 42);
+        let mut seats = seats(&[1, 2, 3], &[4, 5, 6]);
+        let mut out = Vec::new();
+        assert_eq!(
+            play(&mut game, &mut seats, Some(2), &mut out).unwrap(),
+            None
+        );
+
+        assert_eq!(game.status(), Status::Lost(Loss::OutOfAttempts));
+        assert_eq!(lines_with(&out, "guessed").len(), 4);
+        assert_eq!((seats[0].attempts, seats[1].attempts), (2, 2));
+        assert_eq!(lines_with(&out, "No attempts left!").len(), 1);
+        assert_eq!(lines_with(&out, "Nobody won.").len(), 1);
+    }
+
+    #[test]
+    fn the_game_is_lost_when_the_time_is_up() {
+        let mut game = Game::with_secret(1..=100, 42)
+            .with_time_limit(Some(Duration::from_secs(60)))
+            .with_elapsed(Duration::from_secs(120));
+        let mut seats = seats(&[42], &[42]);
+        let mut out = Vec::new();
+        assert_eq!(play(&mut game, &mut seats, None, &mut out).unwrap(), None);
+
+        assert_eq!(game.status(), Status::Lost(Loss::OutOfTime));
+        assert_eq!(seats[0].attempts, 0);
+        assert!(lines_with(&out, "bob, your turn").is_empty());
+        assert_eq!(lines_with(&out, "Time is up!").len(), 1);
+    }
+}
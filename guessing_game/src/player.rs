@@ -0,0 +1,515 @@
+use crate::i18n;
+use crate::input::{GuessInputError, GuessParser};
+use crate::prompt::{self, Command};
+use crate::save::AutoSave;
+use crate::{Attempt, Feedback, Game, GuessError, Loss, Status};
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// What a player does at its turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Turn {
+    Guess(u32),
+    /// A command typed at the prompt, see [`prompt`].
+    Command(Command),
+    /// The player stops playing.
+    Stop,
+}
+
+/// Someone, or something, playing the guessing game.
+pub trait Player {
+    /// The next guess, knowing the attempts already played. `None` means the
+    /// player stops playing.
+    fn next_guess(&mut self, history: &[Attempt]) -> Option<u32>;
+
+    /// The next turn: a guess, unless the player also knows other commands.
+    fn next_turn(&mut self, history: &[Attempt]) -> Turn {
+        match self.next_guess(history) {
+            Some(guess) => Turn::Guess(guess),
+            None => Turn::Stop,
+        }
+    }
+
+    /// The answer to the last guess returned by [`Player::next_guess`], as
+    /// the game shows it: without the direction when its
+    /// [`FeedbackMode`](crate::FeedbackMode) hides it.
+    fn observe(&mut self, _feedback: Feedback) {}
+
+    /// When the time of the game runs out, told before each turn: a player
+    /// waiting for someone to type stops waiting then. `None` means no limit.
+    fn set_deadline(&mut self, _deadline: Option<Instant>) {}
+}
+
+impl<P: Player + ?Sized> Player for Box<P> {
+    fn next_guess(&mut self, history: &[Attempt]) -> Option<u32> {
+        (**self).next_guess(history)
+    }
+
+    fn next_turn(&mut self, history: &[Attempt]) -> Turn {
+        (**self).next_turn(history)
+    }
+
+    fn observe(&mut self, feedback: Feedback) {
+        (**self).observe(feedback)
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        (**self).set_deadline(deadline)
+    }
+}
+
+/// When the time of `game` runs out, for [`Player::set_deadline`].
+pub(crate) fn deadline(game: &Game) -> Option<Instant> {
+    // a limit too far away to be an instant is no limit
+    game.time_left()
+        .and_then(|left| Instant::now().checked_add(left))
+}
+
+/// Ends `game` when its time is up, after its player stopped: the player may
+/// have stopped waiting at the deadline. Returns whether it did.
+pub(crate) fn check_time(game: &mut Game) -> bool {
+    let time_up = !game.is_finished() && game.time_left() == Some(Duration::from_secs(0));
+    if time_up {
+        game.lose(Loss::OutOfTime);
+    }
+    time_up
+}
+
+/// Runs `game` until it is finished or `player` stops, writing to `out` what
+/// happens at each guess.
+pub fn play<P, W>(game: &mut Game, player: &mut P, out: &mut W) -> io::Result<()>
+where
+    P: Player + ?Sized,
+    W: Write,
+{
+    run(game, player, None, out)
+}
+
+/// Like [`play`], keeping `autosave` up to date with every guess.
+pub fn play_autosaving<P, W>(
+    game: &mut Game,
+    player: &mut P,
+    autosave: &AutoSave,
+    out: &mut W,
+) -> io::Result<()>
+where
+    P: Player + ?Sized,
+    W: Write,
+{
+    run(game, player, Some(autosave), out)
+}
+
+fn run<P, W>(
+    game: &mut Game,
+    player: &mut P,
+    autosave: Option<&AutoSave>,
+    out: &mut W,
+) -> io::Result<()>
+where
+    P: Player + ?Sized,
+    W: Write,
+{
+    while !game.is_finished() {
+        if let Some(autosave) = autosave {
+            autosave.update(game);
+        }
+        player.set_deadline(deadline(game));
+        let guess = match player.next_turn(game.history()) {
+            Turn::Guess(guess) => guess,
+            Turn::Command(command) => {
+                prompt::run(&command, game, out)?;
+                continue;
+            }
+            Turn::Stop => {
+                check_time(game);
+                break;
+            }
+        };
+        writeln!(out, "{}", i18n::fill("guessed", &[("guess", &guess)]))?;
+
+        match game.guess(guess) {
+            Ok(_) => {
+                // the feedback mode of the game decides what the player is told
+                if let Some(feedback) = game.feedback() {
+                    player.observe(feedback);
+                    writeln!(out, "{}", feedback)?;
+                }
+            }
+            // reported below, together with the secret number
+            Err(GuessError::TimeUp) => {}
+            Err(err) => writeln!(out, "{}", err)?,
+        }
+    }
+
+    write_loss(game, out)?;
+    write_lies(game, out)
+}
+
+/// When `game` is lost, tells why and reveals the secret number.
+pub(crate) fn write_loss<W: Write>(game: &Game, out: &mut W) -> io::Result<()> {
+    if let Status::Lost(loss) = game.status() {
+        let key = match loss {
+            Loss::OutOfAttempts => "loss.attempts",
+            Loss::OutOfTime => "loss.time",
+            Loss::GaveUp => "loss.gave-up",
+        };
+        writeln!(out, "{}", i18n::text(key))?;
+        let secret = game.secret_number();
+        writeln!(out, "{}", i18n::fill("loss.secret", &[("secret", &secret)]))?;
+    }
+    Ok(())
+}
+
+/// When `game` can lie and is finished, tells which answers were lies.
+pub(crate) fn write_lies<W: Write>(game: &Game, out: &mut W) -> io::Result<()> {
+    if game.liar().is_none() || !game.is_finished() {
+        return Ok(());
+    }
+    let lies: Vec<String> = game
+        .lies()
+        .iter()
+        .map(|&i| format!("{} ({})", game.history()[i].guess, i + 1))
+        .collect();
+    if lies.is_empty() {
+        return writeln!(out, "{}", i18n::text("lies.none"));
+    }
+    let about = lies.join(", ");
+    writeln!(
+        out,
+        "{}",
+        i18n::plural("lies", lies.len() as u64, &[("lies", &about)])
+    )
+}
+
+/// Where a [`StdinPlayer`] reads its lines.
+enum Input {
+    /// The standard input: it is locked only while reading a line, so that
+    /// several players can share the terminal.
+    Stdin,
+    Reader(Box<dyn BufRead + Send>),
+    /// The lines of a reader moved to a thread, see [`lines_of`].
+    Lines(Receiver<io::Result<String>>),
+}
+
+/// The lines of the standard input, read by a thread from the first time a
+/// player waits for one with a deadline: from then on every player reads
+/// them here, the thread holds the standard input.
+static STDIN_LINES: OnceLock<Mutex<Receiver<io::Result<String>>>> = OnceLock::new();
+
+/// Reads `input` in a new thread, so that waiting for a line can stop at a
+/// deadline; the thread sends an empty line at the end of the input.
+fn lines_of<R: BufRead + Send + 'static>(mut input: R) -> Receiver<io::Result<String>> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let mut line = String::new();
+        let read = read_text_line(&mut input, &mut line).map(|_| line);
+        let end = !matches!(&read, Ok(line) if !line.is_empty());
+        // nobody is waiting for the line anymore when the send fails
+        if sender.send(read).is_err() || end {
+            return;
+        }
+    });
+    receiver
+}
+
+/// Like `read_line`, but a line that is not UTF-8 is read too, with its
+/// invalid bytes replaced: it is only a guess that is not a number.
+pub(crate) fn read_text_line<R: BufRead + ?Sized>(
+    input: &mut R,
+    buf: &mut String,
+) -> io::Result<usize> {
+    let mut bytes = Vec::new();
+    let read = input.read_until(b'\n', &mut bytes)?;
+    buf.push_str(&String::from_utf8_lossy(&bytes));
+    Ok(read)
+}
+
+/// The next line of `lines`, an error of kind `TimedOut` when `deadline`
+/// comes first.
+fn receive(
+    lines: &Receiver<io::Result<String>>,
+    deadline: Option<Instant>,
+    buf: &mut String,
+) -> io::Result<usize> {
+    let line = match deadline {
+        Some(deadline) => {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match lines.recv_timeout(timeout) {
+                Ok(line) => line,
+                Err(RecvTimeoutError::Timeout) => return Err(io::ErrorKind::TimedOut.into()),
+                Err(RecvTimeoutError::Disconnected) => Ok(String::new()),
+            }
+        }
+        None => lines.recv().unwrap_or_else(|_| Ok(String::new())),
+    }?;
+    buf.push_str(&line);
+    Ok(line.len())
+}
+
+/// The player at the terminal, or anything else giving one guess per line.
+pub struct StdinPlayer {
+    input: Input,
+    parser: GuessParser,
+    deadline: Option<Instant>,
+}
+
+impl StdinPlayer {
+    pub fn new() -> StdinPlayer {
+        StdinPlayer {
+            input: Input::Stdin,
+            parser: GuessParser::new(),
+            deadline: None,
+        }
+    }
+
+    pub fn from_reader<R: BufRead + Send + 'static>(input: R) -> StdinPlayer {
+        StdinPlayer {
+            input: Input::Reader(Box::new(input)),
+            parser: GuessParser::new(),
+            deadline: None,
+        }
+    }
+
+    /// Reads the guesses with `parser`, digits only by default.
+    pub fn with_parser(mut self, parser: GuessParser) -> StdinPlayer {
+        self.parser = parser;
+        self
+    }
+
+    /// Reads a line into `buf`, or fails with `TimedOut` at the deadline.
+    fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+        // a reader blocks until the line comes: it moves to a thread to wait
+        // with a deadline
+        if self.deadline.is_some() {
+            match std::mem::replace(&mut self.input, Input::Stdin) {
+                Input::Stdin => {
+                    STDIN_LINES
+                        .get_or_init(|| Mutex::new(lines_of(io::BufReader::new(io::stdin()))));
+                }
+                Input::Reader(reader) => self.input = Input::Lines(lines_of(reader)),
+                lines => self.input = lines,
+            }
+        }
+        match &mut self.input {
+            Input::Stdin => match STDIN_LINES.get() {
+                Some(lines) => {
+                    let lines = lines.lock().unwrap_or_else(|err| err.into_inner());
+                    receive(&lines, self.deadline, buf)
+                }
+                None => read_text_line(&mut io::stdin().lock(), buf),
+            },
+            Input::Reader(input) => read_text_line(input, buf),
+            Input::Lines(lines) => receive(lines, self.deadline, buf),
+        }
+    }
+}
+
+impl Default for StdinPlayer {
+    fn default() -> StdinPlayer {
+        StdinPlayer::new()
+    }
+}
+
+impl StdinPlayer {
+    /// Reads lines until one is a guess, or a command when `commands` is set:
+    /// a line starting with `/`, or `save [file]` and `load [file]` as before
+    /// the commands had a slash.
+    fn read_turn(&mut self, commands: bool, history: &[Attempt]) -> Turn {
+        loop {
+            println!("{}", i18n::text("prompt"));
+
+            let mut guess = String::new(); // mutable string
+                                           // :: syntax in the ::new line indicates that new is an associated function of the String type --> static method
+
+            // the stdin function --> return an instance of std:io::Stdin --> type to handel the standard input from terminal
+            // (self.read_line calls it, unless the player reads a file or a thread reads for it, see read_line above)
+
+            // read_line(&mut guess) --> calls read_line method on the standard input handle to get input from the user.
+
+            // The job of read_line is to take whatever the user types into standard input and place that into a string,
+            // so it takes that string as an argument.
+            // The string argument needs to be mutable so the method can change the string’s content by adding the user input.
+
+            // The & indicates that this argument is a reference, which gives you a way to let multiple
+            // parts of your code access one piece of data without needing to copy that data into memory multiple times.
+            // Here an Err does not crash the program: input that cannot be read is not a guess,
+            // so the player stops, like at the end of the input, and the game can be resumed.
+            let read = match self.read_line(&mut guess) {
+                Ok(read) => read,
+                // When the time of the game runs out while waiting, the read fails with TimedOut
+                // and the player stops: the game then ends as lost.
+                Err(err) if err.kind() == io::ErrorKind::TimedOut => return Turn::Stop,
+                Err(err) => {
+                    println!("{}", i18n::fill("input.unreadable", &[("error", &err)]));
+                    return Turn::Stop;
+                }
+            };
+
+            // read_line puts what the user types into the string we’re passing it,
+            // but it also returns a value—in this case, an io::Result
+            // Rust has a number of types named Result in its standard library:
+            // a generic Result as well as specific versions for submodules, such as io::Result.
+
+            // The Result types are enumerations, often referred to as enums.
+            // An enumeration is a type that can have a fixed set of values, and those values are called
+            // the enum’s variants
+
+            // For Result, the variants are Ok or Err. The Ok variant indicates the operation was successful,
+            // and inside Ok is the successfully generated value.
+
+            // The Err variant means the operation failed, and Err contains information about how or why the
+            // operation failed.
+
+            // The purpose of these Result types is to encode error-handling information
+
+            //  An instance of io::Result has an expect method that you can call.
+            // If this instance of io::Result is an Err value, expect will cause the program to crash and
+            // display the message that you passed as an argument to expect
+
+            // f the read_line method returns an Err, it would likely be the result of an error coming from
+            // the underlying operating system. If this instance of io::Result is an Ok value,
+            // expect will take the return value that Ok is holding and return just that value to you so
+            // you can use it. In this case, that value is the number of bytes in what the user
+            // entered into standard input.
+            // 0 bytes is the end of the input (Ctrl-D): the player stops instead of looping forever
+            if read == 0 {
+                return Turn::Stop;
+            }
+
+            if commands {
+                let line = guess.trim();
+                let word = line.split_whitespace().next().unwrap_or("");
+                if line.starts_with('/') || word == "save" || word == "load" {
+                    match prompt::parse(line) {
+                        Ok(turn) => return turn,
+                        Err(err) => {
+                            println!("{}", err);
+                            continue;
+                        }
+                    }
+                }
+            }
+
+            // let guess: u32 = guess.trim().parse().expect("Please type a number!");
+            // We create a variable named guess. But wait, doesn’t the program already have a variable named guess?
+            // It does, but Rust allows us to shadow the previous value of guess with a new one.
+
+            // The trim method on a String instance will eliminate any whitespace at the beginning and end.
+            // The parse method on strings parses a string into some kind of number. Because this method can parse
+            // a variety of number types, we need to tell Rust the exact number type we want by using let guess: u32.
+            // The colon (:) after guess tells Rust we’ll annotate the variable’s type.
+            // Rust has a few built-in number types; the u32 seen here is an unsigned, 32-bit integer.
+            // It’s a good default choice for a small positive number.
+            // Here the parser of src/input.rs trims and parses, and also says why a line is not a guess
+            // (empty, negative, too big, out of range or already guessed).
+
+            // if i want handling invalid input
+            // Switching from an expect call to a match expression is how you generally
+            // move from crashing on an error to handling the error.
+            // Remember that parse returns a Result type and Result is an enum that has the variants Ok or Err.
+            // We’re using a match expression here, as we did with the Ordering result of the cmp method
+
+            // If parse is able to successfully turn the string into a number, it will return an Ok value
+            // that contains the resulting number. That Ok value will match the first arm’s pattern,
+            // and the match expression will just return the num value that parse produced and put inside
+            // the Ok value. That number will end up right where we want it in the new guess variable we’re creating.
+            // If parse is not able to turn the string into a number, it will return an Err value that
+            // contains more information about the error. The Err value does not match the Ok(num) pattern
+            // in the first match arm, but it does match the Err(err) pattern in the last arm.
+            // The tutorial matches Err(_) instead: the underscore, _, is a catchall value, that matches all
+            // Err values, no matter what information they have inside them, and so ignores all errors
+            // that parse might encounter. Here we print the error, then continue, which tells the program
+            // to go to the next iteration of the loop and ask for another guess.
+            let guess: u32 = match self.parser.parse(&guess, history) {
+                Ok(num) => num,
+                Err(err @ GuessInputError::NotANumber(_)) if commands => {
+                    println!("{}", i18n::fill("input.help", &[("error", &err)]));
+                    continue;
+                }
+                Err(err) => {
+                    println!("{}", err);
+                    continue;
+                }
+            };
+            return Turn::Guess(guess);
+        }
+    }
+}
+
+impl Player for StdinPlayer {
+    fn next_guess(&mut self, history: &[Attempt]) -> Option<u32> {
+        match self.read_turn(false, history) {
+            Turn::Guess(guess) => Some(guess),
+            _ => None,
+        }
+    }
+
+    fn next_turn(&mut self, history: &[Attempt]) -> Turn {
+        self.read_turn(true, history)
+    }
+
+    fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+}
+
+/// A player repeating guesses written in advance.
+#[derive(Debug, Clone)]
+pub struct ScriptedPlayer {
+    guesses: std::vec::IntoIter<u32>,
+}
+
+impl ScriptedPlayer {
+    pub fn new<I: IntoIterator<Item = u32>>(guesses: I) -> ScriptedPlayer {
+        ScriptedPlayer {
+            guesses: guesses.into_iter().collect::<Vec<_>>().into_iter(),
+        }
+    }
+
+    /// Reads one guess per line from the file at `path`; empty lines and lines
+    /// starting with `#` are skipped.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<ScriptedPlayer> {
+        let content = fs::read_to_string(path)?;
+        let mut guesses = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let guess = line.parse().map_err(|_| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    i18n::fill("input.script", &[("line", &(i + 1)), ("text", &line)]),
+                )
+            })?;
+            guesses.push(guess);
+        }
+        Ok(ScriptedPlayer::new(guesses))
+    }
+}
+
+impl Player for ScriptedPlayer {
+    fn next_guess(&mut self, _history: &[Attempt]) -> Option<u32> {
+        self.guesses.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_huge_time_limit_has_no_deadline() {
+        let game =
+            Game::with_secret(1..=100, 42).with_time_limit(Some(Duration::from_secs(u64::MAX)));
+        assert_eq!(deadline(&game), None);
+        let game = Game::with_secret(1..=100, 42).with_time_limit(Some(Duration::from_secs(60)));
+        assert!(deadline(&game).is_some_and(|deadline| deadline > Instant::now()));
+        assert_eq!(deadline(&Game::with_secret(1..=100, 42)), None);
+    }
+}
@@ -0,0 +1,430 @@
+//! The commands the player at the terminal can type instead of a guess.
+//!
+//! A command starts with `/`, and any beginning of its name that is not
+//! shared with another command works too: `/his` is `/history`.
+
+use crate::hint;
+use crate::i18n;
+use crate::player::Turn;
+use crate::save;
+use crate::{Game, Loss};
+use std::fmt;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// A command typed at the prompt, besides `/quit` which ends the game like
+/// the end of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Suggests a guess, or buys the hint named, like `parity`.
+    Hint(Option<String>),
+    History,
+    Range,
+    Stats,
+    GiveUp,
+    Restart,
+    /// Saves the game, to [`save::default_path`] without a file.
+    Save(Option<PathBuf>),
+    /// Replaces the game with a saved one.
+    Load(Option<PathBuf>),
+    Help,
+}
+
+/// The name, the arguments and the help of a command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandInfo {
+    pub name: &'static str,
+    pub arguments: &'static str,
+    /// The key of the help in the [catalog](crate::i18n).
+    pub help: &'static str,
+}
+
+/// Every command, in the order of `/help`.
+pub const COMMANDS: &[CommandInfo] = &[
+    CommandInfo {
+        name: "hint",
+        arguments: "[name]",
+        help: "command.hint",
+    },
+    CommandInfo {
+        name: "history",
+        arguments: "",
+        help: "command.history",
+    },
+    CommandInfo {
+        name: "range",
+        arguments: "",
+        help: "command.range",
+    },
+    CommandInfo {
+        name: "stats",
+        arguments: "",
+        help: "command.stats",
+    },
+    CommandInfo {
+        name: "giveup",
+        arguments: "",
+        help: "command.giveup",
+    },
+    CommandInfo {
+        name: "restart",
+        arguments: "",
+        help: "command.restart",
+    },
+    CommandInfo {
+        name: "save",
+        arguments: "[file]",
+        help: "command.save",
+    },
+    CommandInfo {
+        name: "load",
+        arguments: "[file]",
+        help: "command.load",
+    },
+    CommandInfo {
+        name: "help",
+        arguments: "",
+        help: "command.help",
+    },
+    CommandInfo {
+        name: "quit",
+        arguments: "",
+        help: "command.quit",
+    },
+];
+
+/// A line starting with `/` that is not a command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCommandError {
+    Unknown(String),
+    /// The beginning of several commands.
+    Ambiguous(String, Vec<&'static str>),
+    /// An argument given to a command that takes none.
+    Argument(&'static str),
+}
+
+impl fmt::Display for ParseCommandError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseCommandError::Unknown(name) => {
+                f.write_str(&i18n::fill("command.unknown", &[("name", name)]))
+            }
+            ParseCommandError::Ambiguous(name, names) => {
+                let names: Vec<String> = names.iter().map(|name| format!("/{}", name)).collect();
+                let names = names.join(i18n::text("command.or"));
+                f.write_str(&i18n::fill(
+                    "command.ambiguous",
+                    &[("name", name), ("names", &names)],
+                ))
+            }
+            ParseCommandError::Argument(name) => {
+                f.write_str(&i18n::fill("command.argument", &[("name", name)]))
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseCommandError {}
+
+/// The turn asked by `line`, which starts with `/`.
+pub fn parse(line: &str) -> Result<Turn, ParseCommandError> {
+    let line = line.trim().trim_start_matches('/');
+    let (name, argument) = match line.find(char::is_whitespace) {
+        Some(i) => (&line[..i], Some(line[i..].trim())),
+        None => (line, None),
+    };
+
+    let matching: Vec<&'static str> = match COMMANDS.iter().find(|info| info.name == name) {
+        Some(info) => vec![info.name],
+        None => COMMANDS
+            .iter()
+            .map(|info| info.name)
+            .filter(|command| !name.is_empty() && command.starts_with(name))
+            .collect(),
+    };
+    let command = match matching.as_slice() {
+        [command] => *command,
+        [] => return Err(ParseCommandError::Unknown(name.to_string())),
+        _ => return Err(ParseCommandError::Ambiguous(name.to_string(), matching)),
+    };
+
+    let file = argument.map(PathBuf::from);
+    let command = match command {
+        "save" => return Ok(Turn::Command(Command::Save(file))),
+        "load" => return Ok(Turn::Command(Command::Load(file))),
+        "hint" => return Ok(Turn::Command(Command::Hint(argument.map(String::from)))),
+        _ if argument.is_some() => return Err(ParseCommandError::Argument(command)),
+        "quit" => return Ok(Turn::Stop),
+        "history" => Command::History,
+        "range" => Command::Range,
+        "stats" => Command::Stats,
+        "giveup" => Command::GiveUp,
+        "restart" => Command::Restart,
+        "help" => Command::Help,
+        _ => unreachable!("every command of COMMANDS is handled"),
+    };
+    Ok(Turn::Command(command))
+}
+
+/// Does what `command` asks to `game`, writing the answer to `out`.
+pub fn run<W: Write>(command: &Command, game: &mut Game, out: &mut W) -> io::Result<()> {
+    match command {
+        Command::Hint(None) => {
+            // without the direction the answers do not halve the numbers
+            if game.feedback_mode().shows_direction() {
+                let range = game.known_range();
+                let middle = (u64::from(*range.start()) + u64::from(*range.end())) / 2;
+                writeln!(out, "{}", i18n::fill("hint.try", &[("guess", &middle)]))?;
+            }
+            writeln!(out, "{}", i18n::text("hint.list"))?;
+            for hint in hint::catalogue() {
+                writeln!(
+                    out,
+                    "  {:<18} -{:>2}%  {}",
+                    hint.usage(),
+                    hint.penalty(),
+                    hint.description()
+                )?;
+            }
+            Ok(())
+        }
+        Command::Hint(Some(name)) => match hint::parse(name) {
+            Ok(hint) => match game.use_hint(hint.as_ref()) {
+                Ok(used) => {
+                    let (answer, penalty) = (used.answer.clone(), used.penalty);
+                    let bought = i18n::fill(
+                        "hint.bought",
+                        &[
+                            ("answer", &answer),
+                            ("penalty", &penalty),
+                            ("total", &game.penalty()),
+                        ],
+                    );
+                    writeln!(out, "{}", bought)
+                }
+                Err(err) => writeln!(out, "{}", err),
+            },
+            Err(err) => writeln!(out, "{}", err),
+        },
+        Command::History => write_history(game, out),
+        Command::Range => {
+            let range = game.known_range();
+            let (min, max) = (range.start(), range.end());
+            writeln!(
+                out,
+                "{}",
+                i18n::fill("range", &[("min", min), ("max", max)])
+            )
+        }
+        Command::Stats => write_stats(game, out),
+        Command::GiveUp => {
+            game.lose(Loss::GaveUp);
+            Ok(())
+        }
+        Command::Restart => {
+            *game = game.restart();
+            let range = game.range();
+            let (min, max) = (range.start(), range.end());
+            writeln!(
+                out,
+                "{}",
+                i18n::fill("restart", &[("min", min), ("max", max)])
+            )
+        }
+        Command::Save(path) => match path.clone().or_else(save::default_path) {
+            Some(path) => match save::save(game, &path) {
+                Ok(()) => writeln!(out, "{}", save::resume_hint(&path)),
+                Err(err) => writeln!(out, "{}", i18n::fill("save.error", &[("error", &err)])),
+            },
+            None => writeln!(
+                out,
+                "{}",
+                i18n::fill("save.error", &[("error", &i18n::text("no-data-dir"))])
+            ),
+        },
+        Command::Load(path) => match path.clone().or_else(save::default_path).map(save::load) {
+            Some(Ok(loaded)) => {
+                *game = loaded;
+                let range = game.range();
+                let (min, max) = (range.start(), range.end());
+                writeln!(
+                    out,
+                    "{}",
+                    i18n::fill("load.done", &[("min", min), ("max", max)])
+                )?;
+                write_history(game, out)
+            }
+            Some(Err(err)) => writeln!(out, "{}", i18n::fill("load.error", &[("error", &err)])),
+            None => writeln!(
+                out,
+                "{}",
+                i18n::fill("load.error", &[("error", &i18n::text("no-data-dir"))])
+            ),
+        },
+        Command::Help => write_help(out),
+    }
+}
+
+fn write_history<W: Write>(game: &Game, out: &mut W) -> io::Result<()> {
+    if game.history().is_empty() && game.hints_used().is_empty() {
+        return writeln!(out, "{}", i18n::text("history.empty"));
+    }
+    let write_hints = |after: usize, out: &mut W| -> io::Result<()> {
+        for used in game
+            .hints_used()
+            .iter()
+            .filter(|used| used.after as usize == after)
+        {
+            let label = i18n::text("history.hint");
+            writeln!(out, "{:>16} {}: {}", label, used.hint, used.answer)?;
+        }
+        Ok(())
+    };
+    for (i, attempt) in game.history().iter().enumerate() {
+        write_hints(i, out)?;
+        writeln!(
+            out,
+            "{:>4}. {:>10} {}",
+            i + 1,
+            attempt.guess,
+            game.feedback_at(i)
+        )?;
+    }
+    write_hints(game.history().len(), out)
+}
+
+fn write_stats<W: Write>(game: &Game, out: &mut W) -> io::Result<()> {
+    let attempts = game.attempts();
+    write!(
+        out,
+        "{}",
+        i18n::fill("stats.attempts", &[("count", &attempts)])
+    )?;
+    match game.max_attempts() {
+        Some(max) => {
+            let left = max.saturating_sub(attempts);
+            let of = i18n::fill("stats.attempts-of", &[("max", &max), ("left", &left)]);
+            writeln!(out, "{}", of)?
+        }
+        None => writeln!(out)?,
+    }
+    let elapsed = game.elapsed().as_secs();
+    write!(out, "{}", i18n::plural("stats.time", elapsed, &[]))?;
+    match (game.time_limit(), game.time_left()) {
+        (Some(limit), Some(left)) => {
+            let (limit, left) = (limit.as_secs(), left.as_secs());
+            let of = i18n::fill("stats.time-of", &[("limit", &limit), ("left", &left)]);
+            writeln!(out, "{}", of)?
+        }
+        _ => writeln!(out)?,
+    }
+    if !game.hints_used().is_empty() {
+        let hints = i18n::fill(
+            "stats.hints",
+            &[
+                ("count", &game.hints_used().len()),
+                ("penalty", &game.penalty()),
+            ],
+        );
+        writeln!(out, "{}", hints)?;
+    }
+    let range = game.known_range();
+    let possible = i18n::fill(
+        "stats.possible",
+        &[
+            (
+                "count",
+                &(u64::from(*range.end()) - u64::from(*range.start()) + 1),
+            ),
+            ("min", range.start()),
+            ("max", range.end()),
+        ],
+    );
+    writeln!(out, "{}", possible)?;
+    let guesses = Game::default_max_attempts(&range);
+    writeln!(
+        out,
+        "{}",
+        i18n::plural("stats.binary-search", u64::from(guesses), &[])
+    )
+}
+
+/// Lists the commands.
+pub fn write_help<W: Write>(out: &mut W) -> io::Result<()> {
+    writeln!(out, "{}", i18n::text("help.header"))?;
+    for info in COMMANDS {
+        let usage = format!("/{} {}", info.name, info.arguments);
+        writeln!(out, "  {:<16} {}", usage.trim_end(), i18n::text(info.help))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_command_parses_by_its_full_name() {
+        // none of them reaches the fallthrough of `parse`
+        for info in COMMANDS {
+            assert!(parse(&format!("/{}", info.name)).is_ok(), "/{}", info.name);
+        }
+    }
+
+    #[test]
+    fn a_shared_beginning_is_ambiguous() {
+        assert_eq!(
+            parse("/h"),
+            Err(ParseCommandError::Ambiguous(
+                "h".to_string(),
+                vec!["hint", "history", "help"]
+            ))
+        );
+        assert_eq!(
+            parse("/hi"),
+            Err(ParseCommandError::Ambiguous(
+                "hi".to_string(),
+                vec!["hint", "history"]
+            ))
+        );
+        assert_eq!(
+            parse("/h").unwrap_err().to_string(),
+            "/h could be /hint or /history or /help"
+        );
+    }
+
+    #[test]
+    fn a_unique_beginning_is_the_command() {
+        assert_eq!(parse("/q"), Ok(Turn::Stop));
+        assert_eq!(parse("/his"), Ok(Turn::Command(Command::History)));
+        assert_eq!(
+            parse("/hin parity"),
+            Ok(Turn::Command(Command::Hint(Some("parity".to_string()))))
+        );
+        assert_eq!(
+            parse("/sa  game.json "),
+            Ok(Turn::Command(Command::Save(Some(PathBuf::from(
+                "game.json"
+            )))))
+        );
+    }
+
+    #[test]
+    fn anything_else_is_unknown() {
+        assert_eq!(
+            parse("/bogus"),
+            Err(ParseCommandError::Unknown("bogus".to_string()))
+        );
+        assert_eq!(
+            parse("hello"),
+            Err(ParseCommandError::Unknown("hello".to_string()))
+        );
+        assert_eq!(parse("/"), Err(ParseCommandError::Unknown(String::new())));
+    }
+
+    #[test]
+    fn only_some_commands_take_an_argument() {
+        assert_eq!(parse("/range 5"), Err(ParseCommandError::Argument("range")));
+        assert_eq!(parse("/q now"), Err(ParseCommandError::Argument("quit")));
+    }
+}
@@ -0,0 +1,288 @@
+//! The line protocol spoken by `guess-server` and `guess-client`.
+//!
+//! Every message is one line of text ending with `\n`, made of a keyword in
+//! capitals and its arguments separated by spaces.
+//!
+//! ```text
+//! server: WELCOME 1 100 7     a new game: range of the secret number and attempts allowed (0: no limit)
+//! client: GUESS 42
+//! server: LESS 1              42 is less than the secret number, 1 attempt used
+//! client: GUESS 60
+//! server: GREATER 2           60 is greater than the secret number, 2 attempts used
+//! client: GUESS 51
+//! server: EQUAL 3             found with 3 attempts: the game is over
+//! client: NEW                 start another game, the server answers WELCOME
+//! client: QUIT
+//! server: BYE quit
+//! ```
+//!
+//! In a race room several clients guess the same secret number:
+//!
+//! ```text
+//! client: JOIN lan alice      join (or create) the room "lan" as alice: names are single words
+//! server: JOINED lan 2        sent to everyone in the room, with the number of players
+//! server: WELCOME 1 100 7     the game of the room, the attempts are for each player
+//! server: HINT bob 30 LESS    the guess of another player and its answer
+//! client: GUESS 42
+//! server: LESS 1
+//! server: WINNER bob 4        sent to everyone when somebody finds the secret number
+//! server: STANDING 1 bob 4 0  rank, player, attempts and distance of the closest guess (- if none)
+//! server: STANDING 2 alice 1 12
+//! server: END                 the race is over, NEW goes back to a game alone
+//! ```
+//!
+//! When the attempts run out the server sends `LOST <secret> attempts` after
+//! the answer to the last guess; a guess made after the end of the time budget
+//! gets `LOST <secret> time` instead of an answer. A request that cannot be served gets
+//! `ERR <message>`. A client that sends nothing for too long gets `BYE idle`
+//! and the connection is closed.
+
+use crate::{Loss, Outcome};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// A line sent by the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Request {
+    Guess(u32),
+    New,
+    Join { room: String, name: String },
+    Quit,
+}
+
+/// A line sent by the server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Response {
+    Welcome {
+        range: RangeInclusive<u32>,
+        max_attempts: Option<u32>,
+    },
+    /// The answer to a guess, with the attempts used so far.
+    Answer {
+        outcome: Outcome,
+        attempts: u32,
+    },
+    Lost {
+        secret_number: u32,
+        loss: Loss,
+    },
+    Error(String),
+    Bye(String),
+    Joined {
+        room: String,
+        players: usize,
+    },
+    Hint {
+        player: String,
+        guess: u32,
+        outcome: Outcome,
+    },
+    Winner {
+        player: String,
+        attempts: u32,
+    },
+    Standing {
+        rank: usize,
+        player: String,
+        attempts: u32,
+        distance: Option<u32>,
+    },
+    End,
+}
+
+/// A line that is not a [`Request`] or a [`Response`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMessageError(String);
+
+impl fmt::Display for ParseMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "malformed message '{}'", self.0)
+    }
+}
+
+impl std::error::Error for ParseMessageError {}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Request::Guess(guess) => write!(f, "GUESS {}", guess),
+            Request::New => write!(f, "NEW"),
+            Request::Join { room, name } => write!(f, "JOIN {} {}", room, name),
+            Request::Quit => write!(f, "QUIT"),
+        }
+    }
+}
+
+impl FromStr for Request {
+    type Err = ParseMessageError;
+
+    fn from_str(line: &str) -> Result<Request, ParseMessageError> {
+        let error = || ParseMessageError(line.trim().to_string());
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["GUESS", guess] => guess.parse().map(Request::Guess).map_err(|_| error()),
+            ["NEW"] => Ok(Request::New),
+            ["JOIN", room, name] => Ok(Request::Join {
+                room: room.to_string(),
+                name: name.to_string(),
+            }),
+            ["QUIT"] => Ok(Request::Quit),
+            _ => Err(error()),
+        }
+    }
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Response::Welcome {
+                range,
+                max_attempts,
+            } => write!(
+                f,
+                "WELCOME {} {} {}",
+                range.start(),
+                range.end(),
+                max_attempts.unwrap_or(0)
+            ),
+            Response::Answer { outcome, attempts } => {
+                write!(f, "{} {}", outcome_keyword(*outcome), attempts)
+            }
+            Response::Lost {
+                secret_number,
+                loss,
+            } => {
+                let reason = match loss {
+                    Loss::OutOfAttempts => "attempts",
+                    Loss::OutOfTime => "time",
+                    Loss::GaveUp => "giveup",
+                };
+                write!(f, "LOST {} {}", secret_number, reason)
+            }
+            Response::Error(message) => write!(f, "ERR {}", message),
+            Response::Bye(reason) => write!(f, "BYE {}", reason),
+            Response::Joined { room, players } => write!(f, "JOINED {} {}", room, players),
+            Response::Hint {
+                player,
+                guess,
+                outcome,
+            } => write!(f, "HINT {} {} {}", player, guess, outcome_keyword(*outcome)),
+            Response::Winner { player, attempts } => write!(f, "WINNER {} {}", player, attempts),
+            Response::Standing {
+                rank,
+                player,
+                attempts,
+                distance,
+            } => {
+                write!(f, "STANDING {} {} {} ", rank, player, attempts)?;
+                match distance {
+                    Some(distance) => write!(f, "{}", distance),
+                    None => write!(f, "-"),
+                }
+            }
+            Response::End => write!(f, "END"),
+        }
+    }
+}
+
+fn outcome_keyword(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::TooSmall => "LESS",
+        Outcome::TooBig => "GREATER",
+        Outcome::Win => "EQUAL",
+    }
+}
+
+fn parse_outcome(keyword: &str) -> Option<Outcome> {
+    match keyword {
+        "LESS" => Some(Outcome::TooSmall),
+        "GREATER" => Some(Outcome::TooBig),
+        "EQUAL" => Some(Outcome::Win),
+        _ => None,
+    }
+}
+
+impl FromStr for Response {
+    type Err = ParseMessageError;
+
+    fn from_str(line: &str) -> Result<Response, ParseMessageError> {
+        let line = line.trim();
+        let error = || ParseMessageError(line.to_string());
+        let (keyword, rest) = match line.find(' ') {
+            Some(i) => (&line[..i], line[i + 1..].trim()),
+            None => (line, ""),
+        };
+        let numbers = || -> Result<Vec<u32>, ParseMessageError> {
+            rest.split_whitespace()
+                .map(|word| word.parse().map_err(|_| error()))
+                .collect()
+        };
+        let answer = |outcome| match numbers()?.as_slice() {
+            [attempts] => Ok(Response::Answer {
+                outcome,
+                attempts: *attempts,
+            }),
+            _ => Err(error()),
+        };
+        match keyword {
+            "WELCOME" => match numbers()?.as_slice() {
+                [min, max, max_attempts] => Ok(Response::Welcome {
+                    range: *min..=*max,
+                    max_attempts: Some(*max_attempts).filter(|max| *max > 0),
+                }),
+                _ => Err(error()),
+            },
+            "LESS" | "GREATER" | "EQUAL" => answer(parse_outcome(keyword).ok_or_else(error)?),
+            "LOST" => {
+                let words: Vec<&str> = rest.split_whitespace().collect();
+                let loss = match words.get(1) {
+                    Some(&"attempts") => Loss::OutOfAttempts,
+                    Some(&"time") => Loss::OutOfTime,
+                    Some(&"giveup") => Loss::GaveUp,
+                    _ => return Err(error()),
+                };
+                match words[0].parse() {
+                    Ok(secret_number) if words.len() == 2 => Ok(Response::Lost {
+                        secret_number,
+                        loss,
+                    }),
+                    _ => Err(error()),
+                }
+            }
+            "ERR" => Ok(Response::Error(rest.to_string())),
+            "BYE" => Ok(Response::Bye(rest.to_string())),
+            "END" if rest.is_empty() => Ok(Response::End),
+            _ => {
+                // the messages of the race rooms start with a name
+                let words: Vec<&str> = rest.split_whitespace().collect();
+                let number = |i: usize| -> Option<u32> { words.get(i)?.parse().ok() };
+                match (keyword, words.len()) {
+                    ("JOINED", 2) => Ok(Response::Joined {
+                        room: words[0].to_string(),
+                        players: number(1).ok_or_else(error)? as usize,
+                    }),
+                    ("HINT", 3) => Ok(Response::Hint {
+                        player: words[0].to_string(),
+                        guess: number(1).ok_or_else(error)?,
+                        outcome: parse_outcome(words[2]).ok_or_else(error)?,
+                    }),
+                    ("WINNER", 2) => Ok(Response::Winner {
+                        player: words[0].to_string(),
+                        attempts: number(1).ok_or_else(error)?,
+                    }),
+                    ("STANDING", 4) => Ok(Response::Standing {
+                        rank: number(0).ok_or_else(error)? as usize,
+                        player: words[1].to_string(),
+                        attempts: number(2).ok_or_else(error)?,
+                        distance: match words[3] {
+                            "-" => None,
+                            distance => Some(distance.parse().map_err(|_| error())?),
+                        },
+                    }),
+                    _ => Err(error()),
+                }
+            }
+        }
+    }
+}
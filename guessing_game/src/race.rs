@@ -0,0 +1,252 @@
+//! Race rooms: several players guess the same secret number at the same
+//! time, and everyone sees the guesses of the others.
+//!
+//! A [`Room`] does no I/O: its methods return the messages to deliver, and
+//! the [`server`](crate::server) sends them.
+
+use crate::protocol::Response;
+use crate::{Game, GuessError, Loss, Outcome, Status};
+use std::cmp::Reverse;
+
+/// The recipients of a message of a [`Room`]; players are known by an id
+/// chosen by the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum To {
+    One(u64),
+    Others(u64),
+    All,
+}
+
+/// A player in a [`Room`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Racer {
+    pub id: u64,
+    pub name: String,
+    /// Guesses made so far.
+    pub attempts: u32,
+    /// How far the closest guess was from the secret number.
+    pub closest: Option<u32>,
+    /// The player left the room.
+    pub left: bool,
+}
+
+/// Players racing to find the secret number of the same game.
+#[derive(Debug)]
+pub struct Room {
+    name: String,
+    game: Game,
+    max_attempts: Option<u32>,
+    racers: Vec<Racer>,
+    winner: Option<usize>,
+}
+
+impl Room {
+    /// A room playing `game`, where every player can make up to
+    /// `max_attempts` guesses; the attempts allowed by `game` itself are for
+    /// all the players together.
+    pub fn new(name: &str, game: Game, max_attempts: Option<u32>) -> Room {
+        Room {
+            name: name.to_string(),
+            game,
+            max_attempts,
+            racers: Vec::new(),
+            winner: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    pub fn racers(&self) -> &[Racer] {
+        &self.racers
+    }
+
+    pub fn winner(&self) -> Option<&Racer> {
+        self.winner.map(|index| &self.racers[index])
+    }
+
+    /// Nobody can guess any more.
+    pub fn is_over(&self) -> bool {
+        self.game.is_finished()
+    }
+
+    /// Nobody is in the room any more.
+    pub fn is_empty(&self) -> bool {
+        self.racers.iter().all(|racer| racer.left)
+    }
+
+    /// Adds the player `id` called `name`, or tells why it cannot join.
+    pub fn join(&mut self, id: u64, name: &str) -> Result<Vec<(To, Response)>, String> {
+        if self.is_over() {
+            return Err("the race is over".to_string());
+        }
+        if self
+            .racers
+            .iter()
+            .any(|racer| !racer.left && racer.name == name)
+        {
+            return Err(format!("{} is already in the room", name));
+        }
+        self.racers.push(Racer {
+            id,
+            name: name.to_string(),
+            attempts: 0,
+            closest: None,
+            left: false,
+        });
+
+        let players = self.racers.iter().filter(|racer| !racer.left).count();
+        Ok(vec![
+            (
+                To::All,
+                Response::Joined {
+                    room: self.name.clone(),
+                    players,
+                },
+            ),
+            (
+                To::One(id),
+                Response::Welcome {
+                    range: self.game.range().clone(),
+                    max_attempts: self.max_attempts,
+                },
+            ),
+        ])
+    }
+
+    /// Removes the player `id`; the race is lost when the players left have
+    /// no attempts.
+    pub fn leave(&mut self, id: u64) -> Vec<(To, Response)> {
+        if let Some(racer) = self.racers.iter_mut().find(|racer| racer.id == id) {
+            racer.left = true;
+        }
+        if !self.is_over() && !self.is_empty() && !self.anyone_can_guess() {
+            self.game.lose(Loss::OutOfAttempts);
+            return self.results();
+        }
+        Vec::new()
+    }
+
+    /// The guess of the player `id`: the answer goes to the player, and to
+    /// the others as a hint.
+    pub fn guess(&mut self, id: u64, guess: u32) -> Vec<(To, Response)> {
+        let index = match self
+            .racers
+            .iter()
+            .position(|racer| racer.id == id && !racer.left)
+        {
+            Some(index) => index,
+            None => return vec![(To::One(id), Response::Error("not in the room".to_string()))],
+        };
+        if self.is_over() {
+            return vec![(To::One(id), Response::Error("the race is over".to_string()))];
+        }
+        if !self.can_guess(&self.racers[index]) {
+            return vec![(To::One(id), Response::Error("no attempts left".to_string()))];
+        }
+
+        match self.game.guess(guess) {
+            Ok(outcome) => {
+                let distance = if guess > self.game.secret_number() {
+                    guess - self.game.secret_number()
+                } else {
+                    self.game.secret_number() - guess
+                };
+                let racer = &mut self.racers[index];
+                racer.attempts += 1;
+                racer.closest = Some(
+                    racer
+                        .closest
+                        .map_or(distance, |closest| closest.min(distance)),
+                );
+
+                let mut messages = vec![
+                    (
+                        To::One(id),
+                        Response::Answer {
+                            outcome,
+                            attempts: racer.attempts,
+                        },
+                    ),
+                    (
+                        To::Others(id),
+                        Response::Hint {
+                            player: racer.name.clone(),
+                            guess,
+                            outcome,
+                        },
+                    ),
+                ];
+                if outcome == Outcome::Win {
+                    self.winner = Some(index);
+                    messages.extend(self.results());
+                } else if let Status::Lost(_) = self.game.status() {
+                    messages.extend(self.results());
+                } else if !self.anyone_can_guess() {
+                    self.game.lose(Loss::OutOfAttempts);
+                    messages.extend(self.results());
+                }
+                messages
+            }
+            Err(GuessError::TimeUp) => self.results(),
+            Err(err) => vec![(To::One(id), Response::Error(err.to_string()))],
+        }
+    }
+
+    /// The players from the first to the last: the winner, then the closest
+    /// guesses, then the fewest attempts.
+    pub fn standings(&self) -> Vec<&Racer> {
+        let mut standings: Vec<(usize, &Racer)> = self.racers.iter().enumerate().collect();
+        standings.sort_by_key(|(index, racer)| {
+            (
+                Reverse(self.winner == Some(*index)),
+                racer.closest.is_none(),
+                racer.closest,
+                racer.attempts,
+            )
+        });
+        standings.into_iter().map(|(_, racer)| racer).collect()
+    }
+
+    fn can_guess(&self, racer: &Racer) -> bool {
+        !racer.left && self.max_attempts.is_none_or(|max| racer.attempts < max)
+    }
+
+    fn anyone_can_guess(&self) -> bool {
+        self.racers.iter().any(|racer| self.can_guess(racer))
+    }
+
+    /// The messages sent to everyone at the end of the race.
+    fn results(&self) -> Vec<(To, Response)> {
+        let mut messages = Vec::new();
+        match (self.winner(), self.game.status()) {
+            (Some(winner), _) => messages.push(Response::Winner {
+                player: winner.name.clone(),
+                attempts: winner.attempts,
+            }),
+            (None, Status::Lost(loss)) => messages.push(Response::Lost {
+                secret_number: self.game.secret_number(),
+                loss,
+            }),
+            (None, _) => {}
+        }
+        for (rank, racer) in self.standings().into_iter().enumerate() {
+            messages.push(Response::Standing {
+                rank: rank + 1,
+                player: racer.name.clone(),
+                attempts: racer.attempts,
+                distance: racer.closest,
+            });
+        }
+        messages.push(Response::End);
+        messages
+            .into_iter()
+            .map(|message| (To::All, message))
+            .collect()
+    }
+}
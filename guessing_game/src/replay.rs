@@ -0,0 +1,445 @@
+//! Recordings of finished games, to share them and to play them back.
+//!
+//! A replay holds what is needed to play the game again: the seed, the range
+//! and limits, every guess with the time it was made and the answer it got.
+//! Playing it back runs the guesses through the current [`Game`] too, so a
+//! change in the rules, or in the secret number a seed picks, shows up as a
+//! [`Difference`].
+
+use crate::i18n;
+use crate::liar::Liar;
+use crate::save::corrupt;
+use crate::score;
+use crate::{FeedbackMode, Game, GuessError, HintUse, Loss, Outcome, Status};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// The version of the format written by [`Replay::save`]; bumped whenever a
+/// field is added.
+///
+/// 1. The first version.
+/// 2. `hints`, `feedback`, `liar` and `evil`.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// The oldest version of the format that [`Replay::load`] still reads.
+pub const OLDEST_VERSION: u32 = 1;
+
+/// How a recorded game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ending {
+    Won,
+    OutOfAttempts,
+    OutOfTime,
+    GaveUp,
+    /// The player stopped before the end.
+    Unfinished,
+}
+
+impl From<Status> for Ending {
+    fn from(status: Status) -> Ending {
+        match status {
+            Status::Playing => Ending::Unfinished,
+            Status::Won => Ending::Won,
+            Status::Lost(Loss::OutOfAttempts) => Ending::OutOfAttempts,
+            Status::Lost(Loss::OutOfTime) => Ending::OutOfTime,
+            Status::Lost(Loss::GaveUp) => Ending::GaveUp,
+        }
+    }
+}
+
+impl fmt::Display for Ending {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(i18n::text(match self {
+            Ending::Won => "ending.won",
+            Ending::OutOfAttempts => "ending.out-of-attempts",
+            Ending::OutOfTime => "ending.out-of-time",
+            Ending::GaveUp => "ending.gave-up",
+            Ending::Unfinished => "ending.unfinished",
+        }))
+    }
+}
+
+/// A guess of a [`Replay`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReplayGuess {
+    /// Milliseconds since the start of the game.
+    pub at: u64,
+    pub guess: u32,
+    pub outcome: Outcome,
+}
+
+/// A recorded game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Replay {
+    pub version: u32,
+    pub seed: Option<u64>,
+    pub min: u32,
+    pub max: u32,
+    pub secret_number: u32,
+    pub max_attempts: Option<u32>,
+    /// In seconds.
+    pub time_limit: Option<u64>,
+    pub guesses: Vec<ReplayGuess>,
+    /// Missing in version 1, like the fields after it.
+    #[serde(default)]
+    pub hints: Vec<HintUse>,
+    #[serde(default)]
+    pub feedback: FeedbackMode,
+    #[serde(default)]
+    pub liar: Option<Liar>,
+    /// An [`Evil`](crate::secret::Evil) secret, which answered as late as
+    /// it could with `secret_number`.
+    #[serde(default)]
+    pub evil: bool,
+    /// Milliseconds from the start to the end of the game.
+    pub duration: u64,
+    pub ending: Ending,
+}
+
+/// Something that the current engine does differently from the recording.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// The seed picks another secret number.
+    SecretNumber {
+        recorded: u32,
+        current: u32,
+    },
+    /// The guess number `index` (from 0) gets another answer.
+    Guess {
+        index: usize,
+        recorded: Outcome,
+        current: Result<Outcome, GuessError>,
+    },
+    Ending {
+        recorded: Ending,
+        current: Ending,
+    },
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message = match self {
+            Difference::SecretNumber { recorded, current } => i18n::fill(
+                "difference.secret",
+                &[("recorded", recorded), ("current", current)],
+            ),
+            Difference::Guess {
+                index,
+                recorded,
+                current,
+            } => match current {
+                Ok(outcome) => i18n::fill(
+                    "difference.guess",
+                    &[
+                        ("index", &(index + 1)),
+                        ("recorded", recorded),
+                        ("current", outcome),
+                    ],
+                ),
+                Err(err) => i18n::fill(
+                    "difference.refused",
+                    &[
+                        ("index", &(index + 1)),
+                        ("recorded", recorded),
+                        ("error", err),
+                    ],
+                ),
+            },
+            Difference::Ending { recorded, current } => i18n::fill(
+                "difference.ending",
+                &[("recorded", recorded), ("current", current)],
+            ),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl Replay {
+    /// The recording of `game`.
+    pub fn record(game: &Game) -> Replay {
+        Replay {
+            version: FORMAT_VERSION,
+            seed: game.seed(),
+            min: *game.range().start(),
+            max: *game.range().end(),
+            secret_number: game.secret_number(),
+            max_attempts: game.max_attempts(),
+            time_limit: game.time_limit().map(|limit| limit.as_secs()),
+            guesses: game
+                .history()
+                .iter()
+                .zip(game.attempt_times())
+                .map(|(attempt, at)| ReplayGuess {
+                    at: at.as_millis() as u64,
+                    guess: attempt.guess,
+                    outcome: attempt.outcome,
+                })
+                .collect(),
+            hints: game.hints_used().to_vec(),
+            feedback: game.feedback_mode(),
+            liar: game.liar(),
+            evil: game.secret().is_evil(),
+            duration: game.elapsed().as_millis() as u64,
+            ending: Ending::from(game.status()),
+        }
+    }
+
+    /// `last_replay.json` in the data directory of the game, where the last
+    /// game played is recorded.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(score::data_dir()?.join("last_replay.json"))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        fs::write(path, json)
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Replay> {
+        let path = path.as_ref();
+        let invalid = |message: String| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", path.display(), message),
+            )
+        };
+
+        let json = fs::read_to_string(path)
+            .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        let value: serde_json::Value = serde_json::from_str(&json)
+            .map_err(|err| invalid(i18n::fill("replay.not-replay", &[("error", &err)])))?;
+        match value.get("version").and_then(|version| version.as_u64()) {
+            // the fields added since version 1 default to a game without them
+            Some(version)
+                if (u64::from(OLDEST_VERSION)..=u64::from(FORMAT_VERSION)).contains(&version) => {}
+            Some(version) => {
+                return Err(invalid(i18n::fill(
+                    "replay.version",
+                    &[
+                        ("version", &version),
+                        ("oldest", &OLDEST_VERSION),
+                        ("newest", &FORMAT_VERSION),
+                    ],
+                )))
+            }
+            None => return Err(invalid(i18n::text("replay.no-version").to_string())),
+        }
+        let replay: Replay = serde_json::from_value(value).map_err(|err| invalid(corrupt(&err)))?;
+        if replay.min > replay.max || !(replay.min..=replay.max).contains(&replay.secret_number) {
+            return Err(invalid(corrupt(&i18n::fill(
+                "corrupt.range",
+                &[
+                    ("secret", &replay.secret_number),
+                    ("min", &replay.min),
+                    ("max", &replay.max),
+                ],
+            ))));
+        }
+        Ok(replay)
+    }
+
+    /// Plays the game back on `out`, waiting between the guesses as long as
+    /// the player did, divided by `speed`: 0 does not wait. Returns what the
+    /// current engine does differently, also written to `out`.
+    pub fn play_back<W: Write>(&self, speed: u32, out: &mut W) -> io::Result<Vec<Difference>> {
+        let wait = |from: u64, to: u64| {
+            if speed > 0 && to > from {
+                thread::sleep(Duration::from_millis(to - from) / speed);
+            }
+        };
+
+        let header = match self.seed {
+            Some(seed) => i18n::fill(
+                "replay.header-seed",
+                &[("min", &self.min), ("max", &self.max), ("seed", &seed)],
+            ),
+            None => i18n::fill("replay.header", &[("min", &self.min), ("max", &self.max)]),
+        };
+        writeln!(out, "{}", header)?;
+
+        let mut differences = Vec::new();
+        let mut game = self.game();
+        if let Some(seed) = self.seed.filter(|_| !self.evil) {
+            let range = self.min..=self.max;
+            let current = Game::new(range, &mut StdRng::seed_from_u64(seed)).secret_number();
+            if current != self.secret_number {
+                differences.push(Difference::SecretNumber {
+                    recorded: self.secret_number,
+                    current,
+                });
+            }
+        }
+
+        let write_hints = |after: usize, out: &mut W| -> io::Result<()> {
+            for used in self
+                .hints
+                .iter()
+                .filter(|used| used.after as usize == after)
+            {
+                writeln!(
+                    out,
+                    "{:>20} {}: {} (-{}%)",
+                    i18n::text("history.hint"),
+                    used.hint,
+                    used.answer,
+                    used.penalty
+                )?;
+            }
+            Ok(())
+        };
+
+        let mut last = 0;
+        for (index, guess) in self.guesses.iter().enumerate() {
+            write_hints(index, out)?;
+            wait(last, guess.at);
+            last = guess.at;
+            write!(
+                out,
+                "[{:>6.1}s] {:>10} {}",
+                guess.at as f64 / 1000.0,
+                guess.guess,
+                guess.outcome
+            )?;
+            let current = self.engine_guess(&mut game, guess);
+            if current != Ok(guess.outcome) {
+                let difference = Difference::Guess {
+                    index,
+                    recorded: guess.outcome,
+                    current,
+                };
+                write!(out, "  <- {}", difference)?;
+                differences.push(difference);
+            }
+            writeln!(out)?;
+        }
+        write_hints(self.guesses.len(), out)?;
+
+        if self.ending == Ending::OutOfTime || self.ending == Ending::GaveUp {
+            wait(last, self.duration);
+        }
+        if !game.is_finished() && self.is_late(self.duration) {
+            game.lose(Loss::OutOfTime);
+        }
+        // giving up is up to the player, not to the rules
+        if self.ending == Ending::GaveUp {
+            game.lose(Loss::GaveUp);
+        }
+        let current = Ending::from(game.status());
+        let seconds = format!("{:.1}", self.duration as f64 / 1000.0);
+        writeln!(
+            out,
+            "{}",
+            i18n::plural(
+                "replay.end",
+                self.guesses.len() as u64,
+                &[("ending", &self.ending), ("seconds", &seconds)]
+            )
+        )?;
+        if current != self.ending {
+            let difference = Difference::Ending {
+                recorded: self.ending,
+                current,
+            };
+            writeln!(
+                out,
+                "{}",
+                i18n::fill("replay.now", &[("difference", &difference)])
+            )?;
+            differences.push(difference);
+        }
+        Ok(differences)
+    }
+
+    /// Checks the replay against the current engine without playing it back.
+    pub fn check(&self) -> Vec<Difference> {
+        self.play_back(0, &mut io::sink())
+            .expect("writing to a sink cannot fail")
+    }
+
+    /// The game of the replay, as the current engine plays it.
+    fn game(&self) -> Game {
+        let game = if self.evil {
+            Game::evil(self.min..=self.max)
+        } else {
+            Game::with_secret(self.min..=self.max, self.secret_number)
+        };
+        game.with_max_attempts(self.max_attempts)
+            .with_seed(self.seed)
+            .with_feedback(self.feedback)
+            .with_liar(self.liar)
+    }
+
+    /// Whether `at` milliseconds from the start are past the time limit; the
+    /// limit comes from the file, so it may be too big to count in them.
+    fn is_late(&self, at: u64) -> bool {
+        self.time_limit
+            .is_some_and(|limit| at >= limit.saturating_mul(1000))
+    }
+
+    /// The answer of the current engine to `guess`. The time is not the one
+    /// of the recording, so a guess made after the time limit is refused here.
+    fn engine_guess(&self, game: &mut Game, guess: &ReplayGuess) -> Result<Outcome, GuessError> {
+        if self.is_late(guess.at) {
+            game.lose(Loss::OutOfTime);
+            return Err(GuessError::TimeUp);
+        }
+        game.guess(guess.guess)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn won_replay(time_limit: Option<u64>) -> Replay {
+        let mut game = Game::with_secret(1..=100, 42);
+        game.guess(10).unwrap();
+        game.guess(42).unwrap();
+        Replay {
+            time_limit,
+            ..Replay::record(&game)
+        }
+    }
+
+    #[test]
+    fn a_huge_time_limit_is_never_reached() {
+        let replay = won_replay(Some(100_000_000_000_000_000));
+        assert_eq!(replay.check(), vec![]);
+        assert_eq!(won_replay(Some(u64::MAX)).check(), vec![]);
+    }
+
+    #[test]
+    fn a_guess_after_the_time_limit_is_refused() {
+        let mut replay = won_replay(Some(1));
+        replay.guesses[1].at = 1500;
+        replay.duration = 1500;
+        assert_eq!(
+            replay.check(),
+            vec![
+                Difference::Guess {
+                    index: 1,
+                    recorded: Outcome::Win,
+                    current: Err(GuessError::TimeUp),
+                },
+                Difference::Ending {
+                    recorded: Ending::Won,
+                    current: Ending::OutOfTime,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Saving a game in progress to a file, and resuming it later.
+//!
+//! The file is JSON with a `version` field: the older versions of the format
+//! are still read, a file written by a newer one is refused instead of being
+//! misread. Loading plays
+//! the saved guesses again, so a file that was edited or damaged is reported
+//! as corrupt.
+
+use crate::i18n;
+use crate::liar::Liar;
+use crate::score;
+use crate::{FeedbackMode, Game, HintUse, Outcome};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// The version of the format written by [`save`]; bumped whenever a field
+/// is added.
+///
+/// 1. The first version.
+/// 2. `hints`, `feedback`, `liar` and `evil`.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// The oldest version of the format that [`load`] still reads.
+pub const OLDEST_VERSION: u32 = 1;
+
+/// The content of a saved game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SavedGame {
+    version: u32,
+    seed: Option<u64>,
+    min: u32,
+    max: u32,
+    secret_number: u32,
+    max_attempts: Option<u32>,
+    /// In seconds.
+    time_limit: Option<u64>,
+    /// The time already spent on the game, in milliseconds.
+    elapsed: u64,
+    history: Vec<SavedAttempt>,
+    /// Missing in version 1, like the fields after it.
+    #[serde(default)]
+    hints: Vec<HintUse>,
+    #[serde(default)]
+    feedback: FeedbackMode,
+    #[serde(default)]
+    liar: Option<Liar>,
+    /// An [`Evil`](crate::secret::Evil) secret, the secret number is only
+    /// the smallest one still possible.
+    #[serde(default)]
+    evil: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SavedAttempt {
+    guess: u32,
+    outcome: Outcome,
+}
+
+/// `saved_game.json` in the data directory of the game, where the game is
+/// saved when no file is given.
+pub fn default_path() -> Option<PathBuf> {
+    Some(score::data_dir()?.join("saved_game.json"))
+}
+
+/// Writes `game` to `path`, replacing the file only once it is complete.
+pub fn save<P: AsRef<Path>>(game: &Game, path: P) -> io::Result<()> {
+    let path = path.as_ref();
+    if game.is_finished() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            i18n::text("save.finished"),
+        ));
+    }
+    let saved = SavedGame {
+        version: FORMAT_VERSION,
+        seed: game.seed(),
+        min: *game.range().start(),
+        max: *game.range().end(),
+        secret_number: game.secret_number(),
+        max_attempts: game.max_attempts(),
+        time_limit: game.time_limit().map(|limit| limit.as_secs()),
+        elapsed: game.elapsed().as_millis() as u64,
+        history: game
+            .history()
+            .iter()
+            .map(|attempt| SavedAttempt {
+                guess: attempt.guess,
+                outcome: attempt.outcome,
+            })
+            .collect(),
+        hints: game.hints_used().to_vec(),
+        feedback: game.feedback_mode(),
+        liar: game.liar(),
+        evil: game.secret().is_evil(),
+    };
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(&saved)?;
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, json)?;
+    fs::rename(&temporary, path)
+}
+
+/// Reads the game saved in `path`, ready to be continued.
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Game> {
+    let path = path.as_ref();
+    let invalid = |message: String| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: {}", path.display(), message),
+        )
+    };
+
+    let json = fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+    // the version is checked first, the rest of the format may have changed
+    let value: serde_json::Value = serde_json::from_str(&json)
+        .map_err(|err| invalid(i18n::fill("save.not-saved", &[("error", &err)])))?;
+    match value.get("version").and_then(|version| version.as_u64()) {
+        // the fields added since version 1 default to a game without them
+        Some(version)
+            if (u64::from(OLDEST_VERSION)..=u64::from(FORMAT_VERSION)).contains(&version) => {}
+        Some(version) => {
+            return Err(invalid(i18n::fill(
+                "save.version",
+                &[
+                    ("version", &version),
+                    ("oldest", &OLDEST_VERSION),
+                    ("newest", &FORMAT_VERSION),
+                ],
+            )))
+        }
+        None => return Err(invalid(i18n::text("save.no-version").to_string())),
+    }
+    let saved: SavedGame = serde_json::from_value(value).map_err(|err| invalid(corrupt(&err)))?;
+
+    let range = saved.min..=saved.max;
+    if !range.contains(&saved.secret_number) {
+        return Err(invalid(corrupt(&i18n::fill(
+            "corrupt.range",
+            &[
+                ("secret", &saved.secret_number),
+                ("min", &saved.min),
+                ("max", &saved.max),
+            ],
+        ))));
+    }
+    let game = if saved.evil {
+        Game::evil(range)
+    } else {
+        Game::with_secret(range, saved.secret_number)
+    };
+    let mut game = game
+        .with_max_attempts(saved.max_attempts)
+        .with_time_limit(saved.time_limit.map(Duration::from_secs))
+        .with_seed(saved.seed)
+        .with_feedback(saved.feedback)
+        .with_liar(saved.liar);
+    for (i, attempt) in saved.history.iter().enumerate() {
+        match game.guess(attempt.guess) {
+            Ok(outcome) if outcome == attempt.outcome => {}
+            Ok(outcome) => {
+                return Err(invalid(corrupt(&i18n::fill(
+                    "corrupt.guess",
+                    &[
+                        ("index", &(i + 1)),
+                        ("guess", &attempt.guess),
+                        ("recorded", &attempt.outcome),
+                        ("current", &outcome),
+                    ],
+                ))))
+            }
+            Err(err) => {
+                let refused =
+                    i18n::fill("corrupt.refused", &[("index", &(i + 1)), ("error", &err)]);
+                return Err(invalid(corrupt(&refused)));
+            }
+        }
+    }
+    if game.is_finished() {
+        return Err(invalid(i18n::text("save.over").to_string()));
+    }
+    for used in saved.hints {
+        if used.after > game.attempts() {
+            return Err(invalid(corrupt(&i18n::plural(
+                "corrupt.hint",
+                u64::from(game.attempts()),
+                &[("hint", &used.hint), ("after", &used.after)],
+            ))));
+        }
+        game.log_hint(used);
+    }
+    Ok(game.with_elapsed(Duration::from_millis(saved.elapsed)))
+}
+
+/// The message of a file that was damaged, for the `reason` given.
+pub(crate) fn corrupt(reason: &dyn fmt::Display) -> String {
+    i18n::fill("corrupt", &[("reason", reason)])
+}
+
+/// Saves the game being played when the process is interrupted with Ctrl-C
+/// (SIGINT), then exits.
+pub struct AutoSave {
+    path: PathBuf,
+    game: Arc<Mutex<Option<Game>>>,
+}
+
+impl AutoSave {
+    /// Installs the handler of Ctrl-C, which can be done only once in a
+    /// process; the game is saved to `path`.
+    pub fn install<P: Into<PathBuf>>(path: P) -> io::Result<AutoSave> {
+        let path = path.into();
+        let game: Arc<Mutex<Option<Game>>> = Arc::new(Mutex::new(None));
+
+        let handler_path = path.clone();
+        let handler_game = Arc::clone(&game);
+        ctrlc::set_handler(move || {
+            let game = handler_game
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            println!();
+            if let Some(game) = game.as_ref() {
+                match save(game, &handler_path) {
+                    Ok(()) => println!("{}", resume_hint(&handler_path)),
+                    Err(err) => eprintln!("{}", i18n::fill("save.error", &[("error", &err)])),
+                }
+            }
+            // 128 + SIGINT, like a shell
+            process::exit(130);
+        })
+        .map_err(|err| io::Error::other(err.to_string()))?;
+
+        Ok(AutoSave { path, game })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keeps a copy of `game` for the handler; a finished game is not saved.
+    pub fn update(&self, game: &Game) {
+        let mut saved = self
+            .game
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        *saved = Some(game.clone()).filter(|game| !game.is_finished());
+    }
+}
+
+/// Tells where the game was saved and how to continue it.
+pub fn resume_hint(path: &Path) -> String {
+    i18n::fill("save.resume", &[("path", &path.display())])
+}
@@ -0,0 +1,262 @@
+use crate::i18n;
+use crate::{Game, Status};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// `$XDG_DATA_HOME/guessing_game`, or `~/.local/share/guessing_game` when
+/// the variable is not set: where the files of the game are kept.
+pub(crate) fn data_dir() -> Option<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+    Some(data_home.join("guessing_game"))
+}
+
+/// Points for a finished game, 0 unless it was won.
+///
+/// Bigger ranges are worth more, every attempt beyond the first divides the
+/// points and a game that lasts a minute is worth half. The hints bought take
+/// away their [penalty](Game::penalty).
+pub fn score(game: &Game) -> u32 {
+    if game.status() != Status::Won {
+        return 0;
+    }
+    let bits = u64::from(Game::default_max_attempts(game.range()));
+    let attempts = u64::from(game.attempts().max(1));
+    let seconds = game.elapsed().as_secs();
+    let points = 1000 * bits * 60 / (attempts * (60 + seconds));
+    (points * u64::from(100 - game.penalty()) / 100) as u32
+}
+
+/// A line of the high-score table.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    pub player: String,
+    pub score: u32,
+    /// Day of the game, as `YYYY-MM-DD`.
+    pub date: String,
+    /// A [`Difficulty`](crate::Difficulty) name, or `custom MIN-MAX`.
+    pub difficulty: String,
+    pub seed: Option<u64>,
+    pub attempts: u32,
+    pub seconds: u64,
+}
+
+impl ScoreEntry {
+    /// The entry for a finished `game`, played today.
+    pub fn new(player: &str, game: &Game, difficulty: &str, seed: Option<u64>) -> ScoreEntry {
+        ScoreEntry {
+            player: player.to_string(),
+            score: score(game),
+            date: crate::date::today(),
+            difficulty: difficulty.to_string(),
+            seed,
+            attempts: game.attempts(),
+            seconds: game.elapsed().as_secs(),
+        }
+    }
+}
+
+/// The high scores, kept in a JSON file.
+#[derive(Debug, Clone)]
+pub struct HighScores {
+    path: PathBuf,
+    entries: Vec<ScoreEntry>,
+}
+
+impl HighScores {
+    /// `$XDG_DATA_HOME/guessing_game/scores.json`, or `~/.local/share/...`
+    /// when the variable is not set.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(data_dir()?.join("scores.json"))
+    }
+
+    /// Reads the table from `path`: a missing file is an empty table.
+    pub fn load<P: Into<PathBuf>>(path: P) -> io::Result<HighScores> {
+        let path = path.into();
+        let entries = match fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(HighScores { path, entries })
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries)?;
+        fs::write(&self.path, json)
+    }
+
+    /// Adds `entry` and returns its rank (starting from 1) among the scores of
+    /// the same difficulty.
+    pub fn add(&mut self, entry: ScoreEntry) -> usize {
+        let rank = 1 + self
+            .entries
+            .iter()
+            .filter(|other| other.difficulty == entry.difficulty && other.score >= entry.score)
+            .count();
+        self.entries.push(entry);
+        rank
+    }
+
+    /// The best `n` scores of each difficulty, highest first.
+    pub fn top(&self, n: usize) -> BTreeMap<&str, Vec<&ScoreEntry>> {
+        let mut by_difficulty: BTreeMap<&str, Vec<&ScoreEntry>> = BTreeMap::new();
+        for entry in &self.entries {
+            by_difficulty
+                .entry(&entry.difficulty)
+                .or_default()
+                .push(entry);
+        }
+        for entries in by_difficulty.values_mut() {
+            entries.sort_by_key(|entry| Reverse(entry.score));
+            entries.truncate(n);
+        }
+        by_difficulty
+    }
+
+    /// Prints the best `n` scores of each difficulty.
+    pub fn write_top<W: Write>(&self, n: usize, out: &mut W) -> io::Result<()> {
+        let top = self.top(n);
+        if top.is_empty() {
+            return writeln!(out, "{}", i18n::text("scores.empty"));
+        }
+        for (difficulty, entries) in top {
+            writeln!(out, "== {} ==", difficulty)?;
+            for (i, entry) in entries.iter().enumerate() {
+                let seed = entry
+                    .seed
+                    .map(|seed| seed.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                // the columns padded here, the words of the language around them
+                let entry = i18n::plural(
+                    "scores.entry",
+                    u64::from(entry.attempts),
+                    &[
+                        ("rank", &format!("{:>3}", i + 1)),
+                        ("player", &format!("{:<16}", entry.player)),
+                        ("score", &format!("{:>7}", entry.score)),
+                        ("date", &entry.date),
+                        ("attempts", &format!("{:>3}", entry.attempts)),
+                        ("seed", &seed),
+                    ],
+                );
+                writeln!(out, "{}", entry)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hint::Parity;
+    use std::ops::RangeInclusive;
+    use std::time::Duration;
+
+    /// A game of `range` won at the attempt `attempts`, after `seconds`.
+    fn won(range: RangeInclusive<u32>, attempts: u32, seconds: u64) -> Game {
+        let secret = *range.end();
+        let mut game = Game::with_secret(range, secret).with_elapsed(Duration::from_secs(seconds));
+        for _ in 1..attempts {
+            game.guess(secret - 1).unwrap();
+        }
+        game.guess(secret).unwrap();
+        game
+    }
+
+    #[test]
+    fn the_score_grows_with_the_range_and_shrinks_with_attempts_and_time() {
+        // 7 attempts needed on 1..=100, 10 on 1..=1000
+        assert_eq!(score(&won(1..=100, 1, 0)), 7000);
+        assert_eq!(score(&won(1..=1000, 1, 0)), 10000);
+        assert_eq!(score(&won(1..=100, 2, 0)), 3500);
+        assert_eq!(score(&won(1..=100, 4, 0)), 1750);
+        // a minute halves the points
+        assert_eq!(score(&won(1..=100, 1, 60)), 3500);
+        assert_eq!(score(&won(1..=100, 2, 180)), 875);
+
+        let mut lost = Game::with_secret(1..=100, 42).with_max_attempts(Some(1));
+        lost.guess(41).unwrap();
+        assert_eq!(score(&lost), 0);
+    }
+
+    #[test]
+    fn the_hints_take_away_their_penalty() {
+        let mut game = Game::with_secret(1..=100, 42);
+        game.use_hint(&Parity).unwrap();
+        game.guess(42).unwrap();
+        // 10% off
+        assert_eq!(score(&game), 6300);
+    }
+
+    fn entry(player: &str, difficulty: &str, score: u32) -> ScoreEntry {
+        ScoreEntry {
+            player: player.to_string(),
+            score,
+            date: "2026-10-18".to_string(),
+            difficulty: difficulty.to_string(),
+            seed: None,
+            attempts: 3,
+            seconds: 10,
+        }
+    }
+
+    fn scores() -> HighScores {
+        HighScores {
+            path: PathBuf::from("scores.json"),
+            entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn a_tie_ranks_after_the_scores_already_there() {
+        let mut scores = scores();
+        assert_eq!(scores.add(entry("alice", "normal", 100)), 1);
+        assert_eq!(scores.add(entry("bob", "normal", 50)), 2);
+        // another difficulty is another table
+        assert_eq!(scores.add(entry("carol", "hard", 10)), 1);
+        assert_eq!(scores.add(entry("dave", "normal", 100)), 2);
+        assert_eq!(scores.add(entry("erin", "normal", 75)), 3);
+        assert_eq!(scores.add(entry("frank", "normal", 200)), 1);
+    }
+
+    #[test]
+    fn the_top_is_kept_for_each_difficulty() {
+        let mut scores = scores();
+        for (player, difficulty, score) in [
+            ("alice", "normal", 100),
+            ("bob", "normal", 300),
+            ("carol", "hard", 50),
+            ("dave", "normal", 200),
+            ("erin", "easy", 10),
+        ] {
+            scores.add(entry(player, difficulty, score));
+        }
+        let top = scores.top(2);
+        let players = |difficulty: &str| -> Vec<&str> {
+            top[difficulty]
+                .iter()
+                .map(|entry| entry.player.as_str())
+                .collect()
+        };
+        assert_eq!(
+            top.keys().copied().collect::<Vec<_>>(),
+            vec!["easy", "hard", "normal"]
+        );
+        assert_eq!(players("normal"), vec!["bob", "dave"]);
+        assert_eq!(players("hard"), vec!["carol"]);
+        assert!(scores.top(0).values().all(|entries| entries.is_empty()));
+    }
+}
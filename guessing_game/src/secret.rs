@@ -0,0 +1,191 @@
+//! What is behind the answers of a [`Game`](crate::Game).
+//!
+//! The game does not compare the guesses with a number itself: it asks its
+//! [`Secret`], which can be a number chosen at the start, or an adversary
+//! that only settles on a number when it has to.
+
+use crate::Outcome;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Answers the guesses of a game.
+pub trait Secret: fmt::Debug + Send {
+    /// The answer to `guess`, which is in the range of the game.
+    fn answer(&mut self, guess: u32) -> Outcome;
+
+    /// A secret number that agrees with all the answers so far.
+    fn number(&self) -> u32;
+
+    /// Whether [`Secret::number`] can no longer change.
+    fn is_fixed(&self) -> bool {
+        true
+    }
+
+    /// Whether this is an [`Evil`] secret, which saved games and replays
+    /// have to remember.
+    fn is_evil(&self) -> bool {
+        false
+    }
+
+    fn box_clone(&self) -> Box<dyn Secret>;
+}
+
+impl Clone for Box<dyn Secret> {
+    fn clone(&self) -> Box<dyn Secret> {
+        self.box_clone()
+    }
+}
+
+/// A secret number chosen at the start, like in the tutorial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixed(pub u32);
+
+impl Secret for Fixed {
+    fn answer(&mut self, guess: u32) -> Outcome {
+        // Comparing the Guess to the Secret Number
+        // Ordering is another enum, but the variants for Ordering are Less, Greater, and Equal.
+        // A match expression is made up of arms. An arm consists of a pattern and the code that should
+        // be run if the value given to the beginning of the match expression fits that arm’s pattern.
+        // Rust takes the value given to match and looks through each arm’s pattern in turn. --> switch
+        // (the match is in From<Ordering> for Outcome, src/game.rs, and the game prints the Outcome)
+
+        // first error cmp(&secret_number)  --> Rust has a strong, static type system. However, it also has type inference
+        // When we wrote let mut guess = String::new(), Rust was able to infer that guess should be a
+        // String and didn’t make us write the type.
+        // The secret_number, on the other hand, is a number type. A few number types can have a value between 1 and 100
+        // i32, a 32-bit number; u32, an unsigned 32-bit number; i64, a 64-bit number; as well as others.
+        // Ultimately, we want to convert the String the program reads as input into a real number type so we
+        // can compare it numerically to the secret number: StdinPlayer (src/player.rs) does it, so here both are u32
+        Outcome::from(guess.cmp(&self.0))
+    }
+
+    fn number(&self) -> u32 {
+        self.0
+    }
+
+    fn box_clone(&self) -> Box<dyn Secret> {
+        Box::new(*self)
+    }
+}
+
+/// An adversary without a number: it keeps the numbers that agree with its
+/// answers and always gives the answer that leaves more of them, so that
+/// the player needs as many guesses as a binary search in the worst case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Evil {
+    low: u32,
+    high: u32,
+}
+
+impl Evil {
+    pub fn new(range: &RangeInclusive<u32>) -> Evil {
+        Evil {
+            low: *range.start(),
+            high: *range.end(),
+        }
+    }
+
+    /// The numbers that agree with the answers so far.
+    pub fn possible(&self) -> RangeInclusive<u32> {
+        self.low..=self.high
+    }
+}
+
+impl Secret for Evil {
+    fn answer(&mut self, guess: u32) -> Outcome {
+        if guess < self.low {
+            return Outcome::TooSmall;
+        }
+        if guess > self.high {
+            return Outcome::TooBig;
+        }
+        let below = guess - self.low;
+        let above = self.high - guess;
+        if below == 0 && above == 0 {
+            Outcome::Win
+        } else if above >= below {
+            self.low = guess + 1;
+            Outcome::TooSmall
+        } else {
+            self.high = guess - 1;
+            Outcome::TooBig
+        }
+    }
+
+    /// The smallest number still possible.
+    fn number(&self) -> u32 {
+        self.low
+    }
+
+    fn is_fixed(&self) -> bool {
+        self.low == self.high
+    }
+
+    fn is_evil(&self) -> bool {
+        true
+    }
+
+    fn box_clone(&self) -> Box<dyn Secret> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_fixed_secret_compares_the_guesses_with_its_number() {
+        let mut secret = Fixed(42);
+        assert_eq!(secret.answer(41), Outcome::TooSmall);
+        assert_eq!(secret.answer(43), Outcome::TooBig);
+        assert_eq!(secret.answer(42), Outcome::Win);
+        assert!(secret.is_fixed());
+    }
+
+    #[test]
+    fn the_evil_secret_always_keeps_the_larger_side() {
+        let mut evil = Evil::new(&(1..=100));
+        // 70 numbers above 30, 29 below
+        assert_eq!(evil.answer(30), Outcome::TooSmall);
+        assert_eq!(evil.possible(), 31..=100);
+        // 10 above 90, 59 below
+        assert_eq!(evil.answer(90), Outcome::TooBig);
+        assert_eq!(evil.possible(), 31..=89);
+        // a tie keeps the numbers above
+        assert_eq!(evil.answer(60), Outcome::TooSmall);
+        assert_eq!(evil.possible(), 61..=89);
+        assert_eq!(evil.number(), 61);
+        assert!(!evil.is_fixed());
+    }
+
+    #[test]
+    fn the_evil_secret_agrees_with_its_earlier_answers() {
+        let mut evil = Evil::new(&(1..=100));
+        evil.answer(50);
+        let possible = evil.possible();
+        // outside of the numbers left the answers do not change them
+        assert_eq!(evil.answer(10), Outcome::TooSmall);
+        assert_eq!(evil.answer(50), Outcome::TooSmall);
+        assert_eq!(evil.possible(), possible);
+    }
+
+    #[test]
+    fn the_evil_secret_needs_all_the_attempts_of_a_binary_search() {
+        let range = 1..=100;
+        let mut evil = Evil::new(&range);
+        let (mut low, mut high) = (*range.start(), *range.end());
+        let mut attempts = 0;
+        loop {
+            let guess = low + (high - low) / 2;
+            attempts += 1;
+            match evil.answer(guess) {
+                Outcome::TooSmall => low = guess + 1,
+                Outcome::TooBig => high = guess - 1,
+                Outcome::Win => break,
+            }
+        }
+        assert_eq!(attempts, crate::Game::default_max_attempts(&range));
+        assert!(evil.is_fixed());
+    }
+}
@@ -0,0 +1,317 @@
+use crate::protocol::{Request, Response};
+use crate::race::{Room, To};
+use crate::{Game, GuessError, Status};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::ops::RangeInclusive;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+/// The games a [`Server`] creates.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    pub range: RangeInclusive<u32>,
+    pub max_attempts: Option<u32>,
+    pub time_limit: Option<Duration>,
+    /// A connection that sends nothing for this long is closed.
+    pub idle_timeout: Duration,
+}
+
+impl ServerConfig {
+    /// A new game following this configuration.
+    pub fn new_game(&self, rng: &mut StdRng) -> Game {
+        Game::new(self.range.clone(), rng)
+            .with_max_attempts(self.max_attempts)
+            .with_time_limit(self.time_limit)
+    }
+
+    /// A new race room following this configuration: the attempts are for
+    /// each player, the time limit starts now.
+    pub fn new_room(&self, name: &str, rng: &mut StdRng) -> Room {
+        let game = Game::new(self.range.clone(), rng).with_time_limit(self.time_limit);
+        Room::new(name, game, self.max_attempts)
+    }
+}
+
+/// How long a line can take to be sent: a client that does not read its
+/// messages is disconnected instead of blocking the others.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The writing half of a connection, shared with the other players of its room.
+type Outbox = Arc<Mutex<TcpStream>>;
+
+/// The messages of a room with the connections they go to, sent by
+/// [`deliver`] once the rooms are unlocked.
+type Mail = Vec<(Outbox, Response)>;
+
+/// A race room and the connections of its players.
+struct Race {
+    room: Room,
+    outboxes: HashMap<u64, Outbox>,
+}
+
+impl Race {
+    /// The connections each of the messages of the room goes to.
+    fn address(&self, messages: Vec<(To, Response)>) -> Mail {
+        let mut mail = Vec::new();
+        for (to, response) in messages {
+            for racer in self.room.racers() {
+                let recipient = match to {
+                    To::One(id) => racer.id == id,
+                    To::Others(id) => racer.id != id,
+                    To::All => true,
+                };
+                if let (true, Some(outbox)) = (recipient, self.outboxes.get(&racer.id)) {
+                    mail.push((Arc::clone(outbox), response.clone()));
+                }
+            }
+        }
+        mail
+    }
+}
+
+/// Sends `mail`, without holding the rooms: a player that cannot be reached
+/// is disconnected, and its own thread makes it leave its room.
+fn deliver(mail: Mail) {
+    for (outbox, response) in mail {
+        let mut stream = lock(&outbox);
+        if send(&mut *stream, &response).is_err() {
+            let _ = stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+type Rooms = Mutex<HashMap<String, Race>>;
+
+/// The guessing game over TCP, speaking the [`protocol`](crate::protocol):
+/// every connection plays its own games, in its own thread, or joins a race
+/// room.
+pub struct Server {
+    listener: TcpListener,
+    config: Arc<ServerConfig>,
+    rng: Arc<Mutex<StdRng>>,
+    rooms: Arc<Rooms>,
+}
+
+impl Server {
+    /// Listens on `addr`; the secret numbers come from `rng`.
+    pub fn bind<A: ToSocketAddrs>(
+        addr: A,
+        config: ServerConfig,
+        rng: StdRng,
+    ) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            config: Arc::new(config),
+            rng: Arc::new(Mutex::new(rng)),
+            rooms: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accepts connections forever.
+    pub fn run(&self) -> io::Result<()> {
+        for (id, stream) in (0..).zip(self.listener.incoming()) {
+            let stream = stream?;
+            let config = Arc::clone(&self.config);
+            let rng = Arc::clone(&self.rng);
+            let rooms = Arc::clone(&self.rooms);
+            thread::spawn(move || {
+                let peer = stream
+                    .peer_addr()
+                    .map(|addr| addr.to_string())
+                    .unwrap_or_else(|_| "?".to_string());
+                eprintln!("{} connected", peer);
+                let mut connection = Connection {
+                    id,
+                    config: &config,
+                    rng: &rng,
+                    rooms: &rooms,
+                    room: None,
+                };
+                if let Err(err) = connection.serve(stream) {
+                    eprintln!("{}: {}", peer, err);
+                }
+                connection.leave();
+                eprintln!("{} disconnected", peer);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// One client of the server.
+struct Connection<'a> {
+    id: u64,
+    config: &'a ServerConfig,
+    rng: &'a Mutex<StdRng>,
+    rooms: &'a Rooms,
+    /// The race room joined, if any.
+    room: Option<String>,
+}
+
+impl<'a> Connection<'a> {
+    /// A generator for a new game, so that the lock is not kept while playing.
+    fn game_rng(&self) -> StdRng {
+        StdRng::from_rng(&mut *lock(self.rng)).expect("StdRng cannot fail to seed from StdRng")
+    }
+
+    /// Plays the games of the connection.
+    fn serve(&mut self, stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(self.config.idle_timeout))?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        let mut reader = BufReader::new(stream.try_clone()?);
+        let outbox: Outbox = Arc::new(Mutex::new(stream));
+        let reply = |response: &Response| send(&mut *lock(&outbox), response);
+
+        let mut game = self.config.new_game(&mut self.game_rng());
+        reply(&welcome(&game))?;
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            match reader.read_line(&mut line) {
+                Ok(0) => return Ok(()),
+                Ok(_) => {}
+                Err(ref err)
+                    if err.kind() == io::ErrorKind::WouldBlock
+                        || err.kind() == io::ErrorKind::TimedOut =>
+                {
+                    return reply(&Response::Bye("idle".to_string()));
+                }
+                Err(err) => return Err(err),
+            }
+
+            let request = match line.parse() {
+                Ok(request) => request,
+                Err(err) => {
+                    reply(&Response::Error(format!("{}", err)))?;
+                    continue;
+                }
+            };
+            match request {
+                Request::Guess(guess) if self.room.is_some() => self.race_guess(guess),
+                Request::Guess(guess) => match game.guess(guess) {
+                    Ok(outcome) => {
+                        let attempts = game.attempts();
+                        reply(&Response::Answer { outcome, attempts })?;
+                        if let Status::Lost(_) = game.status() {
+                            reply(&lost(&game))?;
+                        }
+                    }
+                    Err(GuessError::TimeUp) => reply(&lost(&game))?,
+                    Err(err) => reply(&Response::Error(err.to_string()))?,
+                },
+                Request::New => {
+                    self.leave();
+                    game = self.config.new_game(&mut self.game_rng());
+                    reply(&welcome(&game))?;
+                }
+                Request::Join { room, name } => {
+                    self.leave();
+                    if let Err(message) = self.join(&room, &name, &outbox) {
+                        reply(&Response::Error(message))?;
+                    }
+                }
+                Request::Quit => return reply(&Response::Bye("quit".to_string())),
+            }
+        }
+    }
+
+    /// Joins the room called `name`, creating it when it does not exist or
+    /// its race is over.
+    fn join(&mut self, name: &str, player: &str, outbox: &Outbox) -> Result<(), String> {
+        let mut rooms = lock(self.rooms);
+        let renew = rooms.get(name).is_none_or(|race| race.room.is_over());
+        if renew {
+            let room = self.config.new_room(name, &mut self.game_rng());
+            let race = Race {
+                room,
+                outboxes: HashMap::new(),
+            };
+            rooms.insert(name.to_string(), race);
+        }
+
+        let race = rooms.get_mut(name).expect("the room was just created");
+        let messages = race.room.join(self.id, player)?;
+        race.outboxes.insert(self.id, Arc::clone(outbox));
+        let mail = race.address(messages);
+        drop(rooms);
+        deliver(mail);
+        self.room = Some(name.to_string());
+        Ok(())
+    }
+
+    fn race_guess(&mut self, guess: u32) {
+        let mut rooms = lock(self.rooms);
+        let mail = match self.room.as_ref().and_then(|name| rooms.get_mut(name)) {
+            Some(race) => {
+                let messages = race.room.guess(self.id, guess);
+                race.address(messages)
+            }
+            None => return,
+        };
+        drop(rooms);
+        deliver(mail);
+    }
+
+    /// Leaves the room joined, if any; the room is closed when nobody is left.
+    fn leave(&mut self) {
+        let name = match self.room.take() {
+            Some(name) => name,
+            None => return,
+        };
+        let mut rooms = lock(self.rooms);
+        let mail = match rooms.get_mut(&name) {
+            Some(race) => {
+                let messages = race.room.leave(self.id);
+                race.outboxes.remove(&self.id);
+                let mail = race.address(messages);
+                if race.room.is_empty() {
+                    rooms.remove(&name);
+                }
+                mail
+            }
+            None => return,
+        };
+        drop(rooms);
+        deliver(mail);
+    }
+}
+
+/// Locks `mutex`, even when another thread panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn welcome(game: &Game) -> Response {
+    Response::Welcome {
+        range: game.range().clone(),
+        max_attempts: game.max_attempts(),
+    }
+}
+
+fn lost(game: &Game) -> Response {
+    let loss = match game.status() {
+        Status::Lost(loss) => loss,
+        _ => unreachable!("only a lost game is reported as lost"),
+    };
+    Response::Lost {
+        secret_number: game.secret_number(),
+        loss,
+    }
+}
+
+fn send<W: Write>(writer: &mut W, response: &Response) -> io::Result<()> {
+    writeln!(writer, "{}", response)?;
+    writer.flush()
+}
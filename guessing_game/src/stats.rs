@@ -0,0 +1,398 @@
+//! Every finished game, kept in an append-only JSON Lines file, and the
+//! statistics that `cargo run -- stats` prints from it.
+
+use crate::i18n;
+use crate::score::data_dir;
+use crate::{Game, Loss, Status};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// How a recorded game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ending {
+    Won,
+    OutOfAttempts,
+    OutOfTime,
+    GaveUp,
+}
+
+impl Ending {
+    /// `None` for a game still being played.
+    pub fn of(status: Status) -> Option<Ending> {
+        match status {
+            Status::Playing => None,
+            Status::Won => Some(Ending::Won),
+            Status::Lost(Loss::OutOfAttempts) => Some(Ending::OutOfAttempts),
+            Status::Lost(Loss::OutOfTime) => Some(Ending::OutOfTime),
+            Status::Lost(Loss::GaveUp) => Some(Ending::GaveUp),
+        }
+    }
+}
+
+/// A line of the log: one finished game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    pub player: String,
+    /// A [`Difficulty`](crate::Difficulty) name, or `custom MIN-MAX`.
+    pub difficulty: String,
+    /// Day of the game, as `YYYY-MM-DD`.
+    pub date: String,
+    pub min: u32,
+    pub max: u32,
+    pub seed: Option<u64>,
+    pub secret_number: u32,
+    pub guesses: Vec<u32>,
+    pub ending: Ending,
+    pub seconds: u64,
+}
+
+impl GameRecord {
+    /// The record of `game`, played today; `None` while it is not finished.
+    pub fn new(player: &str, game: &Game, difficulty: &str) -> Option<GameRecord> {
+        Some(GameRecord {
+            player: player.to_string(),
+            difficulty: difficulty.to_string(),
+            date: crate::date::today(),
+            min: *game.range().start(),
+            max: *game.range().end(),
+            seed: game.seed(),
+            secret_number: game.secret_number(),
+            guesses: game.history().iter().map(|attempt| attempt.guess).collect(),
+            ending: Ending::of(game.status())?,
+            seconds: game.elapsed().as_secs(),
+        })
+    }
+
+    /// The fewest attempts any strategy needs on average, the secret number
+    /// being any of the range: an answer finds the guess or keeps one side
+    /// of it, so at best one number is found with 1 attempt, two with 2,
+    /// four with 3 and so on, like in a balanced binary search.
+    pub fn optimum(&self) -> f64 {
+        let size = u64::from(self.max - self.min) + 1;
+        let (mut left, mut total, mut attempts) = (size, 0, 1);
+        while left > 0 {
+            let found = left.min(1 << (attempts - 1));
+            total += found * attempts;
+            left -= found;
+            attempts += 1;
+        }
+        total as f64 / size as f64
+    }
+}
+
+/// The games recorded so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GameLog {
+    pub records: Vec<GameRecord>,
+    /// Lines that are not a record, like the last one of a game interrupted
+    /// while it was written.
+    pub skipped: usize,
+}
+
+impl GameLog {
+    /// `$XDG_DATA_HOME/guessing_game/games.jsonl`, or `~/.local/share/...`
+    /// when the variable is not set.
+    pub fn default_path() -> Option<PathBuf> {
+        Some(data_dir()?.join("games.jsonl"))
+    }
+
+    /// Reads the records of `path`: a missing file is an empty log.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<GameLog> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(ref err) if err.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err),
+        };
+        let mut log = GameLog::default();
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(record) => log.records.push(record),
+                Err(_) => log.skipped += 1,
+            }
+        }
+        Ok(log)
+    }
+
+    /// Adds `record` at the end of `path`, without reading the file.
+    pub fn append<P: AsRef<Path>>(path: P, record: &GameRecord) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)
+    }
+}
+
+/// How far the guesses missed, as a part of the range: the upper bounds in
+/// percent of the buckets of [`Summary::distances`], the last one is the rest.
+pub const DISTANCE_BUCKETS: [u64; 5] = [1, 5, 10, 25, 50];
+
+/// The middle of `sorted`, or the mean of the two in the middle when their
+/// count is even; `None` when it is empty.
+pub(crate) fn median(sorted: &[u32]) -> Option<f64> {
+    let n = sorted.len();
+    match n {
+        0 => None,
+        _ if n % 2 == 1 => Some(f64::from(sorted[n / 2])),
+        _ => Some((f64::from(sorted[n / 2 - 1]) + f64::from(sorted[n / 2])) / 2.0),
+    }
+}
+
+/// The statistics of a group of games.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    pub games: usize,
+    pub won: usize,
+    /// The attempts of the games won, in order.
+    pub attempts: Vec<u32>,
+    /// The mean [`GameRecord::optimum`] of the games won.
+    pub optimum: f64,
+    /// Guesses bigger than the secret number.
+    pub overshoots: usize,
+    /// Guesses smaller than the secret number.
+    pub undershoots: usize,
+    /// The wrong guesses by distance from the secret number, see
+    /// [`DISTANCE_BUCKETS`].
+    pub distances: [usize; DISTANCE_BUCKETS.len() + 1],
+}
+
+impl Summary {
+    pub fn of<'a, I: IntoIterator<Item = &'a GameRecord>>(records: I) -> Summary {
+        let mut summary = Summary::default();
+        let mut optimum = 0.0;
+        for record in records {
+            summary.games += 1;
+            if record.ending == Ending::Won {
+                summary.won += 1;
+                summary.attempts.push(record.guesses.len() as u32);
+                optimum += record.optimum();
+            }
+            let size = u64::from(record.max - record.min) + 1;
+            for &guess in &record.guesses {
+                if guess == record.secret_number {
+                    continue;
+                }
+                if guess > record.secret_number {
+                    summary.overshoots += 1;
+                } else {
+                    summary.undershoots += 1;
+                }
+                let percent = u64::from(guess.abs_diff(record.secret_number)) * 100;
+                let bucket = DISTANCE_BUCKETS
+                    .iter()
+                    .position(|&bound| percent <= bound * size)
+                    .unwrap_or(DISTANCE_BUCKETS.len());
+                summary.distances[bucket] += 1;
+            }
+        }
+        summary.attempts.sort_unstable();
+        if summary.won > 0 {
+            summary.optimum = optimum / summary.won as f64;
+        }
+        summary
+    }
+
+    /// The part of the games won, from 0 to 1.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.won as f64 / self.games as f64
+    }
+
+    /// The mean attempts of the games won.
+    pub fn mean_attempts(&self) -> Option<f64> {
+        if self.attempts.is_empty() {
+            return None;
+        }
+        let total: u32 = self.attempts.iter().sum();
+        Some(f64::from(total) / self.attempts.len() as f64)
+    }
+
+    /// The median attempts of the games won.
+    pub fn median_attempts(&self) -> Option<f64> {
+        median(&self.attempts)
+    }
+
+    pub fn write<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let rate = format!("{:.0}", self.win_rate() * 100.0);
+        writeln!(
+            out,
+            "{}",
+            i18n::fill(
+                "report.games",
+                &[("games", &self.games), ("won", &self.won), ("rate", &rate)]
+            )
+        )?;
+        if let (Some(mean), Some(median)) = (self.mean_attempts(), self.median_attempts()) {
+            writeln!(
+                out,
+                "{}",
+                i18n::fill(
+                    "report.attempts",
+                    &[
+                        ("mean", &format!("{:.1}", mean)),
+                        ("median", &format!("{:.1}", median)),
+                        ("optimum", &format!("{:.1}", self.optimum)),
+                        ("difference", &format!("{:+.1}", mean - self.optimum)),
+                    ]
+                )
+            )?;
+        }
+        let misses = self.overshoots + self.undershoots;
+        if misses == 0 {
+            return Ok(());
+        }
+        let percent = |n: usize| format!("{:.0}", n as f64 * 100.0 / misses as f64);
+        writeln!(
+            out,
+            "{}",
+            i18n::fill(
+                "report.wrong",
+                &[
+                    ("too_big", &self.overshoots),
+                    ("too_big_rate", &percent(self.overshoots)),
+                    ("too_small", &self.undershoots),
+                    ("too_small_rate", &percent(self.undershoots)),
+                ]
+            )
+        )?;
+        writeln!(out, "{}", i18n::text("report.distance"))?;
+        let most = self.distances.iter().copied().max().unwrap_or(0).max(1);
+        for (i, &count) in self.distances.iter().enumerate() {
+            let label = match DISTANCE_BUCKETS.get(i) {
+                Some(bound) => format!("<= {}%", bound),
+                None => format!("> {}%", DISTANCE_BUCKETS[i - 1]),
+            };
+            // bars of at most 40 characters
+            let bar = "#".repeat((count * 40).div_ceil(most));
+            writeln!(out, "  {:>6} {:>5} {}", label, count, bar)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints the [`Summary`] of the games of each player and difficulty, only
+/// of `player` when given.
+pub fn write_report<W: Write>(
+    records: &[GameRecord],
+    player: Option<&str>,
+    out: &mut W,
+) -> io::Result<()> {
+    let mut groups: BTreeMap<(&str, &str), Vec<&GameRecord>> = BTreeMap::new();
+    for record in records {
+        if player.is_none_or(|player| player == record.player) {
+            groups
+                .entry((&record.player, &record.difficulty))
+                .or_default()
+                .push(record);
+        }
+    }
+    if groups.is_empty() {
+        return writeln!(out, "{}", i18n::text("report.empty"));
+    }
+    for ((player, difficulty), records) in groups {
+        writeln!(out, "== {}, {} ==", player, difficulty)?;
+        Summary::of(records).write(out)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(
+        range: (u32, u32),
+        secret_number: u32,
+        guesses: &[u32],
+        ending: Ending,
+    ) -> GameRecord {
+        GameRecord {
+            player: "alice".to_string(),
+            difficulty: "normal".to_string(),
+            date: "2026-10-18".to_string(),
+            min: range.0,
+            max: range.1,
+            seed: None,
+            secret_number,
+            guesses: guesses.to_vec(),
+            ending,
+            seconds: 10,
+        }
+    }
+
+    #[test]
+    fn the_optimum_is_a_balanced_binary_search() {
+        assert_eq!(record((7, 7), 7, &[], Ending::Won).optimum(), 1.0);
+        // 1 + 2 * 2 + 4 * 3 + 1 * 4 attempts
+        assert_eq!(record((1, 8), 1, &[], Ending::Won).optimum(), 21.0 / 8.0);
+        // 63 numbers in 6 attempts, the other 37 in 7
+        assert_eq!(record((1, 100), 1, &[], Ending::Won).optimum(), 5.8);
+    }
+
+    #[test]
+    fn a_summary_counts_the_games_won_and_their_attempts() {
+        let mut records = vec![
+            record((1, 100), 12, &[50, 25, 12], Ending::Won),
+            record((1, 100), 50, &[50], Ending::Won),
+            record((1, 100), 70, &[50, 80], Ending::OutOfAttempts),
+            record((1, 100), 30, &[50, 30], Ending::Won),
+        ];
+        let summary = Summary::of(&records);
+        assert_eq!(summary.games, 4);
+        assert_eq!(summary.won, 3);
+        assert_eq!(summary.win_rate(), 0.75);
+        assert_eq!(summary.attempts, vec![1, 2, 3]);
+        assert_eq!(summary.mean_attempts(), Some(2.0));
+        assert_eq!(summary.median_attempts(), Some(2.0));
+        assert_eq!(summary.optimum, 5.8);
+        // 50 twice and 80 too big, 25 too small
+        assert_eq!((summary.overshoots, summary.undershoots), (4, 1));
+
+        // an even count: the mean of the two in the middle
+        records.push(record((1, 100), 9, &[50, 25, 12, 6, 9], Ending::Won));
+        let summary = Summary::of(&records);
+        assert_eq!(summary.attempts, vec![1, 2, 3, 5]);
+        assert_eq!(summary.median_attempts(), Some(2.5));
+        assert_eq!(summary.mean_attempts(), Some(2.75));
+
+        let empty = Summary::of(&[]);
+        assert_eq!(empty.win_rate(), 0.0);
+        assert_eq!(empty.mean_attempts(), None);
+        assert_eq!(empty.median_attempts(), None);
+    }
+
+    #[test]
+    fn a_distance_on_the_edge_of_a_bucket_is_in_it() {
+        // on 1..=100 a distance of n is n% of the range
+        let guesses = [2, 6, 7, 11, 12, 26, 27, 51, 52];
+        let summary = Summary::of(&[record((1, 100), 1, &guesses, Ending::GaveUp)]);
+        assert_eq!(summary.distances, [1, 1, 2, 2, 2, 1]);
+        assert_eq!((summary.overshoots, summary.undershoots), (9, 0));
+    }
+
+    #[test]
+    fn a_truncated_last_line_is_skipped() {
+        let path =
+            std::env::temp_dir().join(format!("guessing_game_stats_{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let won = record((1, 100), 50, &[50], Ending::Won);
+        GameLog::append(&path, &won).unwrap();
+        GameLog::append(&path, &won).unwrap();
+        let line = serde_json::to_string(&won).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{}", &line[..line.len() / 2]).unwrap();
+
+        let log = GameLog::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(log.records, vec![won.clone(), won]);
+        assert_eq!(log.skipped, 1);
+        assert_eq!(GameLog::load(&path).unwrap(), GameLog::default());
+    }
+}
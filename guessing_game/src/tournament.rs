@@ -0,0 +1,492 @@
+//! Tournaments between players of the guessing game, usually bots.
+//!
+//! Every registered [`Entrant`] plays the same seeded games on every
+//! [`Round`], so each of them meets the same secret numbers, and the games
+//! are spread over threads. A new strategy only has to implement
+//! [`Player`]; [`Tournament::register`] adds it next to the bots of
+//! [`Strategy::ALL`].
+
+use crate::bot::{Bot, Strategy};
+use crate::player::{self, Player};
+use crate::stats;
+use crate::{Game, Status};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// Builds the player of one game, for its range and with its own generator.
+pub type Factory = Box<dyn Fn(RangeInclusive<u32>, StdRng) -> Box<dyn Player> + Send + Sync>;
+
+/// Games handed to a thread at a time.
+const CHUNK: u32 = 64;
+
+/// A player taking part in a [`Tournament`].
+pub struct Entrant {
+    pub name: String,
+    factory: Factory,
+}
+
+/// A range played by every entrant, with its attempt limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub name: String,
+    pub range: RangeInclusive<u32>,
+    /// `None` means no limit.
+    pub max_attempts: Option<u32>,
+}
+
+/// The entrants, the rounds, and how many seeded games each entrant plays in
+/// each round.
+pub struct Tournament {
+    entrants: Vec<Entrant>,
+    rounds: Vec<Round>,
+    games: u32,
+    seed: u64,
+    threads: usize,
+}
+
+impl Tournament {
+    /// A tournament without entrants nor rounds; game `i` of every round
+    /// uses the seed `seed + i`.
+    pub fn new(seed: u64) -> Tournament {
+        Tournament {
+            entrants: Vec::new(),
+            rounds: Vec::new(),
+            games: 1000,
+            seed,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+
+    /// Adds a bot of each [`Strategy`].
+    pub fn with_bots(mut self) -> Tournament {
+        for strategy in Strategy::ALL.iter().copied() {
+            self = self.register(strategy.name(), move |range, rng| {
+                Box::new(Bot::new(strategy, range, rng))
+            });
+        }
+        self
+    }
+
+    /// Adds the player built by `factory`, called `name` in the results.
+    pub fn register<F>(mut self, name: &str, factory: F) -> Tournament
+    where
+        F: Fn(RangeInclusive<u32>, StdRng) -> Box<dyn Player> + Send + Sync + 'static,
+    {
+        self.entrants.push(Entrant {
+            name: name.to_string(),
+            factory: Box::new(factory),
+        });
+        self
+    }
+
+    pub fn with_round(mut self, round: Round) -> Tournament {
+        self.rounds.push(round);
+        self
+    }
+
+    /// The games of each entrant in each round, 1000 by default.
+    pub fn with_games(mut self, games: u32) -> Tournament {
+        self.games = games;
+        self
+    }
+
+    /// The threads playing the games, one per CPU by default.
+    pub fn with_threads(mut self, threads: usize) -> Tournament {
+        self.threads = threads.max(1);
+        self
+    }
+
+    pub fn entrants(&self) -> &[Entrant] {
+        &self.entrants
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Plays all the games; the results do not depend on the threads.
+    pub fn run(&self) -> Results {
+        // (entrant, round, first game) of each chunk of games
+        let mut jobs = Vec::new();
+        for entrant in 0..self.entrants.len() {
+            for round in 0..self.rounds.len() {
+                jobs.extend(
+                    (0..self.games)
+                        .step_by(CHUNK as usize)
+                        .map(|first| (entrant, round, first)),
+                );
+            }
+        }
+        let next = AtomicUsize::new(0);
+        let mut played: Vec<(usize, usize, u32, GameResult)> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..self.threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut played = Vec::new();
+                        while let Some(&(entrant, round, first)) =
+                            jobs.get(next.fetch_add(1, Ordering::Relaxed))
+                        {
+                            for i in first..(first + CHUNK).min(self.games) {
+                                played.push((entrant, round, i, self.play(entrant, round, i)));
+                            }
+                        }
+                        played
+                    })
+                })
+                .collect();
+            workers
+                .into_iter()
+                .flat_map(|worker| worker.join().expect("a tournament thread panicked"))
+                .collect()
+        });
+        played.sort_by_key(|&(entrant, round, i, _)| (entrant, round, i));
+        let games: Vec<GameResult> = played.into_iter().map(|(_, _, _, result)| result).collect();
+
+        let mut standings = Vec::new();
+        for round in &self.rounds {
+            for entrant in &self.entrants {
+                let results = games
+                    .iter()
+                    .filter(|game| game.bot == entrant.name && game.round == round.name);
+                standings.push(Standing::new(&entrant.name, round, results));
+            }
+        }
+        Results { games, standings }
+    }
+
+    /// Game `i` of `round`, played by `entrant`: the secret number only
+    /// depends on the seed, the same for all the entrants.
+    fn play(&self, entrant: usize, round: usize, i: u32) -> GameResult {
+        let (entrant, round) = (&self.entrants[entrant], &self.rounds[round]);
+        let seed = self.seed.wrapping_add(u64::from(i));
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut game = Game::new(round.range.clone(), &mut rng)
+            .with_max_attempts(round.max_attempts)
+            .with_seed(Some(seed));
+        let bot_rng = StdRng::from_rng(&mut rng).expect("StdRng cannot fail to seed from StdRng");
+        let mut player = (entrant.factory)(round.range.clone(), bot_rng);
+        player::play(&mut game, &mut player, &mut io::sink()).expect("the sink cannot fail");
+        GameResult {
+            bot: entrant.name.clone(),
+            round: round.name.clone(),
+            seed,
+            secret_number: game.secret_number(),
+            attempts: game.attempts(),
+            won: game.status() == Status::Won,
+        }
+    }
+}
+
+/// One game of a tournament.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct GameResult {
+    pub bot: String,
+    pub round: String,
+    pub seed: u64,
+    pub secret_number: u32,
+    pub attempts: u32,
+    pub won: bool,
+}
+
+/// How an entrant did in a round.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Standing {
+    pub bot: String,
+    pub round: String,
+    pub games: usize,
+    pub won: usize,
+    /// Of the games won.
+    pub mean_attempts: Option<f64>,
+    /// Like the one of [`stats::Summary`]: the mean of the two in the middle
+    /// when the count is even.
+    pub median_attempts: Option<f64>,
+    pub worst_attempts: Option<u32>,
+    /// The games won with each number of attempts, from 1.
+    pub distribution: BTreeMap<u32, usize>,
+}
+
+impl Standing {
+    fn new<'a, I: Iterator<Item = &'a GameResult>>(
+        bot: &str,
+        round: &Round,
+        results: I,
+    ) -> Standing {
+        let mut games = 0;
+        let mut attempts = Vec::new();
+        for result in results {
+            games += 1;
+            if result.won {
+                attempts.push(result.attempts);
+            }
+        }
+        attempts.sort_unstable();
+        let mut distribution = BTreeMap::new();
+        for &n in &attempts {
+            *distribution.entry(n).or_insert(0) += 1;
+        }
+        let total: u64 = attempts.iter().map(|&n| u64::from(n)).sum();
+        Standing {
+            bot: bot.to_string(),
+            round: round.name.clone(),
+            games,
+            won: attempts.len(),
+            mean_attempts: Some(total as f64 / attempts.len() as f64)
+                .filter(|_| !attempts.is_empty()),
+            median_attempts: stats::median(&attempts),
+            worst_attempts: attempts.last().copied(),
+            distribution,
+        }
+    }
+
+    /// The part of the games won, from 0 to 1.
+    pub fn win_rate(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        self.won as f64 / self.games as f64
+    }
+
+    /// The distribution of the attempts up to `max`, one bar of `▁` to `█`
+    /// for each number of attempts.
+    pub fn sparkline(&self, max: u32) -> String {
+        const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        let most = self.distribution.values().copied().max().unwrap_or(0);
+        (1..=max)
+            .map(|n| match self.distribution.get(&n) {
+                Some(&count) if most > 0 => BARS[count * (BARS.len() - 1) / most],
+                _ => ' ',
+            })
+            .collect()
+    }
+}
+
+/// Everything a [`Tournament`] played.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Results {
+    /// In order of entrant, round and seed.
+    pub games: Vec<GameResult>,
+    /// In order of round and entrant.
+    pub standings: Vec<Standing>,
+}
+
+impl Results {
+    /// The standings of `round`, the best first: more games won, then fewer
+    /// attempts on average.
+    pub fn ranking(&self, round: &str) -> Vec<&Standing> {
+        let mut ranking: Vec<&Standing> =
+            self.standings.iter().filter(|s| s.round == round).collect();
+        ranking.sort_by(|a, b| {
+            b.won.cmp(&a.won).then(
+                a.mean_attempts
+                    .unwrap_or(f64::MAX)
+                    .total_cmp(&b.mean_attempts.unwrap_or(f64::MAX)),
+            )
+        });
+        ranking
+    }
+
+    /// Prints the ranking of each round, with the distribution of the
+    /// attempts of the games won.
+    pub fn write_table<W: Write>(&self, rounds: &[Round], out: &mut W) -> io::Result<()> {
+        for round in rounds {
+            writeln!(
+                out,
+                "== {} ({}-{}) ==",
+                round.name,
+                round.range.start(),
+                round.range.end()
+            )?;
+            let ranking = self.ranking(&round.name);
+            // the distribution goes up to the limit, or to the worst game
+            let max = round
+                .max_attempts
+                .or_else(|| ranking.iter().filter_map(|s| s.worst_attempts).max())
+                .unwrap_or(0);
+            writeln!(
+                out,
+                "   # bot           won    mean  median  worst  attempts 1..{}",
+                max
+            )?;
+            let dash = || "-".to_string();
+            for (i, standing) in ranking.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{:>4} {:<12} {:>5.1}% {:>7} {:>7} {:>6}  {}",
+                    i + 1,
+                    standing.bot,
+                    standing.win_rate() * 100.0,
+                    standing
+                        .mean_attempts
+                        .map_or_else(dash, |mean| format!("{:.2}", mean)),
+                    standing
+                        .median_attempts
+                        .map_or_else(dash, |n| n.to_string()),
+                    standing.worst_attempts.map_or_else(dash, |n| n.to_string()),
+                    standing.sparkline(max)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes every game as a CSV line, after a header.
+    pub fn write_csv<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        writeln!(out, "bot,round,seed,secret_number,attempts,won")?;
+        for game in &self.games {
+            writeln!(
+                out,
+                "{},{},{},{},{},{}",
+                csv_field(&game.bot),
+                csv_field(&game.round),
+                game.seed,
+                game.secret_number,
+                game.attempts,
+                game.won
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Writes the standings and every game as JSON.
+    pub fn write_json<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut *out, self)?;
+        writeln!(out)
+    }
+}
+
+/// `field` quoted when it has a comma or a quote.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament(threads: usize) -> Tournament {
+        Tournament::new(7)
+            .with_bots()
+            .with_games(200)
+            .with_threads(threads)
+            .with_round(Round {
+                name: "small".to_string(),
+                range: 1..=100,
+                max_attempts: Some(7),
+            })
+            .with_round(Round {
+                name: "unlimited".to_string(),
+                range: 1..=1000,
+                max_attempts: None,
+            })
+    }
+
+    #[test]
+    fn the_results_do_not_depend_on_the_threads() {
+        let results = tournament(1).run();
+        assert_eq!(results.games.len(), 4 * 2 * 200);
+        assert_eq!(results, tournament(8).run());
+    }
+
+    fn standing(bot: &str, won: usize, mean_attempts: Option<f64>) -> Standing {
+        Standing {
+            bot: bot.to_string(),
+            round: "normal".to_string(),
+            games: 12,
+            won,
+            mean_attempts,
+            median_attempts: None,
+            worst_attempts: None,
+            distribution: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn the_median_of_an_even_count_is_between_the_middle_two() {
+        let round = Round {
+            name: "normal".to_string(),
+            range: 1..=100,
+            max_attempts: Some(7),
+        };
+        let result = |attempts, won| GameResult {
+            bot: "binary".to_string(),
+            round: round.name.clone(),
+            seed: 0,
+            secret_number: 1,
+            attempts,
+            won,
+        };
+        let results = [
+            result(4, true),
+            result(7, false),
+            result(6, true),
+            result(5, true),
+        ];
+        let odd = Standing::new("binary", &round, results.iter());
+        assert_eq!(odd.median_attempts, Some(5.0));
+        let even = Standing::new("binary", &round, results.iter().chain(&[result(7, true)]));
+        assert_eq!(even.median_attempts, Some(5.5));
+        assert_eq!(even.worst_attempts, Some(7));
+    }
+
+    #[test]
+    fn the_ranking_puts_more_wins_then_fewer_attempts_first() {
+        let results = Results {
+            games: Vec::new(),
+            standings: vec![
+                standing("slow", 10, Some(6.0)),
+                standing("none", 0, None),
+                standing("fast", 10, Some(5.0)),
+                standing("winner", 12, Some(7.0)),
+                Standing {
+                    round: "hard".to_string(),
+                    ..standing("elsewhere", 12, Some(1.0))
+                },
+            ],
+        };
+        let ranking: Vec<&str> = results
+            .ranking("normal")
+            .iter()
+            .map(|standing| standing.bot.as_str())
+            .collect();
+        assert_eq!(ranking, vec!["winner", "fast", "slow", "none"]);
+    }
+
+    #[test]
+    fn csv_fields_with_commas_or_quotes_are_quoted() {
+        let results = Tournament::new(1)
+            .register("the \"best\", really", |range, rng| {
+                Box::new(Bot::new(Strategy::BinarySearch, range, rng))
+            })
+            .with_games(1)
+            .with_round(Round {
+                name: "1,10".to_string(),
+                range: 1..=10,
+                max_attempts: Some(4),
+            })
+            .run();
+        let mut out = Vec::new();
+        results.write_csv(&mut out).unwrap();
+        let csv = String::from_utf8(out).unwrap();
+        let game = &results.games[0];
+        assert_eq!(
+            csv,
+            format!(
+                "bot,round,seed,secret_number,attempts,won\n\
+                 \"the \"\"best\"\", really\",\"1,10\",1,{},{},true\n",
+                game.secret_number, game.attempts
+            )
+        );
+        assert_eq!(csv_field("binary"), "binary");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+}
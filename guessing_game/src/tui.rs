@@ -0,0 +1,304 @@
+//! The full-screen interface of `--tui`, built with the `tui` feature.
+//!
+//! [`App`] holds what is on the screen besides the [`Game`]: the number being
+//! typed and the last message. It reacts to keys with [`App::handle_key`]
+//! and draws itself on any ratatui backend with [`App::draw`], so the tests
+//! drive it with a `TestBackend` instead of a terminal.
+
+use crate::i18n;
+use crate::{Game, Loss, Status};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+use std::io;
+use std::ops::RangeInclusive;
+use std::time::Duration;
+
+/// How often the screen is redrawn without keys, for the timer.
+const TICK: Duration = Duration::from_millis(250);
+
+/// The state of the screen while `game` is played.
+pub struct App<'a> {
+    game: &'a mut Game,
+    input: String,
+    message: String,
+    /// How the game ended, once it is finished.
+    end: Option<String>,
+    quit: bool,
+}
+
+impl<'a> App<'a> {
+    pub fn new(game: &'a mut Game) -> App<'a> {
+        App {
+            game,
+            input: String::new(),
+            message: i18n::text("tui.start").to_string(),
+            end: None,
+            quit: false,
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        self.game
+    }
+
+    /// The number being typed.
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// What the last key or guess did.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Whether the player left, with Esc or Ctrl-C, or with any key once the
+    /// game is finished.
+    pub fn is_done(&self) -> bool {
+        self.quit
+    }
+
+    /// Types, erases or plays the number. Only digits are typed, and only
+    /// while the number stays in the range of the game.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.kind != KeyEventKind::Press {
+            return;
+        }
+        let ctrl_c =
+            key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c');
+        if ctrl_c || key.code == KeyCode::Esc || self.game.is_finished() {
+            self.quit = true;
+            return;
+        }
+        match key.code {
+            KeyCode::Char(digit) if digit.is_ascii_digit() => self.type_digit(digit),
+            KeyCode::Char(other) => {
+                self.message = i18n::fill("tui.not-a-digit", &[("key", &other)]);
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+            }
+            KeyCode::Enter => self.submit(),
+            _ => {}
+        }
+    }
+
+    fn type_digit(&mut self, digit: char) {
+        let range = self.game.range().clone();
+        let typed = format!("{}{}", self.input, digit);
+        match typed.parse::<u32>() {
+            // a leading zero adds nothing to the number
+            _ if self.input == "0" => self.message = i18n::text("tui.leading-zero").to_string(),
+            Ok(number) if number <= *range.end() => self.input = typed,
+            _ => {
+                self.message = i18n::fill("tui.too-big", &[("typed", &typed), ("max", range.end())])
+            }
+        }
+    }
+
+    fn submit(&mut self) {
+        let guess: u32 = match self.input.parse() {
+            Ok(guess) => guess,
+            Err(_) => {
+                self.message = i18n::text("tui.empty").to_string();
+                return;
+            }
+        };
+        self.input.clear();
+        self.message = match self.game.guess(guess) {
+            Ok(_) => match self.game.feedback() {
+                Some(feedback) => format!("{}: {}", guess, feedback),
+                None => String::new(),
+            },
+            Err(err) => format!("{}: {}.", guess, err),
+        };
+        self.end_message();
+    }
+
+    /// Ends the game when its time is up, without waiting for a guess: the
+    /// timer on the screen has to stop at zero.
+    pub fn tick(&mut self) {
+        if !self.game.is_finished() && self.game.time_left() == Some(Duration::from_secs(0)) {
+            self.game.lose(Loss::OutOfTime);
+            self.input.clear();
+            self.end_message();
+        }
+    }
+
+    fn end_message(&mut self) {
+        let secret_number = self.game.secret_number();
+        let end = match self.game.status() {
+            Status::Playing => return,
+            Status::Won => i18n::plural("tui.won", u64::from(self.game.attempts()), &[]),
+            Status::Lost(Loss::OutOfTime) => {
+                i18n::fill("tui.time-up", &[("secret", &secret_number)])
+            }
+            Status::Lost(_) => i18n::fill("tui.no-attempts", &[("secret", &secret_number)]),
+        };
+        self.message = i18n::fill("tui.leave", &[("end", &end)]);
+        self.end = Some(end);
+    }
+
+    /// Draws the number line, the history, the attempts and the timer, and
+    /// the number being typed.
+    pub fn draw(&self, frame: &mut Frame) {
+        let [title, bar, middle, input, message] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(3),
+            Constraint::Min(4),
+            Constraint::Length(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [history, status] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(middle);
+
+        let range = self.game.range();
+        frame.render_widget(
+            Paragraph::new(i18n::fill(
+                "banner",
+                &[("min", range.start()), ("max", range.end())],
+            )),
+            title,
+        );
+        self.draw_bar(frame, bar);
+        self.draw_history(frame, history);
+        self.draw_status(frame, status);
+        self.draw_input(frame, input);
+        frame.render_widget(Paragraph::new(self.message.as_str()), message);
+    }
+
+    fn draw_bar(&self, frame: &mut Frame, area: Rect) {
+        let known = self.game.known_range();
+        let block = Block::default().borders(Borders::ALL).title(i18n::fill(
+            "tui.possible",
+            &[("min", known.start()), ("max", known.end())],
+        ));
+        let width = block.inner(area).width as usize;
+        let line = number_line(self.game.range(), &known, width);
+        frame.render_widget(
+            Paragraph::new(line)
+                .style(Style::default().fg(Color::Green))
+                .block(block),
+            area,
+        );
+    }
+
+    fn draw_history(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(i18n::text("tui.history"));
+        // the last guesses that fit
+        let rows = block.inner(area).height as usize;
+        let history = self.game.history();
+        let lines: Vec<Line> = (history.len().saturating_sub(rows)..history.len())
+            .map(|i| {
+                Line::from(format!(
+                    "{:>3}. {:>10}  {}",
+                    i + 1,
+                    history[i].guess,
+                    self.game.feedback_at(i)
+                ))
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_status(&self, frame: &mut Frame, area: Rect) {
+        let count = self.game.attempts();
+        let attempts = match self.game.max_attempts() {
+            Some(max) => i18n::fill("tui.attempts-of", &[("count", &count), ("max", &max)]),
+            None => i18n::fill("stats.attempts", &[("count", &count)]),
+        };
+        let time = match self.game.time_left() {
+            Some(left) => i18n::fill("tui.time-left", &[("clock", &clock(left))]),
+            None => i18n::fill("tui.time", &[("clock", &clock(self.game.elapsed()))]),
+        };
+        let mut lines = vec![Line::from(attempts), Line::from(time)];
+        if let Some(left) = self.game.attempts_left() {
+            lines.push(Line::from(i18n::fill(
+                "tui.attempts-left",
+                &[("count", &left)],
+            )));
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(i18n::text("tui.status"));
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+
+    fn draw_input(&self, frame: &mut Frame, area: Rect) {
+        let known = self.game.known_range();
+        // a number the answers already ruled out is allowed, but shown in yellow
+        let color = match self.input.parse::<u32>() {
+            Ok(number) if !known.contains(&number) => Color::Yellow,
+            _ => Color::Reset,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(i18n::text("tui.your-guess"));
+        frame.render_widget(
+            Paragraph::new(format!("> {}", self.input))
+                .style(Style::default().fg(color))
+                .block(block),
+            area,
+        );
+    }
+}
+
+/// The range drawn in `width` cells: full where the numbers of the cell can
+/// still be the secret number, light elsewhere.
+pub fn number_line(
+    range: &RangeInclusive<u32>,
+    known: &RangeInclusive<u32>,
+    width: usize,
+) -> String {
+    let start = u64::from(*range.start());
+    let size = u64::from(*range.end()) - start + 1;
+    let width = width as u64;
+    (0..width)
+        .map(|i| {
+            let low = start + i * size / width;
+            let high = (start + (i + 1) * size / width).saturating_sub(1).max(low);
+            if low <= u64::from(*known.end()) && high >= u64::from(*known.start()) {
+                '█'
+            } else {
+                '░'
+            }
+        })
+        .collect()
+}
+
+/// A duration as minutes and seconds, like `1:05`.
+fn clock(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Plays `game` full-screen until it is finished and a key is pressed, or
+/// the player leaves; the terminal is restored in any case.
+pub fn run(game: &mut Game) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let mut app = App::new(game);
+    let result = (|| {
+        while !app.is_done() {
+            terminal.draw(|frame| app.draw(frame))?;
+            if event::poll(TICK)? {
+                if let Event::Key(key) = event::read()? {
+                    app.handle_key(key);
+                }
+            }
+            app.tick();
+        }
+        Ok(())
+    })();
+    ratatui::restore();
+    if let Some(end) = &app.end {
+        println!("{}", end);
+    }
+    result
+}
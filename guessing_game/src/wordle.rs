@@ -0,0 +1,554 @@
+//! Wordle: the secret is a word of a list, and every guess tells which of
+//! its letters are in the right place, in another place or not in the word.
+//!
+//! The words come from a file, one per line, see [`WordList::load`]. A
+//! [`WordGame`] keeps the state like [`Game`](crate::Game) does, and
+//! [`play`] is the stdin loop of the number game with words.
+
+use crate::i18n;
+use crate::player;
+use crate::{Loss, Status};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+/// Guesses allowed when nothing else is chosen.
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 6;
+
+/// The words a game can choose from, and accepts as guesses: all of the same
+/// length, lowercase.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordList {
+    words: Vec<String>,
+    known: HashSet<String>,
+}
+
+impl WordList {
+    /// Reads the words of `path`, one per line; empty lines and lines
+    /// starting with `#` are skipped.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<WordList> {
+        let text = fs::read_to_string(path)?;
+        WordList::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// The words of `text`, one per line, like in a file.
+    pub fn parse(text: &str) -> Result<WordList, String> {
+        let mut words: Vec<String> = Vec::new();
+        for line in text.lines() {
+            let word = line.trim().to_lowercase();
+            if word.is_empty() || word.starts_with('#') {
+                continue;
+            }
+            if !word.chars().all(char::is_alphabetic) {
+                return Err(format!("'{}' is not a word", word));
+            }
+            if let Some(first) = words.first() {
+                if first.chars().count() != word.chars().count() {
+                    return Err(format!(
+                        "'{}' and '{}' are not of the same length",
+                        first, word
+                    ));
+                }
+            }
+            words.push(word);
+        }
+        if words.is_empty() {
+            return Err("there are no words".to_string());
+        }
+        words.sort();
+        words.dedup();
+        let known = words.iter().cloned().collect();
+        Ok(WordList { words, known })
+    }
+
+    /// The letters of every word.
+    pub fn length(&self) -> usize {
+        self.words[0].chars().count()
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.known.contains(word)
+    }
+
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// A word picked with `rng`.
+    pub fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> &str {
+        &self.words[rng.gen_range(0, self.words.len())]
+    }
+
+    /// The word of the daily puzzle of `date` (`YYYY-MM-DD`): the same for
+    /// everybody with the same list.
+    pub fn daily(&self, date: &str) -> &str {
+        self.random(&mut StdRng::seed_from_u64(daily_seed(date)))
+    }
+}
+
+/// The seed of the daily puzzle of `date`: its digits, like `20261018`.
+pub fn daily_seed(date: &str) -> u64 {
+    date.chars()
+        .filter_map(|c| c.to_digit(10))
+        .fold(0, |seed, digit| seed * 10 + u64::from(digit))
+}
+
+/// What a guess tells about one of its letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mark {
+    /// The letter is in this place of the secret word.
+    Correct,
+    /// The letter is in another place of the secret word.
+    Present,
+    /// The letter is not in the secret word, or not as many times.
+    Absent,
+}
+
+impl Mark {
+    /// The marks of `guess` against `secret`, two words of the same length.
+    ///
+    /// A letter repeated in the guess is present only as many times as it is
+    /// in the secret word, not counting the places where it is correct: with
+    /// the secret `crane`, only the last `e` of `eerie` is marked, correct.
+    pub fn of(secret: &str, guess: &str) -> Vec<Mark> {
+        let secret: Vec<char> = secret.chars().collect();
+        let guess: Vec<char> = guess.chars().collect();
+        let mut marks = vec![Mark::Absent; guess.len()];
+        // the letters of the secret word not matched by a correct letter
+        let mut left = Vec::new();
+        for (i, (&s, &g)) in secret.iter().zip(&guess).enumerate() {
+            if s == g {
+                marks[i] = Mark::Correct;
+            } else {
+                left.push(s);
+            }
+        }
+        for (i, letter) in guess.iter().enumerate() {
+            if marks[i] == Mark::Correct {
+                continue;
+            }
+            if let Some(j) = left.iter().position(|s| s == letter) {
+                left.swap_remove(j);
+                marks[i] = Mark::Present;
+            }
+        }
+        marks
+    }
+
+    /// How the mark is drawn below the letters.
+    pub fn symbol(self) -> char {
+        match self {
+            Mark::Correct => '=',
+            Mark::Present => '+',
+            Mark::Absent => '.',
+        }
+    }
+}
+
+/// A guess already played, with the marks of its letters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordAttempt {
+    pub guess: String,
+    pub marks: Vec<Mark>,
+}
+
+impl fmt::Display for WordAttempt {
+    /// The letters, uppercase, then their marks: `C R A N E  = . + . .`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let letters: Vec<String> = self
+            .guess
+            .to_uppercase()
+            .chars()
+            .map(String::from)
+            .collect();
+        let marks: Vec<String> = self.marks.iter().map(|m| m.symbol().to_string()).collect();
+        write!(f, "{}  {}", letters.join(" "), marks.join(" "))
+    }
+}
+
+/// A guess that the game refused: it does not count as an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    Length {
+        expected: usize,
+        found: usize,
+    },
+    /// Not in the word list.
+    Unknown(String),
+    /// Hard mode: a letter found correct is not kept in its place.
+    Moved {
+        letter: char,
+        place: usize,
+    },
+    /// Hard mode: a letter found in the word is missing.
+    Missing(char),
+    Finished,
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordError::Length { expected, found } => f.write_str(&i18n::plural(
+                "wordle.length",
+                *expected as u64,
+                &[("found", found)],
+            )),
+            WordError::Unknown(word) => {
+                f.write_str(&i18n::fill("wordle.unknown", &[("word", word)]))
+            }
+            WordError::Moved { letter, place } => f.write_str(&i18n::fill(
+                "wordle.moved",
+                &[("place", place), ("letter", &letter.to_uppercase())],
+            )),
+            WordError::Missing(letter) => f.write_str(&i18n::fill(
+                "wordle.missing",
+                &[("letter", &letter.to_uppercase())],
+            )),
+            WordError::Finished => f.write_str(i18n::text("finished")),
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
+
+/// One round of Wordle.
+#[derive(Debug, Clone)]
+pub struct WordGame {
+    words: WordList,
+    secret: String,
+    hard: bool,
+    max_attempts: Option<u32>,
+    history: Vec<WordAttempt>,
+    status: Status,
+}
+
+impl WordGame {
+    /// Starts a new round, picking the secret word of `words` with `rng`.
+    pub fn new<R: Rng + ?Sized>(words: WordList, rng: &mut R) -> WordGame {
+        let secret = words.random(rng).to_string();
+        WordGame::with_secret(words, secret)
+    }
+
+    /// Starts the daily puzzle of `date`, see [`WordList::daily`].
+    pub fn daily(words: WordList, date: &str) -> WordGame {
+        let secret = words.daily(date).to_string();
+        WordGame::with_secret(words, secret)
+    }
+
+    /// Starts a new round with a known secret word.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `secret` is not in `words`.
+    pub fn with_secret(words: WordList, secret: String) -> WordGame {
+        assert!(
+            words.contains(&secret),
+            "the secret word is not in the word list"
+        );
+        WordGame {
+            words,
+            secret,
+            hard: false,
+            max_attempts: Some(DEFAULT_MAX_ATTEMPTS),
+            history: Vec::new(),
+            status: Status::Playing,
+        }
+    }
+
+    /// In hard mode the letters found must be used in the next guesses: the
+    /// correct ones in their place, the present ones anywhere.
+    pub fn with_hard_mode(mut self, hard: bool) -> WordGame {
+        self.hard = hard;
+        self
+    }
+
+    /// Limits the number of attempts, `None` means no limit.
+    pub fn with_max_attempts(mut self, max_attempts: Option<u32>) -> WordGame {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Marks `guess`, which must be a word of the list, and records the
+    /// attempt.
+    pub fn guess(&mut self, guess: &str) -> Result<&WordAttempt, WordError> {
+        if self.is_finished() {
+            return Err(WordError::Finished);
+        }
+        let guess = guess.to_lowercase();
+        let length = guess.chars().count();
+        if length != self.words.length() {
+            return Err(WordError::Length {
+                expected: self.words.length(),
+                found: length,
+            });
+        }
+        if !self.words.contains(&guess) {
+            return Err(WordError::Unknown(guess));
+        }
+        if self.hard {
+            self.check_hard(&guess)?;
+        }
+
+        let marks = Mark::of(&self.secret, &guess);
+        let won = marks.iter().all(|&mark| mark == Mark::Correct);
+        self.history.push(WordAttempt { guess, marks });
+        if won {
+            self.status = Status::Won;
+        } else if self.attempts_left() == Some(0) {
+            self.status = Status::Lost(Loss::OutOfAttempts);
+        }
+        Ok(self.history.last().expect("an attempt was just pushed"))
+    }
+
+    /// Whether `guess` uses what the last attempt found, as hard mode wants.
+    fn check_hard(&self, guess: &str) -> Result<(), WordError> {
+        let last = match self.history.last() {
+            Some(last) => last,
+            None => return Ok(()),
+        };
+        let guess: Vec<char> = guess.chars().collect();
+        let mut left = guess.clone();
+        for (i, (letter, mark)) in last.guess.chars().zip(&last.marks).enumerate() {
+            if *mark == Mark::Correct {
+                if guess[i] != letter {
+                    return Err(WordError::Moved {
+                        letter,
+                        place: i + 1,
+                    });
+                }
+                left[i] = '\0';
+            }
+        }
+        // each present letter needs its own place in the guess
+        for (letter, mark) in last.guess.chars().zip(&last.marks) {
+            if *mark == Mark::Present {
+                match left.iter().position(|&c| c == letter) {
+                    Some(j) => left[j] = '\0',
+                    None => return Err(WordError::Missing(letter)),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ends the game as lost, unless it is already finished.
+    pub fn give_up(&mut self) {
+        if !self.is_finished() {
+            self.status = Status::Lost(Loss::GaveUp);
+        }
+    }
+
+    pub fn words(&self) -> &WordList {
+        &self.words
+    }
+
+    /// The secret word: only meant to be shown once the game is over.
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.hard
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn history(&self) -> &[WordAttempt] {
+        &self.history
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.status != Status::Playing
+    }
+}
+
+/// Plays `game` with the guesses read from `input`, one per line, until it
+/// is finished or the input ends; `/history`, `/giveup` and `/quit` work
+/// like in the number game.
+pub fn play<R, W>(game: &mut WordGame, input: &mut R, out: &mut W) -> io::Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    while !game.is_finished() {
+        writeln!(out, "{}", i18n::text("prompt"))?;
+
+        let mut guess = String::new();
+        // 0 bytes read is the end of the input: the player stops
+        match player::read_text_line(input, &mut guess) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(err) => {
+                writeln!(
+                    out,
+                    "{}",
+                    i18n::fill("input.unreadable", &[("error", &err)])
+                )?;
+                break;
+            }
+        }
+
+        // trim drops the newline of read_line, like for the numbers
+        match guess.trim() {
+            "" => continue,
+            "/history" => {
+                write_history(game.history(), out)?;
+                continue;
+            }
+            "/giveup" => {
+                game.give_up();
+                break;
+            }
+            "/quit" => break,
+            command if command.starts_with('/') => {
+                writeln!(out, "{}", i18n::text("play.commands-list"))?;
+                continue;
+            }
+            _ => {}
+        }
+        match game.guess(guess.trim()) {
+            Ok(attempt) => writeln!(out, "{}", attempt)?,
+            Err(err) => {
+                writeln!(out, "{}", i18n::fill("wordle.invalid", &[("error", &err)]))?;
+                continue;
+            }
+        }
+        if game.status() == Status::Won {
+            writeln!(out, "{}", i18n::text("feedback.win"))?;
+        }
+    }
+
+    match game.status() {
+        Status::Lost(Loss::GaveUp) => writeln!(out, "{}", i18n::text("loss.gave-up"))?,
+        Status::Lost(_) => writeln!(out, "{}", i18n::text("loss.attempts"))?,
+        _ => return Ok(()),
+    }
+    let word = game.secret().to_uppercase();
+    writeln!(out, "{}", i18n::fill("wordle.secret", &[("word", &word)]))
+}
+
+/// Writes the guesses so far with their marks.
+pub fn write_history<W: Write>(history: &[WordAttempt], out: &mut W) -> io::Result<()> {
+    if history.is_empty() {
+        return writeln!(out, "{}", i18n::text("history.empty"));
+    }
+    for (i, attempt) in history.iter().enumerate() {
+        writeln!(out, "{:>4}. {}", i + 1, attempt)?;
+    }
+    Ok(())
+}
+
+/// The result of a game without its letters, to share the daily puzzle
+/// without giving the word away.
+pub fn summary(game: &WordGame) -> String {
+    let attempts = match game.status() {
+        Status::Won => game.attempts().to_string(),
+        _ => "X".to_string(),
+    };
+    let mut summary = match game.max_attempts() {
+        Some(max) => format!("{}/{}", attempts, max),
+        None => attempts,
+    };
+    if game.is_hard() {
+        summary.push('*');
+    }
+    for attempt in game.history() {
+        summary.push('\n');
+        summary.extend(attempt.marks.iter().map(|mark| mark.symbol()));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Mark::{Absent, Correct, Present};
+
+    fn words() -> WordList {
+        WordList::parse("crane\ntrace\nreact\nbrave\ngrace").unwrap()
+    }
+
+    #[test]
+    fn a_repeated_letter_is_marked_as_many_times_as_in_the_secret_word() {
+        assert_eq!(
+            Mark::of("crane", "eerie"),
+            vec![Absent, Absent, Present, Absent, Correct]
+        );
+        assert_eq!(
+            Mark::of("abide", "speed"),
+            vec![Absent, Absent, Present, Absent, Present]
+        );
+        assert_eq!(
+            Mark::of("speed", "abide"),
+            vec![Absent, Absent, Absent, Present, Present]
+        );
+        // the correct e takes the only e of the secret word first
+        assert_eq!(
+            Mark::of("abide", "eerie"),
+            vec![Absent, Absent, Absent, Present, Correct]
+        );
+    }
+
+    #[test]
+    fn hard_mode_keeps_what_was_found() {
+        let mut game = WordGame::with_secret(words(), "crane".to_string()).with_hard_mode(true);
+        let marks = game.guess("trace").unwrap().marks.clone();
+        assert_eq!(marks, vec![Absent, Correct, Correct, Present, Correct]);
+        assert_eq!(
+            game.guess("react").unwrap_err(),
+            WordError::Moved {
+                letter: 'r',
+                place: 2
+            }
+        );
+        assert_eq!(game.guess("brave").unwrap_err(), WordError::Missing('c'));
+        // refused guesses do not count
+        assert_eq!(game.attempts(), 1);
+        assert!(game.guess("grace").is_ok());
+        assert!(game.guess("crane").is_ok());
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn the_daily_puzzle_is_the_same_for_the_same_date() {
+        assert_eq!(daily_seed("2026-10-18"), 20261018);
+        let words = words();
+        assert_eq!(words.daily("2026-10-18"), words.daily("2026-10-18"));
+        let game = WordGame::daily(words.clone(), "2026-10-18");
+        assert_eq!(game.secret(), words.daily("2026-10-18"));
+    }
+
+    #[test]
+    fn a_line_that_is_not_utf8_is_only_a_wrong_guess() {
+        let mut game = WordGame::with_secret(words(), "crane".to_string());
+        let mut out = Vec::new();
+        play(&mut game, &mut &b"cr\xffne\ncrane\n"[..], &mut out).unwrap();
+        assert_eq!(game.status(), Status::Won);
+        assert_eq!(game.attempts(), 1);
+        let played = String::from_utf8(out).unwrap();
+        assert!(played.contains("is not in the word list"), "{}", played);
+    }
+}
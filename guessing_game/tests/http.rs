@@ -0,0 +1,241 @@
+//! The JSON API, through a server listening on an ephemeral local port.
+
+use guessing_game::http::HttpServer;
+use guessing_game::Game;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::{json, Value};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+use std::time::Duration;
+
+fn start_server() -> SocketAddr {
+    serve(HttpServer::bind("127.0.0.1:0", StdRng::seed_from_u64(1)).unwrap())
+}
+
+fn serve(server: HttpServer) -> SocketAddr {
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    addr
+}
+
+/// Sends one request and returns the status, the headers and the body.
+fn send(addr: SocketAddr, method: &str, path: &str, body: Option<&str>) -> (u16, String, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    let body = body.unwrap_or("");
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_at(response.find("\r\n\r\n").unwrap());
+    let status = head.split_whitespace().nth(1).unwrap().parse().unwrap();
+    let body = match body.trim() {
+        "" => Value::Null,
+        body => serde_json::from_str(body).unwrap(),
+    };
+    (status, head.to_string(), body)
+}
+
+fn create(addr: SocketAddr, body: Value) -> Value {
+    let (status, head, game) = send(addr, "POST", "/games", Some(&body.to_string()));
+    assert_eq!(status, 201, "{}", game);
+    assert!(head.contains(&format!("Location: /games/{}", game["id"])));
+    game
+}
+
+fn guess(addr: SocketAddr, id: &Value, guess: u32) -> (u16, Value) {
+    let body = json!({ "guess": guess }).to_string();
+    let (status, _, answer) = send(addr, "POST", &format!("/games/{}/guesses", id), Some(&body));
+    (status, answer)
+}
+
+#[test]
+fn binary_search_wins_and_the_history_is_kept() {
+    let addr = start_server();
+    let game = create(addr, json!({ "min": 1, "max": 100, "seed": 42 }));
+    assert_eq!(game["min"], 1);
+    assert_eq!(game["max"], 100);
+    assert_eq!(game["max_attempts"], 7);
+    assert_eq!(game["status"], "playing");
+    assert_eq!(game["secret_number"], Value::Null);
+
+    let (mut low, mut high) = (1, 100);
+    let mut attempts = 0;
+    loop {
+        let middle = (low + high) / 2;
+        let (status, answer) = guess(addr, &game["id"], middle);
+        assert_eq!(status, 200, "{}", answer);
+        attempts += 1;
+        assert_eq!(answer["attempts"], attempts);
+        match answer["result"].as_str().unwrap() {
+            "less" => low = middle + 1,
+            "greater" => high = middle - 1,
+            "equal" => {
+                assert_eq!(answer["status"], "won");
+                break;
+            }
+            result => panic!("unexpected result {}", result),
+        }
+    }
+
+    let (status, _, state) = send(addr, "GET", &format!("/games/{}", game["id"]), None);
+    assert_eq!(status, 200);
+    assert_eq!(state["status"], "won");
+    assert_eq!(state["attempts"], attempts);
+    assert_eq!(
+        state["history"].as_array().unwrap().len(),
+        attempts as usize
+    );
+    assert_eq!(state["history"][attempts as usize - 1]["result"], "equal");
+    assert_eq!(state["secret_number"], (low + high) / 2);
+}
+
+#[test]
+fn a_seed_picks_the_same_secret_number_as_the_command_line() {
+    let addr = start_server();
+    let game = create(
+        addr,
+        json!({ "min": 1, "max": 1000, "seed": 7, "max_attempts": 1 }),
+    );
+    let expected = Game::new(1..=1000, &mut StdRng::seed_from_u64(7)).secret_number();
+
+    let wrong = if expected == 1 { 2 } else { 1 };
+    let (status, answer) = guess(addr, &game["id"], wrong);
+    assert_eq!(status, 200);
+    assert_eq!(answer["status"], "lost");
+    assert_eq!(answer["attempts_left"], 0);
+
+    let (_, _, state) = send(addr, "GET", &format!("/games/{}", game["id"]), None);
+    assert_eq!(state["seed"], 7);
+    assert_eq!(state["loss"], "attempts");
+    assert_eq!(state["secret_number"], expected);
+
+    let (status, answer) = guess(addr, &game["id"], expected);
+    assert_eq!(status, 409);
+    assert_eq!(answer["error"], "the game is already finished");
+}
+
+#[test]
+fn presets_and_limits_follow_the_command_line() {
+    let addr = start_server();
+    let game = create(addr, json!({ "difficulty": "easy" }));
+    assert_eq!(
+        (game["min"].clone(), game["max"].clone()),
+        (json!(1), json!(10))
+    );
+    assert_eq!(game["max_attempts"], 6);
+
+    let game = create(addr, json!({ "max_attempts": 0, "time_limit": 30 }));
+    assert_eq!(game["max_attempts"], Value::Null);
+    assert_eq!(game["attempts_left"], Value::Null);
+    assert_eq!(game["time_limit"], 30);
+    assert!(game["seed"].is_u64());
+
+    let game = create(addr, json!({}));
+    assert_eq!(game["max"], 100);
+}
+
+#[test]
+fn refused_requests() {
+    let addr = start_server();
+    let (status, _, body) = send(addr, "POST", "/games", Some(r#"{"min": 10, "max": 1}"#));
+    assert_eq!(status, 422);
+    assert!(
+        body["error"].as_str().unwrap().contains("bigger"),
+        "{}",
+        body
+    );
+    let (status, _, _) = send(
+        addr,
+        "POST",
+        "/games",
+        Some(r#"{"difficulty": "nightmare"}"#),
+    );
+    assert_eq!(status, 422);
+    let (status, _, _) = send(addr, "POST", "/games", Some(r#"{"colour": "red"}"#));
+    assert_eq!(status, 400);
+    let (status, _, _) = send(addr, "POST", "/games", Some("{"));
+    assert_eq!(status, 400);
+
+    let game = create(addr, json!({ "min": 1, "max": 10 }));
+    let (status, answer) = guess(addr, &game["id"], 11);
+    assert_eq!(status, 422);
+    assert!(answer["error"].as_str().unwrap().contains("out of range"));
+    let path = format!("/games/{}/guesses", game["id"]);
+    let (status, _, _) = send(addr, "POST", &path, Some(r#"{"guess": -1}"#));
+    assert_eq!(status, 400);
+    let (_, _, state) = send(addr, "GET", &format!("/games/{}", game["id"]), None);
+    assert_eq!(state["attempts"], 0);
+
+    let (status, _, _) = send(addr, "GET", "/games/999", None);
+    assert_eq!(status, 404);
+    let (status, _, _) = send(addr, "GET", "/games/abc", None);
+    assert_eq!(status, 404);
+    let (status, _, _) = send(addr, "GET", "/players", None);
+    assert_eq!(status, 404);
+    let (status, _, _) = send(addr, "PUT", "/games", None);
+    assert_eq!(status, 405);
+
+    let mut stream = TcpStream::connect(addr).unwrap();
+    stream.write_all(b"hello\r\n\r\n").unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    assert!(response.starts_with("HTTP/1.1 400 "), "{}", response);
+}
+
+#[test]
+fn deleted_games_are_gone() {
+    let addr = start_server();
+    let game = create(addr, json!({}));
+    let path = format!("/games/{}", game["id"]);
+
+    let (status, _, body) = send(addr, "DELETE", &path, None);
+    assert_eq!(status, 204);
+    assert_eq!(body, Value::Null);
+    let (status, _, _) = send(addr, "GET", &path, None);
+    assert_eq!(status, 404);
+    let (status, _, _) = send(addr, "DELETE", &path, None);
+    assert_eq!(status, 404);
+    let (status, _) = guess(addr, &game["id"], 50);
+    assert_eq!(status, 404);
+}
+
+#[test]
+fn idle_games_are_forgotten() {
+    let server = HttpServer::bind("127.0.0.1:0", StdRng::seed_from_u64(1)).unwrap();
+    let addr = serve(server.with_game_ttl(Duration::from_millis(300)));
+    let idle = create(addr, json!({}));
+    let played = create(addr, json!({}));
+    for _ in 0..4 {
+        thread::sleep(Duration::from_millis(100));
+        let (status, _) = guess(addr, &played["id"], 1);
+        assert_eq!(status, 200);
+    }
+    let (status, _, _) = send(addr, "GET", &format!("/games/{}", idle["id"]), None);
+    assert_eq!(status, 404);
+    let (status, _, _) = send(addr, "GET", &format!("/games/{}", played["id"]), None);
+    assert_eq!(status, 200);
+}
+
+#[test]
+fn too_many_games_are_refused() {
+    let server = HttpServer::bind("127.0.0.1:0", StdRng::seed_from_u64(1)).unwrap();
+    let addr = serve(server.with_max_games(2));
+    let first = create(addr, json!({}));
+    create(addr, json!({}));
+    let (status, _, body) = send(addr, "POST", "/games", Some("{}"));
+    assert_eq!(status, 503, "{}", body);
+    assert!(body["error"].is_string());
+
+    send(addr, "DELETE", &format!("/games/{}", first["id"]), None);
+    create(addr, json!({}));
+}
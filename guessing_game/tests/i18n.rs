@@ -0,0 +1,415 @@
+//! The catalogs of the messages: every language has every key, with the same
+//! placeholders, and the games, the reports and the errors speak the
+//! language that is set.
+
+use guessing_game::cli::{ArgError, Options};
+use guessing_game::client::{self, Client};
+use guessing_game::hint::Parity;
+use guessing_game::i18n::{self, Lang};
+use guessing_game::mastermind::{self, Code, CodeAttempt, CodeBreaker, CodeGame, CodeRules};
+use guessing_game::player::ScriptedPlayer;
+use guessing_game::replay::{Difference, Ending, Replay};
+use guessing_game::save;
+use guessing_game::score::{HighScores, ScoreEntry};
+use guessing_game::server::{Server, ServerConfig};
+use guessing_game::stats::{self, GameRecord};
+use guessing_game::wordle::{self, WordGame, WordList};
+use guessing_game::Game;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::BTreeSet;
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::net::TcpListener;
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+use std::time::Duration;
+
+// the language is one for the whole process, and the tests run in parallel
+static LANG_LOCK: Mutex<()> = Mutex::new(());
+
+/// Italian until dropped, then English again.
+struct Italian(#[allow(dead_code)] MutexGuard<'static, ()>);
+
+impl Italian {
+    fn set() -> Italian {
+        let lock = LANG_LOCK
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        i18n::set_lang(Lang::Italian);
+        Italian(lock)
+    }
+}
+
+impl Drop for Italian {
+    fn drop(&mut self) {
+        i18n::set_lang(Lang::English);
+    }
+}
+
+/// A game won at the first attempt.
+fn won_game() -> Game {
+    let mut game = Game::with_secret(1..=100, 42);
+    game.guess(42).unwrap();
+    game
+}
+
+fn keys(lang: Lang) -> BTreeSet<&'static str> {
+    lang.catalog().iter().map(|&(key, _)| key).collect()
+}
+
+/// The `{name}`s of a message.
+fn placeholders(message: &str) -> BTreeSet<&str> {
+    message
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split('}').next())
+        .collect()
+}
+
+#[test]
+fn every_language_has_every_key_once() {
+    let english = keys(Lang::English);
+    assert_eq!(
+        english.len(),
+        Lang::English.catalog().len(),
+        "a key is repeated"
+    );
+    for lang in Lang::ALL.iter().copied() {
+        assert_eq!(
+            keys(lang).len(),
+            lang.catalog().len(),
+            "a key of {} is repeated",
+            lang
+        );
+        let translated = keys(lang);
+        let missing: Vec<_> = english.difference(&translated).collect();
+        let unknown: Vec<_> = translated.difference(&english).collect();
+        assert!(missing.is_empty(), "{} misses {:?}", lang, missing);
+        assert!(
+            unknown.is_empty(),
+            "{} has unknown keys {:?}",
+            lang,
+            unknown
+        );
+    }
+}
+
+#[test]
+fn translations_keep_the_placeholders() {
+    for lang in Lang::ALL.iter().copied() {
+        for &(key, message) in lang.catalog() {
+            let english = Lang::English
+                .catalog()
+                .iter()
+                .find(|&&(k, _)| k == key)
+                .map_or("", |&(_, message)| message);
+            assert_eq!(
+                placeholders(message),
+                placeholders(english),
+                "{} of {}",
+                key,
+                lang
+            );
+        }
+    }
+}
+
+#[test]
+fn plural_messages_have_both_forms() {
+    let english = keys(Lang::English);
+    for key in &english {
+        if let Some(base) = key.strip_suffix(".one") {
+            assert!(
+                english.contains(format!("{}.other", base).as_str()),
+                "{}",
+                key
+            );
+        }
+    }
+}
+
+#[test]
+fn the_locale_picks_the_language() {
+    assert_eq!(Lang::from_locale("it_IT.UTF-8"), Some(Lang::Italian));
+    assert_eq!(Lang::from_locale("en_GB"), Some(Lang::English));
+    assert_eq!(Lang::from_locale("C"), None);
+    assert_eq!(Lang::from_locale("fr_FR.UTF-8"), None);
+    assert_eq!("IT".parse(), Ok(Lang::Italian));
+    assert!("klingon".parse::<Lang>().is_err());
+}
+
+#[test]
+fn messages_follow_the_language() {
+    let italian = Italian::set();
+    assert_eq!(i18n::plural("attempts-left", 1, &[]), "Hai 1 tentativo.");
+    assert_eq!(i18n::plural("attempts-left", 7, &[]), "Hai 7 tentativi.");
+    assert_eq!(
+        i18n::fill("banner", &[("min", &1), ("max", &100)]),
+        "Indovina il numero tra 1 e 100!"
+    );
+    drop(italian);
+    assert_eq!(i18n::plural("attempts-left", 1, &[]), "You have 1 attempt.");
+    assert_eq!(
+        i18n::plural("attempts-left", 0, &[]),
+        "You have 0 attempts."
+    );
+    // a key missing from the catalogs shows itself
+    assert_eq!(i18n::text("no such key"), "no such key");
+}
+
+#[test]
+fn argument_errors_follow_the_language() {
+    let parse = |args: &[&str]| Options::parse(args.iter().map(|arg| arg.to_string()), None);
+    let _italian = Italian::set();
+    assert_eq!(
+        parse(&["--frobnicate"]).unwrap_err().to_string(),
+        "argomento sconosciuto '--frobnicate'"
+    );
+    assert_eq!(
+        parse(&["--min", "9", "--max", "1"])
+            .unwrap_err()
+            .to_string(),
+        "--min 9 è più grande di --max 1"
+    );
+    assert_eq!(
+        ArgError::TooManyCodes(100_000_000).to_string(),
+        "--solver gioca con al massimo 200000 codici, ma ce ne sono 100000000"
+    );
+}
+
+#[test]
+fn a_hint_before_the_secret_number_is_chosen_follows_the_language() {
+    let mut game = Game::evil(1..=100);
+    let _italian = Italian::set();
+    let err = game.use_hint(&Parity).unwrap_err().to_string();
+    assert!(
+        err.contains("il numero segreto non è ancora stato scelto"),
+        "{}",
+        err
+    );
+}
+
+#[test]
+fn the_reports_follow_the_language() {
+    let game = won_game();
+    let record = GameRecord::new("alice", &game, "normal").unwrap();
+    let mut scores =
+        HighScores::load(env::temp_dir().join("guessing_game_i18n_none.json")).unwrap();
+    let _italian = Italian::set();
+
+    let mut out = Vec::new();
+    stats::write_report(&[], None, &mut out).unwrap();
+    stats::write_report(&[record], None, &mut out).unwrap();
+    let report = String::from_utf8(out).unwrap();
+    assert!(report.starts_with("Ancora nessuna partita registrata.\n"));
+    assert!(report.contains("Partite: 1, vinte 1 (100%)"), "{}", report);
+    assert!(
+        report.contains("Tentativi per vincere: media 1.0"),
+        "{}",
+        report
+    );
+
+    let mut out = Vec::new();
+    scores.write_top(10, &mut out).unwrap();
+    scores.add(ScoreEntry::new("alice", &game, "normal", Some(7)));
+    scores.write_top(10, &mut out).unwrap();
+    let top = String::from_utf8(out).unwrap();
+    assert!(top.starts_with("Ancora nessun punteggio.\n"));
+    // one attempt, in the singular
+    assert!(top.contains("  1 tentativo  seme 7"), "{}", top);
+}
+
+#[test]
+fn replays_follow_the_language() {
+    let replay = Replay::record(&won_game());
+    let _italian = Italian::set();
+    let mut out = Vec::new();
+    replay.play_back(0, &mut out).unwrap();
+    let played = String::from_utf8(out).unwrap();
+    assert!(
+        played.starts_with("Replay di una partita tra 1 e 100"),
+        "{}",
+        played
+    );
+    assert!(played.contains("42 giusto"), "{}", played);
+    assert!(
+        played.contains("Esito della partita: vinta, dopo 1 tentativo e"),
+        "{}",
+        played
+    );
+    let difference = Difference::Ending {
+        recorded: Ending::Won,
+        current: Ending::GaveUp,
+    };
+    assert_eq!(
+        difference.to_string(),
+        "la partita era vinta, ma adesso è abbandonata"
+    );
+}
+
+#[test]
+fn file_errors_follow_the_language() {
+    let path = env::temp_dir().join(format!("i18n-file-errors-{}", std::process::id()));
+    let _italian = Italian::set();
+
+    fs::write(&path, "{}").unwrap();
+    let err = Replay::load(&path).unwrap_err().to_string();
+    assert!(
+        err.ends_with("non è un replay (manca la versione del formato)"),
+        "{}",
+        err
+    );
+    let newer = save::FORMAT_VERSION + 1;
+    fs::write(&path, format!(r#"{{ "version": {} }}"#, newer)).unwrap();
+    let err = save::load(&path).unwrap_err().to_string();
+    assert!(
+        err.ends_with(&format!(
+            "salvata con la versione {} del formato, ma si leggono solo le versioni da {} a {}",
+            newer,
+            save::OLDEST_VERSION,
+            save::FORMAT_VERSION
+        )),
+        "{}",
+        err
+    );
+    let mut game = Game::with_secret(1..=100, 42);
+    game.guess(50).unwrap();
+    save::save(&game, &path).unwrap();
+    let edited = fs::read_to_string(&path)
+        .unwrap()
+        .replace("\"secret_number\": 42", "\"secret_number\": 500");
+    fs::write(&path, edited).unwrap();
+    let err = save::load(&path).unwrap_err().to_string();
+    assert!(
+        err.ends_with("danneggiato (il numero segreto 500 non è tra 1 e 100)"),
+        "{}",
+        err
+    );
+
+    fs::write(&path, "50\ncinquanta\n").unwrap();
+    let err = ScriptedPlayer::from_file(&path).err().unwrap();
+    assert_eq!(err.to_string(), "riga 2: 'cinquanta' non è un tentativo");
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn the_server_follows_the_language() {
+    let server = env!("CARGO_BIN_EXE_guess-server");
+    let mut child = Command::new(server)
+        .args(["--lang", "it", "--addr", "127.0.0.1:0"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut line = String::new();
+    BufReader::new(child.stdout.take().unwrap())
+        .read_line(&mut line)
+        .unwrap();
+    child.kill().unwrap();
+    child.wait().unwrap();
+    assert!(line.starts_with("In ascolto su 127.0.0.1:"), "{}", line);
+
+    // the address is taken
+    let taken = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = taken.local_addr().unwrap().to_string();
+    let output = Command::new(server)
+        .args(["--lang", "it", "--addr", &addr])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    let err = String::from_utf8(output.stderr).unwrap();
+    assert!(
+        err.starts_with(&format!("Impossibile ascoltare su {}: ", addr)),
+        "{}",
+        err
+    );
+}
+
+/// Plays the codes it is given, in order.
+struct ScriptedBreaker(Vec<Code>);
+
+impl CodeBreaker for ScriptedBreaker {
+    fn next_guess(&mut self, history: &[CodeAttempt]) -> io::Result<Option<Code>> {
+        Ok(self.0.get(history.len()).cloned())
+    }
+}
+
+#[test]
+fn mastermind_follows_the_language() {
+    let rules = CodeRules::new(2, "123", false).unwrap();
+    let code = |text: &str| Code(text.chars().collect());
+    let mut game = CodeGame::with_secret(rules.clone(), code("12"));
+    let mut breaker = ScriptedBreaker(vec![code("21"), code("13"), code("12")]);
+    let _italian = Italian::set();
+
+    assert_eq!(rules.to_string(), "2 simboli tra 123, tutti diversi");
+    assert_eq!(
+        CodeRules::new(1, "123", true).unwrap().to_string(),
+        "1 simbolo tra 123"
+    );
+    assert_eq!(
+        rules.parse("1").unwrap_err().to_string(),
+        "il codice ha 2 simboli, non 1"
+    );
+
+    let mut out = Vec::new();
+    mastermind::play(&mut game, &mut breaker, &mut out).unwrap();
+    let played = String::from_utf8(out).unwrap();
+    assert_eq!(
+        played,
+        "Hai provato: 21\n0 tori, 2 mucche\nHai provato: 13\n1 toro, 0 mucche\n\
+         Hai provato: 12\nHai vinto!\n"
+    );
+}
+
+#[test]
+fn wordle_follows_the_language() {
+    let words = WordList::parse("casa\ncane\nmare").unwrap();
+    let mut game = WordGame::with_secret(words, "casa".to_string());
+    let _italian = Italian::set();
+    let mut out = Vec::new();
+    wordle::play(&mut game, &mut "ca\ncosa\n/giveup\n".as_bytes(), &mut out).unwrap();
+    let played = String::from_utf8(out).unwrap();
+    assert!(
+        played.contains("Inserisci una parola dell'elenco: la parola ha 4 lettere, non 2"),
+        "{}",
+        played
+    );
+    assert!(
+        played.contains("'cosa' non è nell'elenco di parole"),
+        "{}",
+        played
+    );
+    assert!(
+        played.ends_with("Hai rinunciato!\nLa parola segreta era CASA.\n"),
+        "{}",
+        played
+    );
+}
+
+#[test]
+fn the_client_follows_the_language() {
+    let config = ServerConfig {
+        range: 1..=100,
+        max_attempts: Some(1),
+        time_limit: None,
+        idle_timeout: Duration::from_secs(30),
+    };
+    let server = Server::bind("127.0.0.1:0", config, StdRng::seed_from_u64(1)).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+
+    let mut client = Client::connect(addr).unwrap();
+    let _italian = Italian::set();
+    let mut out = Vec::new();
+    client::play(&mut client, &mut ScriptedPlayer::new(vec![50]), &mut out).unwrap();
+    let played = String::from_utf8(out).unwrap();
+    assert!(played.starts_with("Hai provato: 50\n"), "{}", played);
+    assert!(
+        played.ends_with("Hai vinto!\n") || played.contains("Il numero segreto era"),
+        "{}",
+        played
+    );
+}
@@ -0,0 +1,130 @@
+//! The game loop of `player::play`, driven without a terminal.
+
+use guessing_game::player::{self, ScriptedPlayer, StdinPlayer};
+use guessing_game::{FeedbackMode, Game, Loss, Status};
+use std::fs;
+use std::io::{self, BufReader};
+use std::time::{Duration, Instant};
+
+/// What `play` writes for `game` and `player`.
+fn transcript<P: guessing_game::Player>(game: &mut Game, player: &mut P) -> String {
+    let mut out = Vec::new();
+    player::play(game, player, &mut out).unwrap();
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn a_script_plays_until_the_win() {
+    let mut game = Game::with_secret(1..=100, 42);
+    let mut player = ScriptedPlayer::new(vec![50, 25, 42, 99]);
+    let out = transcript(&mut game, &mut player);
+    assert_eq!(
+        out,
+        "You guessed: 50\nToo big!\n\
+         You guessed: 25\nToo small!\n\
+         You guessed: 42\nYou win!\n"
+    );
+    // the guess after the win is not played
+    assert_eq!(game.attempts(), 3);
+    assert_eq!(game.status(), Status::Won);
+}
+
+#[test]
+fn a_script_can_run_out_of_attempts() {
+    let mut game = Game::with_secret(1..=100, 42).with_max_attempts(Some(2));
+    let mut player = ScriptedPlayer::new(vec![1, 2, 42]);
+    let out = transcript(&mut game, &mut player);
+    assert_eq!(
+        out,
+        "You guessed: 1\nToo small!\n\
+         You guessed: 2\nToo small!\n\
+         No attempts left!\nThe secret number was 42.\n"
+    );
+    assert_eq!(game.status(), Status::Lost(Loss::OutOfAttempts));
+}
+
+#[test]
+fn a_refused_guess_is_reported_and_not_counted() {
+    let mut game = Game::with_secret(1..=100, 42).with_feedback(FeedbackMode::Temperature);
+    let mut player = ScriptedPlayer::new(vec![500, 40]);
+    let out = transcript(&mut game, &mut player);
+    assert_eq!(
+        out,
+        "You guessed: 500\n\
+         500 is out of range, the secret number is between 1 and 100\n\
+         You guessed: 40\nHot! Too small!\n"
+    );
+    // the script ended before the secret number was found
+    assert_eq!(game.attempts(), 1);
+    assert_eq!(game.status(), Status::Playing);
+}
+
+#[test]
+fn a_script_file_skips_comments_and_blank_lines() {
+    let path = std::env::temp_dir().join(format!("guesses-{}.txt", std::process::id()));
+    fs::write(&path, "# the first guesses\n50\n\n  42  \n").unwrap();
+    let mut player = ScriptedPlayer::from_file(&path).unwrap();
+    let mut game = Game::with_secret(1..=100, 42);
+    let out = transcript(&mut game, &mut player);
+    assert!(out.ends_with("You guessed: 42\nYou win!\n"), "{}", out);
+    assert_eq!(game.attempts(), 2);
+
+    fs::write(&path, "50\nfifty\n").unwrap();
+    let err = ScriptedPlayer::from_file(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "line 2: 'fifty' is not a guess");
+}
+
+#[test]
+fn the_time_runs_out_at_the_prompt() {
+    // nothing is ever typed, but the writer keeps the input open
+    let (reader, _writer) = io::pipe().unwrap();
+    let mut player = StdinPlayer::from_reader(BufReader::new(reader));
+    let mut game = Game::with_secret(1..=100, 42)
+        .with_time_limit(Some(Duration::from_secs(10)))
+        .with_elapsed(Duration::from_millis(9_800));
+    let start = Instant::now();
+    let out = transcript(&mut game, &mut player);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(game.status(), Status::Lost(Loss::OutOfTime));
+    assert_eq!(out, "Time is up!\nThe secret number was 42.\n");
+}
+
+#[test]
+fn the_guesses_typed_in_time_are_played() {
+    let mut player = StdinPlayer::from_reader(&b"50\n42\n"[..]);
+    let mut game = Game::with_secret(1..=100, 42).with_time_limit(Some(Duration::from_secs(60)));
+    let out = transcript(&mut game, &mut player);
+    assert_eq!(game.status(), Status::Won);
+    assert_eq!(game.attempts(), 2);
+    assert!(out.ends_with("You guessed: 42\nYou win!\n"), "{}", out);
+}
+
+#[test]
+fn a_line_that_is_not_utf8_is_not_a_guess() {
+    let mut player = StdinPlayer::from_reader(&b"\xff\xfe\n42\n"[..]);
+    let mut game = Game::with_secret(1..=100, 42);
+    let out = transcript(&mut game, &mut player);
+    assert_eq!(out, "You guessed: 42\nYou win!\n");
+    assert_eq!(game.attempts(), 1);
+}
+
+/// An input that always fails, like a terminal that went away.
+struct Broken;
+
+impl io::Read for Broken {
+    fn read(&mut self, _buf: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::other("the terminal went away"))
+    }
+}
+
+#[test]
+fn the_player_stops_when_the_input_fails() {
+    let mut player = StdinPlayer::from_reader(BufReader::new(Broken));
+    let mut game = Game::with_secret(1..=100, 42);
+    let out = transcript(&mut game, &mut player);
+    assert_eq!(out, "");
+    // the game is not lost, it can still be saved and resumed
+    assert_eq!(game.status(), Status::Playing);
+}
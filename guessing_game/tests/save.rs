@@ -0,0 +1,135 @@
+//! The versions of the formats of the saved games and of the replays.
+
+use guessing_game::replay::{self, Ending, Replay};
+use guessing_game::save;
+use guessing_game::{FeedbackMode, Game, Outcome};
+use std::fs;
+use std::path::PathBuf;
+
+/// A file of the temporary directory, unique to this process and `name`.
+fn temporary(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("{}-{}.json", name, std::process::id()))
+}
+
+#[test]
+fn a_game_is_saved_with_the_current_version() {
+    let path = temporary("saved-current");
+    let mut game = Game::with_secret(1..=100, 42).with_feedback(FeedbackMode::Temperature);
+    game.guess(50).unwrap();
+    save::save(&game, &path).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(json["version"], save::FORMAT_VERSION);
+
+    let loaded = save::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.attempts(), 1);
+    assert_eq!(loaded.feedback_mode(), FeedbackMode::Temperature);
+}
+
+#[test]
+fn a_game_saved_with_version_1_is_still_read() {
+    let path = temporary("saved-version-1");
+    let json = r#"{
+        "version": 1,
+        "seed": null,
+        "min": 1,
+        "max": 100,
+        "secret_number": 42,
+        "max_attempts": 7,
+        "time_limit": null,
+        "elapsed": 1500,
+        "history": [{ "guess": 50, "outcome": "too_big" }]
+    }"#;
+    fs::write(&path, json).unwrap();
+    let game = save::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(game.attempts(), 1);
+    assert_eq!(game.max_attempts(), Some(7));
+    assert_eq!(game.feedback_mode(), FeedbackMode::default());
+    assert!(game.hints_used().is_empty());
+    assert!(game.liar().is_none());
+    assert!(!game.secret().is_evil());
+}
+
+#[test]
+fn a_game_saved_with_a_newer_version_is_refused() {
+    let path = temporary("saved-newer");
+    let newer = save::FORMAT_VERSION + 1;
+    // a field this version does not know is not reported as corruption
+    let json = format!(
+        r#"{{ "version": {}, "min": 1, "max": 100, "secret_number": 42, "shiny": true }}"#,
+        newer
+    );
+    fs::write(&path, json).unwrap();
+    let err = save::load(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    let expected = format!(
+        "saved with format version {}, but only versions {} to {} can be read",
+        newer,
+        save::OLDEST_VERSION,
+        save::FORMAT_VERSION
+    );
+    assert!(err.to_string().ends_with(&expected), "{}", err);
+}
+
+#[test]
+fn a_replay_is_recorded_with_the_current_version() {
+    let path = temporary("replay-current");
+    let mut game = Game::with_secret(1..=100, 42);
+    game.guess(42).unwrap();
+    let recorded = Replay::record(&game);
+    assert_eq!(recorded.version, replay::FORMAT_VERSION);
+    recorded.save(&path).unwrap();
+    let loaded = Replay::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, recorded);
+}
+
+#[test]
+fn a_replay_recorded_with_version_1_is_still_read() {
+    let path = temporary("replay-version-1");
+    let json = r#"{
+        "version": 1,
+        "seed": null,
+        "min": 1,
+        "max": 100,
+        "secret_number": 42,
+        "max_attempts": null,
+        "time_limit": null,
+        "guesses": [{ "at": 800, "guess": 42, "outcome": "win" }],
+        "duration": 900,
+        "ending": "won"
+    }"#;
+    fs::write(&path, json).unwrap();
+    let replay = Replay::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(replay.version, 1);
+    assert_eq!(replay.guesses[0].outcome, Outcome::Win);
+    assert_eq!(replay.ending, Ending::Won);
+    assert_eq!(replay.feedback, FeedbackMode::default());
+    assert!(replay.hints.is_empty());
+    assert!(replay.liar.is_none());
+    assert!(!replay.evil);
+    assert!(replay.play_back(0, &mut Vec::new()).unwrap().is_empty());
+}
+
+#[test]
+fn a_replay_recorded_with_a_newer_version_is_refused() {
+    let path = temporary("replay-newer");
+    let newer = replay::FORMAT_VERSION + 1;
+    fs::write(
+        &path,
+        format!(r#"{{ "version": {}, "shiny": true }}"#, newer),
+    )
+    .unwrap();
+    let err = Replay::load(&path).unwrap_err();
+    fs::remove_file(&path).unwrap();
+    let expected = format!(
+        "recorded with format version {}, but only versions {} to {} can be read",
+        newer,
+        replay::OLDEST_VERSION,
+        replay::FORMAT_VERSION
+    );
+    assert!(err.to_string().ends_with(&expected), "{}", err);
+}
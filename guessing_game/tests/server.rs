@@ -0,0 +1,188 @@
+//! The line protocol of `guess-server`, through a server listening on an
+//! ephemeral local port.
+
+use guessing_game::server::{Server, ServerConfig};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::thread;
+use std::time::{Duration, Instant};
+
+fn config() -> ServerConfig {
+    ServerConfig {
+        range: 1..=100,
+        max_attempts: Some(7),
+        time_limit: None,
+        idle_timeout: Duration::from_secs(30),
+    }
+}
+
+fn start_server(config: ServerConfig) -> SocketAddr {
+    let server = Server::bind("127.0.0.1:0", config, StdRng::seed_from_u64(1)).unwrap();
+    let addr = server.local_addr().unwrap();
+    thread::spawn(move || server.run());
+    addr
+}
+
+/// A client speaking the protocol one line at a time.
+struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    fn connect(addr: SocketAddr) -> Client {
+        let stream = TcpStream::connect(addr).unwrap();
+        // a server that does not answer fails the test instead of hanging it
+        stream
+            .set_read_timeout(Some(Duration::from_secs(10)))
+            .unwrap();
+        Client {
+            reader: BufReader::new(stream.try_clone().unwrap()),
+            writer: stream,
+        }
+    }
+
+    fn send(&mut self, line: &str) {
+        writeln!(self.writer, "{}", line).unwrap();
+    }
+
+    /// The next line of the server, without its newline; empty once the
+    /// server closed the connection.
+    fn receive(&mut self) -> String {
+        let mut line = String::new();
+        self.reader.read_line(&mut line).unwrap();
+        line.trim_end().to_string()
+    }
+
+    fn ask(&mut self, line: &str) -> String {
+        self.send(line);
+        self.receive()
+    }
+
+    /// Plays a binary search on 1..=100 and returns the secret number; the
+    /// answers of the server go through `answer`.
+    fn find_secret(&mut self, mut answer: impl FnMut(&mut Client, u32) -> String) -> u32 {
+        let (mut low, mut high) = (1, 100);
+        loop {
+            let guess = low + (high - low) / 2;
+            let reply = answer(self, guess);
+            match reply.split_whitespace().next() {
+                Some("LESS") => low = guess + 1,
+                Some("GREATER") => high = guess - 1,
+                Some("EQUAL") => return guess,
+                _ => panic!("unexpected answer to {}: {}", guess, reply),
+            }
+        }
+    }
+}
+
+#[test]
+fn a_scripted_game_over_the_protocol() {
+    let addr = start_server(config());
+    let mut client = Client::connect(addr);
+    assert_eq!(client.receive(), "WELCOME 1 100 7");
+
+    let mut attempts = 0;
+    client.find_secret(|client, guess| {
+        attempts += 1;
+        let reply = client.ask(&format!("GUESS {}", guess));
+        // every answer counts the attempts used so far
+        assert!(reply.ends_with(&format!(" {}", attempts)), "{}", reply);
+        reply
+    });
+    assert!(attempts <= 7);
+
+    // the game is over until NEW
+    assert!(client.ask("GUESS 50").starts_with("ERR "));
+    assert_eq!(client.ask("NEW"), "WELCOME 1 100 7");
+    assert!(client.ask("GUESS 500").starts_with("ERR "));
+    assert!(client.ask("HELLO").starts_with("ERR "));
+    assert!(client.ask("GUESS fifty").starts_with("ERR "));
+    assert_eq!(client.ask("GUESS 1").split_whitespace().nth(1), Some("1"));
+    assert_eq!(client.ask("QUIT"), "BYE quit");
+    assert_eq!(client.receive(), "");
+}
+
+#[test]
+fn the_last_attempt_reveals_the_secret_number() {
+    let addr = start_server(ServerConfig {
+        max_attempts: Some(1),
+        ..config()
+    });
+    let mut client = Client::connect(addr);
+    assert_eq!(client.receive(), "WELCOME 1 100 1");
+    // 1 is the secret number once in a hundred games
+    while client.ask("GUESS 1") == "EQUAL 1" {
+        assert_eq!(client.ask("NEW"), "WELCOME 1 100 1");
+    }
+    let lost = client.receive();
+    let words: Vec<&str> = lost.split_whitespace().collect();
+    assert_eq!(words.len(), 3, "{}", lost);
+    assert_eq!((words[0], words[2]), ("LOST", "attempts"));
+    let secret: u32 = words[1].parse().unwrap();
+    assert!((2..=100).contains(&secret));
+    assert!(client.ask("GUESS 2").starts_with("ERR "));
+}
+
+#[test]
+fn an_idle_connection_is_closed() {
+    let addr = start_server(ServerConfig {
+        idle_timeout: Duration::from_millis(200),
+        ..config()
+    });
+    let mut client = Client::connect(addr);
+    assert_eq!(client.receive(), "WELCOME 1 100 7");
+    // a request resets the timeout
+    thread::sleep(Duration::from_millis(100));
+    assert!(client.ask("GUESS 50").ends_with(" 1"));
+
+    let start = Instant::now();
+    assert_eq!(client.receive(), "BYE idle");
+    assert!(start.elapsed() >= Duration::from_millis(150));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert_eq!(client.receive(), "");
+}
+
+#[test]
+fn two_players_race_in_one_room() {
+    let addr = start_server(config());
+    let mut alice = Client::connect(addr);
+    let mut bob = Client::connect(addr);
+    assert_eq!(alice.receive(), "WELCOME 1 100 7");
+    assert_eq!(bob.receive(), "WELCOME 1 100 7");
+
+    assert_eq!(alice.ask("JOIN lan alice"), "JOINED lan 1");
+    assert_eq!(alice.receive(), "WELCOME 1 100 7");
+    assert_eq!(
+        bob.ask("JOIN lan alice"),
+        "ERR alice is already in the room"
+    );
+    assert_eq!(bob.ask("JOIN lan bob"), "JOINED lan 2");
+    assert_eq!(bob.receive(), "WELCOME 1 100 7");
+    assert_eq!(alice.receive(), "JOINED lan 2");
+
+    // every guess of bob is a hint for alice, until bob finds the secret number
+    let mut attempts = 0;
+    bob.find_secret(|bob, guess| {
+        attempts += 1;
+        let answer = bob.ask(&format!("GUESS {}", guess));
+        let outcome = answer.split_whitespace().next().unwrap().to_string();
+        assert_eq!(answer, format!("{} {}", outcome, attempts));
+        assert_eq!(alice.receive(), format!("HINT bob {} {}", guess, outcome));
+        answer
+    });
+
+    let results = vec![
+        format!("WINNER bob {}", attempts),
+        format!("STANDING 1 bob {} 0", attempts),
+        "STANDING 2 alice 0 -".to_string(),
+        "END".to_string(),
+    ];
+    for client in &mut [alice, bob] {
+        for line in &results {
+            assert_eq!(&client.receive(), line);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+//! The full-screen interface drawn on ratatui's `TestBackend`, with the keys
+//! sent straight to the `App`.
+
+use guessing_game::tui::{self, App};
+use guessing_game::{Game, Loss, Status};
+use ratatui::backend::TestBackend;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Terminal;
+use std::time::Duration;
+
+fn press(app: &mut App, code: KeyCode) {
+    app.handle_key(KeyEvent::new(code, KeyModifiers::NONE));
+}
+
+fn type_line(app: &mut App, text: &str) {
+    for c in text.chars() {
+        press(app, KeyCode::Char(c));
+    }
+    press(app, KeyCode::Enter);
+}
+
+/// The screen as text, one line per row.
+fn screen(app: &App) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(60, 16)).unwrap();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let buffer = terminal.backend().buffer();
+    let width = buffer.area.width as usize;
+    buffer
+        .content()
+        .chunks(width)
+        .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn guesses_fill_the_history_and_shrink_the_bar() {
+    let mut game = Game::with_secret(1..=100, 42).with_max_attempts(Some(7));
+    let mut app = App::new(&mut game);
+    assert!(!screen(&app).contains('░'));
+
+    type_line(&mut app, "50");
+    type_line(&mut app, "25");
+    let screen = screen(&app);
+    assert!(screen.contains("Still possible: 26 to 49"), "{}", screen);
+    assert!(screen.contains("1.         50  Too big!"), "{}", screen);
+    assert!(screen.contains("2.         25  Too small!"), "{}", screen);
+    assert!(screen.contains("Attempts: 2 of 7"), "{}", screen);
+    assert!(screen.contains("Attempts left: 5"), "{}", screen);
+    assert!(screen.contains('░') && screen.contains('█'), "{}", screen);
+    assert_eq!(app.message(), "25: Too small!");
+}
+
+#[test]
+fn keys_are_validated_as_they_are_typed() {
+    let mut game = Game::with_secret(1..=100, 42);
+    let mut app = App::new(&mut game);
+
+    press(&mut app, KeyCode::Char('x'));
+    assert_eq!(app.input(), "");
+    assert_eq!(app.message(), "'x' is not a digit.");
+
+    for c in "1000".chars() {
+        press(&mut app, KeyCode::Char(c));
+    }
+    assert_eq!(app.input(), "100");
+    assert_eq!(
+        app.message(),
+        "1000 is bigger than 100, the end of the range."
+    );
+
+    press(&mut app, KeyCode::Backspace);
+    assert_eq!(app.input(), "10");
+    assert!(screen(&app).contains("> 10"));
+
+    press(&mut app, KeyCode::Backspace);
+    press(&mut app, KeyCode::Backspace);
+    press(&mut app, KeyCode::Enter);
+    assert_eq!(app.message(), "Type a number first.");
+    assert_eq!(app.game().attempts(), 0);
+}
+
+#[test]
+fn a_finished_game_waits_for_a_key() {
+    let mut game = Game::with_secret(1..=10, 7);
+    let mut app = App::new(&mut game);
+    type_line(&mut app, "7");
+    assert_eq!(app.game().status(), Status::Won);
+    assert!(screen(&app).contains("You win! Found in 1 attempt."));
+    assert!(!app.is_done());
+
+    press(&mut app, KeyCode::Char('q'));
+    assert!(app.is_done());
+}
+
+#[test]
+fn the_timer_ends_the_game() {
+    let mut game = Game::with_secret(1..=100, 42).with_time_limit(Some(Duration::from_secs(0)));
+    let mut app = App::new(&mut game);
+    assert!(screen(&app).contains("Time left: 0:00"));
+
+    app.tick();
+    assert_eq!(app.game().status(), Status::Lost(Loss::OutOfTime));
+    assert!(screen(&app).contains("Time is up! The secret number was 42."));
+}
+
+#[test]
+fn escape_leaves_the_game_as_it_is() {
+    let mut game = Game::with_secret(1..=100, 42);
+    let mut app = App::new(&mut game);
+    type_line(&mut app, "50");
+    press(&mut app, KeyCode::Esc);
+    assert!(app.is_done());
+    assert_eq!(game.status(), Status::Playing);
+    assert_eq!(game.attempts(), 1);
+}
+
+#[test]
+fn the_number_line_covers_the_range() {
+    assert_eq!(tui::number_line(&(1..=100), &(1..=100), 10), "██████████");
+    assert_eq!(tui::number_line(&(1..=100), &(51..=70), 10), "░░░░░██░░░");
+    // a range smaller than the bar
+    assert_eq!(tui::number_line(&(1..=4), &(2..=2), 8), "░░██░░░░");
+    assert_eq!(tui::number_line(&(0..=u32::MAX), &(0..=0), 4), "█░░░");
+}